@@ -0,0 +1,237 @@
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use reqwest::{header, Client, StatusCode};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use crate::jobs::CancellationToken;
+
+/// A download target under the system temp dir that is deleted when dropped,
+/// so failed or cancelled installs do not leave large archives behind.
+pub struct TempDownload {
+    path: PathBuf,
+    keep_partial: bool,
+}
+
+impl TempDownload {
+    pub fn new(file_name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("rtxlauncher-{}-{}", std::process::id(), file_name));
+        Self { path, keep_partial: false }
+    }
+
+    /// A stable path that survives failed or cancelled downloads so the next attempt can resume it.
+    /// Call [`TempDownload::complete`] once the download finished to have it removed on drop again.
+    pub fn resumable(file_name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("rtxlauncher-{}.part", file_name.replace(['/', '\\'], "_")));
+        Self { path, keep_partial: true }
+    }
+
+    pub fn complete(&mut self) { self.keep_partial = false; }
+
+    pub fn path(&self) -> &Path { &self.path }
+}
+
+impl Drop for TempDownload {
+    fn drop(&mut self) {
+        if !self.keep_partial { let _ = fs::remove_file(&self.path); }
+    }
+}
+
+/// Byte counts passed to the `download_to_file` callback.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    /// 0 when the server does not send a content length
+    pub total: u64,
+    /// Bytes already on disk from an earlier attempt; 0 for a fresh download
+    pub resumed_from: u64,
+}
+
+/// What `download_to_file` reports while it runs.
+#[derive(Debug, Clone)]
+pub enum DownloadEvent {
+    Progress(DownloadProgress),
+    /// A transient failure; attempt `attempt` of `attempts` starts after a backoff delay
+    Retrying { attempt: u32, attempts: u32, error: String },
+}
+
+/// Attempts made for a download before giving up.
+pub(crate) const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Backoff before attempt `attempt` (2-based): 1s, 2s, 4s, ...
+pub(crate) fn retry_delay(attempt: u32) -> std::time::Duration {
+    std::time::Duration::from_secs(1 << attempt.saturating_sub(2).min(5))
+}
+
+/// Network hiccups and server-side errors are worth retrying; cancellation, client errors
+/// (404, bad token) and local disk errors are not.
+pub(crate) fn is_transient(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<reqwest::Error>() {
+        Some(e) => match e.status() {
+            Some(s) => s.is_server_error() || s == StatusCode::TOO_MANY_REQUESTS,
+            None => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() || e.is_decode(),
+        },
+        None => false,
+    }
+}
+
+/// Smoothed transfer rate for progress messages. Samples are taken at most every
+/// `min_interval`, matching the progress throttle, and blended so the ETA does not jump around.
+pub struct RateMeter {
+    sample: Option<(std::time::Instant, u64)>,
+    min_interval: std::time::Duration,
+    bytes_per_sec: f64,
+}
+
+impl RateMeter {
+    pub fn new(min_interval_ms: u64) -> Self {
+        Self { sample: None, min_interval: std::time::Duration::from_millis(min_interval_ms), bytes_per_sec: 0.0 }
+    }
+
+    /// Record the running byte count; the first call only sets the baseline so resumed bytes are not counted.
+    pub fn update(&mut self, downloaded: u64) {
+        let now = std::time::Instant::now();
+        let Some((at, bytes)) = self.sample else { self.sample = Some((now, downloaded)); return; };
+        let dt = now.duration_since(at);
+        if dt < self.min_interval { return; }
+        let current = downloaded.saturating_sub(bytes) as f64 / dt.as_secs_f64();
+        self.bytes_per_sec = if self.bytes_per_sec == 0.0 { current } else { self.bytes_per_sec * 0.7 + current * 0.3 };
+        self.sample = Some((now, downloaded));
+    }
+
+    /// " (12.3 MB/s, ~45s left)", or empty until a rate is known.
+    pub fn suffix(&self, downloaded: u64, total: u64) -> String {
+        format_rate(self.bytes_per_sec, total.saturating_sub(downloaded), total > 0)
+    }
+}
+
+fn format_rate(bytes_per_sec: f64, remaining: u64, known_total: bool) -> String {
+    if bytes_per_sec <= 0.0 { return String::new(); }
+    let speed = format!("{:.1} MB/s", bytes_per_sec / 1_048_576.0);
+    if !known_total { return format!(" ({})", speed); }
+    let secs = (remaining as f64 / bytes_per_sec).ceil() as u64;
+    let eta = if secs >= 60 { format!("{}m {}s", secs / 60, secs % 60) } else { format!("{}s", secs) };
+    format!(" ({}, ~{} left)", speed, eta)
+}
+
+/// Stream `url` to `dest` chunk by chunk. If `dest` already holds a partial download, a
+/// `Range` request continues it; servers that answer with a full `200` restart from zero.
+/// Transient failures are retried with exponential backoff, resuming from what was written.
+pub async fn download_to_file(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    cancel: &CancellationToken,
+    mut on_event: impl FnMut(DownloadEvent),
+) -> Result<u64> {
+    let mut attempt = 1;
+    loop {
+        match download_attempt(client, url, dest, cancel, &mut on_event).await {
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS && is_transient(&e) => {
+                attempt += 1;
+                tracing::warn!("Download of {} failed: {:#}; retrying ({}/{})", url, e, attempt, DOWNLOAD_ATTEMPTS);
+                on_event(DownloadEvent::Retrying { attempt, attempts: DOWNLOAD_ATTEMPTS, error: e.to_string() });
+                tokio::time::sleep(retry_delay(attempt)).await;
+                cancel.check()?;
+            }
+            r => return r,
+        }
+    }
+}
+
+async fn download_attempt(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    cancel: &CancellationToken,
+    on_event: &mut impl FnMut(DownloadEvent),
+) -> Result<u64> {
+    let mut existing = fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+    let resp = loop {
+        let mut req = client.get(url).header("User-Agent", "RTXLauncher-RS");
+        if existing > 0 { req = req.header(header::RANGE, format!("bytes={}-", existing)); }
+        let resp = req.send().await?;
+        let usable = match resp.status() {
+            StatusCode::RANGE_NOT_SATISFIABLE => false,
+            StatusCode::PARTIAL_CONTENT => content_range_start(&resp) == Some(existing),
+            _ => true,
+        };
+        if existing > 0 && !usable {
+            // The partial file does not line up with what the server has; start over
+            tracing::warn!("Discarding partial download {} ({} bytes)", dest.display(), existing);
+            let _ = fs::remove_file(dest);
+            existing = 0;
+            continue;
+        }
+        break resp.error_for_status()?;
+    };
+    let resumed_from = if resp.status() == StatusCode::PARTIAL_CONTENT { existing } else { 0 };
+    let total = resp.content_length().map(|len| len + resumed_from).unwrap_or(0);
+    let file = if resumed_from > 0 {
+        OpenOptions::new().append(true).open(dest)
+    } else {
+        File::create(dest)
+    }.with_context(|| format!("create {}", dest.display()))?;
+    let mut out = BufWriter::new(file);
+    let mut bytes = resp.bytes_stream();
+    let mut downloaded = resumed_from;
+    while let Some(chunk_res) = bytes.next().await {
+        cancel.check()?;
+        let chunk = chunk_res?;
+        out.write_all(&chunk).with_context(|| format!("write {}", dest.display()))?;
+        downloaded += chunk.len() as u64;
+        on_event(DownloadEvent::Progress(DownloadProgress { downloaded, total, resumed_from }));
+    }
+    out.flush()?;
+    Ok(downloaded)
+}
+
+// Start offset from a "Content-Range: bytes <start>-<end>/<size>" header
+fn content_range_start(resp: &reqwest::Response) -> Option<u64> {
+    let value = resp.headers().get(header::CONTENT_RANGE)?.to_str().ok()?;
+    value.strip_prefix("bytes ")?.split('-').next()?.trim().parse().ok()
+}
+
+/// Lowercase hex SHA-256 of a file on disk, read in chunks.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+    let mut f = File::open(path).with_context(|| format!("open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 16];
+    loop {
+        let n = f.read(&mut buf)?;
+        if n == 0 { break; }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Extract the digest from a `.sha256` manifest. Accepts a bare hash or `sha256sum` output ("<hash>  <file>").
+pub fn parse_sha256_manifest(text: &str) -> Option<String> {
+    let token = text.split_whitespace().next()?;
+    let token = token.trim_start_matches('\u{feff}');
+    if token.len() == 64 && token.chars().all(|c| c.is_ascii_hexdigit()) { Some(token.to_ascii_lowercase()) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_rate, parse_sha256_manifest};
+
+    #[test]
+    fn rate_suffix() {
+        assert_eq!(format_rate(0.0, 100, true), "");
+        assert_eq!(format_rate(2.0 * 1_048_576.0, 90 * 1_048_576, true), " (2.0 MB/s, ~45s left)");
+        assert_eq!(format_rate(1_048_576.0, 125 * 1_048_576, true), " (1.0 MB/s, ~2m 5s left)");
+        assert_eq!(format_rate(1_048_576.0, 0, false), " (1.0 MB/s)");
+    }
+
+    #[test]
+    fn parse_manifest_formats() {
+        let h = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        assert_eq!(parse_sha256_manifest(h), Some(h.to_ascii_lowercase()));
+        assert_eq!(parse_sha256_manifest(&format!("{}  remix-release.zip\n", h)), Some(h.to_ascii_lowercase()));
+        assert_eq!(parse_sha256_manifest("not-a-hash remix.zip"), None);
+        assert_eq!(parse_sha256_manifest(""), None);
+    }
+}
@@ -0,0 +1,140 @@
+//! Typed errors for the public install/update API so callers can react to the kind of
+//! failure (and show a hint) instead of matching on message text.
+use reqwest::StatusCode;
+
+use crate::jobs::Cancelled;
+
+pub type LauncherResult<T> = std::result::Result<T, LauncherError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LauncherError {
+    /// Connection, DNS, TLS or timeout failure before a response arrived.
+    #[error("network error: {0}")]
+    Network(String),
+    /// The server answered with a non-success status.
+    #[error("server returned {0}")]
+    Http(StatusCode),
+    /// GitHub refused the request because the API quota is used up.
+    #[error("GitHub API rate limit exceeded")]
+    RateLimited { reset_unix: i64 },
+    #[error("invalid archive: {0}")]
+    Zip(String),
+    #[error("{message}")]
+    Io { kind: std::io::ErrorKind, message: String },
+    #[error("could not parse {0}")]
+    Parse(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// The release has no asset the installer knows how to use.
+    #[error("this release has no installable asset ({0})")]
+    NoAsset(String),
+    /// Another job that writes into the install holds the job lock.
+    #[error("{0} is already running")]
+    Busy(String),
+    #[error("operation cancelled")]
+    Cancelled,
+    #[error("{0:#}")]
+    Other(anyhow::Error),
+}
+
+impl LauncherError {
+    pub fn is_cancelled(&self) -> bool { matches!(self, LauncherError::Cancelled) }
+
+    /// Something the user can do about this error, if there is anything obvious.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            LauncherError::RateLimited { .. } => Some("Set a GitHub personal access token in Settings to raise the API rate limit."),
+            LauncherError::Http(s) if *s == StatusCode::UNAUTHORIZED => Some("The GitHub token was rejected; update or clear it in Settings."),
+            LauncherError::Http(s) if *s == StatusCode::FORBIDDEN || *s == StatusCode::TOO_MANY_REQUESTS => {
+                Some("GitHub may be rate limiting this machine; set a personal access token in Settings or try again later.")
+            }
+            LauncherError::Http(s) if *s == StatusCode::NOT_FOUND => Some("The release or file no longer exists; refresh the release list."),
+            LauncherError::Http(s) if s.is_server_error() => Some("The server is having problems; try again in a few minutes."),
+            LauncherError::Network(_) => Some("Check your internet connection, proxy, or the GitHub API base in Settings."),
+            LauncherError::Zip(_) => Some("The download may be corrupt; try installing again."),
+            LauncherError::Io { kind: std::io::ErrorKind::PermissionDenied, .. } => {
+                Some("Close the game and make sure the install folder is writable (or run the launcher as administrator).")
+            }
+            LauncherError::NotFound(_) => Some("Check the install folder in Settings."),
+            LauncherError::NoAsset(_) => Some("Pick another release (for RTX Remix you can also pick an asset under \"Advanced: choose asset\")."),
+            LauncherError::Busy(_) => Some("Wait for it to finish or cancel it, then try again."),
+            _ => None,
+        }
+    }
+}
+
+impl From<Cancelled> for LauncherError {
+    fn from(_: Cancelled) -> Self { LauncherError::Cancelled }
+}
+
+// Library errors that reach `?` directly go through the same classification
+macro_rules! from_via_anyhow {
+    ($($t:ty),*) => {$(
+        impl From<$t> for LauncherError {
+            fn from(e: $t) -> Self { anyhow::Error::from(e).into() }
+        }
+    )*};
+}
+from_via_anyhow!(std::io::Error, serde_json::Error, reqwest::Error, zip::result::ZipError);
+
+impl From<anyhow::Error> for LauncherError {
+    /// Classify by the first recognizable cause; the message keeps the whole context chain.
+    fn from(err: anyhow::Error) -> Self {
+        let message = format!("{:#}", err);
+        for cause in err.chain() {
+            if cause.is::<Cancelled>() { return LauncherError::Cancelled; }
+            if let Some(e) = cause.downcast_ref::<LauncherError>() {
+                return match e {
+                    LauncherError::Http(s) => LauncherError::Http(*s),
+                    LauncherError::RateLimited { reset_unix } => LauncherError::RateLimited { reset_unix: *reset_unix },
+                    LauncherError::Zip(_) => LauncherError::Zip(message),
+                    LauncherError::Io { kind, .. } => LauncherError::Io { kind: *kind, message },
+                    LauncherError::Parse(_) => LauncherError::Parse(message),
+                    LauncherError::NotFound(m) => LauncherError::NotFound(m.clone()),
+                    LauncherError::NoAsset(m) => LauncherError::NoAsset(m.clone()),
+                    LauncherError::Busy(m) => LauncherError::Busy(m.clone()),
+                    LauncherError::Cancelled => LauncherError::Cancelled,
+                    LauncherError::Network(_) => LauncherError::Network(message),
+                    LauncherError::Other(_) => break,
+                };
+            }
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                return match e.status() {
+                    Some(s) => LauncherError::Http(s),
+                    None if e.is_decode() => LauncherError::Parse(message),
+                    None => LauncherError::Network(message),
+                };
+            }
+            if cause.is::<zip::result::ZipError>() { return LauncherError::Zip(message); }
+            if cause.is::<serde_json::Error>() || cause.is::<toml::de::Error>() { return LauncherError::Parse(message); }
+            if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+                return match e.kind() {
+                    std::io::ErrorKind::NotFound => LauncherError::NotFound(message),
+                    kind => LauncherError::Io { kind, message },
+                };
+            }
+        }
+        LauncherError::Other(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn classifies_through_context() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        let err: LauncherError = Err::<(), _>(io).context("write bin/d3d9.dll").unwrap_err().into();
+        assert!(matches!(err, LauncherError::Io { kind: std::io::ErrorKind::PermissionDenied, .. }));
+        assert_eq!(err.to_string(), "write bin/d3d9.dll: denied");
+        assert!(err.hint().is_some());
+
+        let err: LauncherError = anyhow::Error::from(Cancelled).context("install").into();
+        assert!(err.is_cancelled());
+
+        let err: LauncherError = anyhow::Error::from(LauncherError::RateLimited { reset_unix: 0 }).into();
+        assert!(err.hint().unwrap().contains("personal access token"));
+    }
+}
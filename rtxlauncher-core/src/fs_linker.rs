@@ -1,71 +1,156 @@
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::jobs::CancellationToken;
 
 #[cfg(windows)]
 use std::os::windows::fs as winfs;
 
+/// How links back to the vanilla install are created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LinkStrategy {
+    /// Symlink, then junction (Windows), then fall back to copying
+    #[default]
+    Auto,
+    /// Fail instead of copying when no link can be made
+    SymlinkOnly,
+    /// Always copy; uses more disk but needs no special permissions
+    CopyOnly,
+}
+
+/// What `link_dir_best_effort` / `link_file_best_effort` ended up doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkMethod {
+    /// The destination already existed and was left alone
+    Existing,
+    Symlink,
+    Junction,
+    Copied { bytes: u64 },
+}
+
+fn is_link(dst: &Path) -> bool {
+    let symlink = fs::symlink_metadata(dst).map(|m| m.file_type().is_symlink()).unwrap_or(false);
+    #[cfg(windows)]
+    let symlink = symlink || junction::exists(dst).unwrap_or(false);
+    symlink
+}
+
+/// Clear `dst` so a fresh link can be made. Symlinks and junctions are always
+/// removed when `replace` is set (dangling ones always); real directories only
+/// when empty. Returns false when something worth keeping is in the way.
+fn clear_destination(dst: &Path, replace: bool) -> Result<bool> {
+    if is_link(dst) {
+        if !replace && dst.exists() {
+            return Ok(false);
+        }
+        // Directory links on Windows need remove_dir; everything else remove_file
+        fs::remove_file(dst)
+            .or_else(|_| fs::remove_dir(dst))
+            .with_context(|| format!("remove existing link {}", dst.display()))?;
+        return Ok(true);
+    }
+    if !dst.exists() {
+        return Ok(true);
+    }
+    if replace && dst.is_dir() && fs::read_dir(dst).map(|mut d| d.next().is_none()).unwrap_or(false) {
+        fs::remove_dir(dst).with_context(|| format!("remove empty dir {}", dst.display()))?;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
 /// Attempt to create a directory link from dst -> src.
-/// Strategy: symlink_dir -> junction -> copy (fallback).
-pub fn link_dir_best_effort(src: &Path, dst: &Path) -> Result<()> {
+/// Strategy (Auto): symlink_dir -> junction -> copy (fallback).
+/// With `replace`, an existing link (or empty folder) at `dst` is recreated.
+pub fn link_dir_best_effort(src: &Path, dst: &Path, strategy: LinkStrategy, replace: bool) -> Result<LinkMethod> {
     // Ensure parent exists
     if let Some(parent) = dst.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("create parent for {}", dst.display()))?;
     }
 
-    // If already exists, do nothing
-    if dst.exists() {
-        return Ok(());
+    if !clear_destination(dst, replace)? {
+        return Ok(LinkMethod::Existing);
     }
 
-    // Try symlink
-    #[cfg(windows)]
-    {
-        if let Err(_e) = winfs::symlink_dir(src, dst) {
-            // Try junction as fallback
-            if let Err(e2) = junction::create(dst, src) {
-                // Last resort: copy
-                let _ = copy_dir_recursive(src, dst)
-                    .with_context(|| format!("junction failed: {e2}; copied instead"))?;
-            }
-        }
-        return Ok(());
+    if strategy == LinkStrategy::CopyOnly {
+        return Ok(LinkMethod::Copied { bytes: copy_dir_recursive(src, dst)? });
     }
 
+    // Try symlink
+    #[cfg(windows)]
+    let link_err = match winfs::symlink_dir(src, dst) {
+        Ok(()) => return Ok(LinkMethod::Symlink),
+        // Try junction as fallback
+        Err(_) => match junction::create(src, dst) {
+            Ok(()) => return Ok(LinkMethod::Junction),
+            Err(e) => e,
+        },
+    };
     #[cfg(not(windows))]
-    {
-        // Non-Windows: symlink_dir
-        std::os::unix::fs::symlink(src, dst)
-            .or_else(|_| copy_dir_recursive(src, dst).map(|_| ()))?;
-        return Ok(());
+    let link_err = match std::os::unix::fs::symlink(src, dst) {
+        Ok(()) => return Ok(LinkMethod::Symlink),
+        Err(e) => e,
+    };
+
+    if strategy == LinkStrategy::SymlinkOnly {
+        return Err(anyhow::anyhow!("link {} -> {}: {}", dst.display(), src.display(), link_err));
     }
+    // Last resort: copy
+    let bytes = copy_dir_recursive(src, dst)
+        .with_context(|| format!("link failed: {link_err}; copy failed too"))?;
+    Ok(LinkMethod::Copied { bytes })
 }
 
 /// Attempt to create a file link from dst -> src.
-/// Strategy: symlink_file -> copy fallback.
-pub fn link_file_best_effort(src: &Path, dst: &Path) -> Result<()> {
+/// Strategy (Auto): symlink_file -> copy fallback.
+/// With `replace`, an existing symlink at `dst` is recreated; real files are kept.
+pub fn link_file_best_effort(src: &Path, dst: &Path, strategy: LinkStrategy, replace: bool) -> Result<LinkMethod> {
     if let Some(parent) = dst.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("create parent for {}", dst.display()))?;
     }
-    if dst.exists() {
-        return Ok(());
+    if !clear_destination(dst, replace && !dst.is_dir())? {
+        return Ok(LinkMethod::Existing);
     }
 
-    #[cfg(windows)]
-    {
-        if let Err(_e) = winfs::symlink_file(src, dst) {
-            fs::copy(src, dst).with_context(|| format!("copy {} -> {}", src.display(), dst.display()))?;
+    if strategy != LinkStrategy::CopyOnly {
+        #[cfg(windows)]
+        let linked = winfs::symlink_file(src, dst);
+        #[cfg(not(windows))]
+        let linked = std::os::unix::fs::symlink(src, dst);
+        match linked {
+            Ok(()) => return Ok(LinkMethod::Symlink),
+            Err(e) if strategy == LinkStrategy::SymlinkOnly => {
+                return Err(anyhow::anyhow!("link {} -> {}: {}", dst.display(), src.display(), e));
+            }
+            Err(_) => {}
         }
-        return Ok(());
     }
-    #[cfg(not(windows))]
-    {
-        if let Err(_e) = std::os::unix::fs::symlink(src, dst) {
-            fs::copy(src, dst).with_context(|| format!("copy {} -> {}", src.display(), dst.display()))?;
-        }
-        return Ok(());
+    let bytes = copy_file(src, dst).with_context(|| format!("copy {} -> {}", src.display(), dst.display()))?;
+    Ok(LinkMethod::Copied { bytes })
+}
+
+/// `fs::copy` that also gives `dst` the source's modification time, so size+mtime update
+/// detection sees the copy as unchanged on the next run.
+pub fn copy_file(src: &Path, dst: &Path) -> std::io::Result<u64> {
+    let bytes = fs::copy(src, dst)?;
+    copy_mtime(src, dst);
+    Ok(bytes)
+}
+
+// Best effort: a filesystem that cannot store the time still has a usable copy
+fn copy_mtime(src: &Path, dst: &Path) {
+    if let Ok(meta) = fs::metadata(src) {
+        let _ = filetime::set_file_mtime(dst, filetime::FileTime::from_last_modification_time(&meta));
+    }
+}
+
+// fs_extra does not keep timestamps; walk the copied tree and restore them file by file
+fn copy_tree_mtimes(src: &Path, dst: &Path) {
+    for entry in walkdir::WalkDir::new(src).into_iter().flatten().filter(|e| e.file_type().is_file()) {
+        if let Ok(rel) = entry.path().strip_prefix(src) { copy_mtime(entry.path(), &dst.join(rel)); }
     }
 }
 
@@ -77,24 +162,134 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<u64> {
     opts.overwrite = true;
     fs::create_dir_all(dst).ok();
     let n = copy(src, dst, &opts).with_context(|| format!("copy dir {} -> {}", src.display(), dst.display()))?;
+    copy_tree_mtimes(src, dst);
     Ok(n)
 }
 
 /// Recursive copy with simple progress callback (0..=100 is up to caller).
-/// We report best-effort progress based on bytes.
-pub fn copy_dir_with_progress<F: FnMut(u64, u64)>(src: &Path, dst: &Path, mut on_progress: F) -> Result<u64> {
-    use fs_extra::dir::{copy_with_progress, CopyOptions, TransitProcess};
+/// We report best-effort progress based on bytes. Once `cancel` is set the copy stops at the
+/// next progress callback and returns a `Cancelled` error, leaving what was already copied.
+pub fn copy_dir_with_progress<F: FnMut(u64, u64)>(src: &Path, dst: &Path, cancel: &CancellationToken, mut on_progress: F) -> Result<u64> {
+    use fs_extra::dir::{copy_with_progress, CopyOptions, TransitProcess, TransitProcessResult};
     let mut opts = CopyOptions::new();
     opts.copy_inside = true;
     opts.overwrite = true;
     fs::create_dir_all(dst).ok();
     let handler = |tp: TransitProcess| {
+        if cancel.is_cancelled() { return TransitProcessResult::Abort; }
         on_progress(tp.copied_bytes, tp.total_bytes);
-        fs_extra::dir::TransitProcessResult::ContinueOrAbort
+        TransitProcessResult::ContinueOrAbort
     };
-    let n = copy_with_progress(src, dst, &opts, handler)
-        .with_context(|| format!("copy (progress) {} -> {}", src.display(), dst.display()))?;
+    let copied = copy_with_progress(src, dst, &opts, handler);
+    cancel.check()?;
+    let n = copied.with_context(|| format!("copy (progress) {} -> {}", src.display(), dst.display()))?;
+    copy_tree_mtimes(src, dst);
     Ok(n)
 }
 
+/// What `sync_dir_with_progress` did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncStats {
+    pub copied_files: usize,
+    pub copied_bytes: u64,
+    pub skipped_files: usize,
+}
+
+// Same size and modification time counts as identical (copies made here carry the source mtime)
+fn same_file(src: &fs::Metadata, dst: &Path) -> bool {
+    let Ok(dst) = fs::metadata(dst) else { return false };
+    dst.is_file() && src.len() == dst.len()
+        && filetime::FileTime::from_last_modification_time(src) == filetime::FileTime::from_last_modification_time(&dst)
+}
+
+/// Copy only the files under `src` that are missing from `dst` or differ in size or mtime, so
+/// re-running an install over an intact copy is quick. Progress is in bytes of the files that
+/// need copying; cancellation behaves as in `copy_dir_with_progress`.
+pub fn sync_dir_with_progress<F: FnMut(u64, u64)>(src: &Path, dst: &Path, cancel: &CancellationToken, mut on_progress: F) -> Result<SyncStats> {
+    let mut stats = SyncStats::default();
+    let mut pending = Vec::new();
+    for entry in walkdir::WalkDir::new(src).min_depth(1) {
+        let entry = entry.with_context(|| format!("scan {}", src.display()))?;
+        let rel = entry.path().strip_prefix(src).context("walkdir entry outside its root")?.to_path_buf();
+        let target = dst.join(&rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target).with_context(|| format!("create {}", target.display()))?;
+        } else {
+            let meta = entry.metadata()?;
+            if same_file(&meta, &target) { stats.skipped_files += 1; } else { pending.push((entry.into_path(), target, meta.len())); }
+        }
+    }
+    fs::create_dir_all(dst).with_context(|| format!("create {}", dst.display()))?;
+    let total: u64 = pending.iter().map(|(_, _, len)| len).sum();
+    on_progress(0, total);
+    for (from, to, len) in pending {
+        cancel.check()?;
+        copy_file(&from, &to).with_context(|| format!("copy {} -> {}", from.display(), to.display()))?;
+        stats.copied_files += 1;
+        stats.copied_bytes += len;
+        on_progress(stats.copied_bytes, total);
+    }
+    Ok(stats)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_relinks_but_keeps_real_dirs() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-link-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (old, new, dst, real) = (root.join("old"), root.join("new"), root.join("dst"), root.join("real"));
+        for d in [&old, &new, &real] { fs::create_dir_all(d).unwrap(); }
+        fs::write(real.join("user.txt"), b"keep").unwrap();
+
+        link_dir_best_effort(&old, &dst, LinkStrategy::SymlinkOnly, false).unwrap();
+        assert_eq!(link_dir_best_effort(&new, &dst, LinkStrategy::SymlinkOnly, false).unwrap(), LinkMethod::Existing);
+        assert_eq!(link_dir_best_effort(&new, &dst, LinkStrategy::SymlinkOnly, true).unwrap(), LinkMethod::Symlink);
+        assert_eq!(fs::read_link(&dst).unwrap(), new);
+
+        assert_eq!(link_dir_best_effort(&new, &real, LinkStrategy::SymlinkOnly, true).unwrap(), LinkMethod::Existing);
+        assert!(real.join("user.txt").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
 
+    #[test]
+    fn cancelled_copy_stops_with_cancelled_error() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-copy-cancel-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        fs::create_dir_all(&src).unwrap();
+        for i in 0..3 { fs::write(src.join(format!("{}.bin", i)), vec![0u8; 1024]).unwrap(); }
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = copy_dir_with_progress(&src, &dst, &cancel, |_, _| {}).unwrap_err();
+        let copied = fs::read_dir(&dst).map(|d| d.count()).unwrap_or(0);
+        let _ = fs::remove_dir_all(&root);
+        assert!(err.is::<crate::jobs::Cancelled>());
+        assert!(copied < 3);
+    }
+
+    #[test]
+    fn sync_copies_only_what_differs() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-sync-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        fs::create_dir_all(src.join("win64")).unwrap();
+        fs::write(src.join("a.dll"), b"aaaa").unwrap();
+        fs::write(src.join("win64/b.dll"), b"bbbb").unwrap();
+        let cancel = CancellationToken::new();
+
+        let first = sync_dir_with_progress(&src, &dst, &cancel, |_, _| {}).unwrap();
+        let second = sync_dir_with_progress(&src, &dst, &cancel, |_, _| {}).unwrap();
+        fs::write(src.join("win64/b.dll"), b"bbbbbb").unwrap();
+        let third = sync_dir_with_progress(&src, &dst, &cancel, |_, _| {}).unwrap();
+        let b = fs::read(dst.join("win64/b.dll")).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!((first.copied_files, first.skipped_files), (2, 0));
+        assert_eq!((second.copied_files, second.skipped_files), (0, 2));
+        assert_eq!((third.copied_files, third.copied_bytes), (1, 6));
+        assert_eq!(b, b"bbbbbb");
+    }
+}
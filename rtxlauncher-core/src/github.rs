@@ -1,8 +1,9 @@
 use anyhow::{Result, Context};
+use crate::error::{LauncherError, LauncherResult};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf, time::Duration};
-use tracing::info;
+use std::{fs, path::{Path, PathBuf}, sync::atomic::{AtomicBool, Ordering}, time::Duration};
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GitHubAsset {
@@ -20,6 +21,29 @@ pub struct GitHubRelease {
     // Optional extra fields for richer UI rendering
     pub body: Option<String>,
     pub prerelease: Option<bool>,
+    pub html_url: Option<String>,
+}
+
+impl GitHubRelease {
+    pub fn is_prerelease(&self) -> bool { self.prerelease.unwrap_or(false) }
+
+    pub fn published(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.published_at.as_deref().and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+    }
+
+    /// Publication day as `YYYY-MM-DD`, when GitHub gave a parseable date.
+    pub fn published_date(&self) -> Option<String> { self.published().map(|d| d.format("%Y-%m-%d").to_string()) }
+}
+
+/// Newest `published_at` first; releases without a usable date keep their order at the end.
+pub fn sort_newest_first(releases: &mut [GitHubRelease]) {
+    releases.sort_by_key(|r| std::cmp::Reverse(r.published()));
+}
+
+/// The newest release, skipping pre-releases unless `include_prereleases`; falls back to the newest
+/// pre-release when a repository has nothing else.
+pub fn latest_release(releases: &[GitHubRelease], include_prereleases: bool) -> Option<&GitHubRelease> {
+    releases.iter().find(|r| include_prereleases || !r.is_prerelease()).or_else(|| releases.first())
 }
 
 #[derive(Debug, Clone, Default)]
@@ -29,7 +53,60 @@ pub struct GitHubRateLimit {
     pub reset_unix: i64,
 }
 
-fn cache_dir() -> Result<PathBuf> {
+pub const DEFAULT_GITHUB_API_BASE: &str = "https://api.github.com";
+pub const DEFAULT_GITHUB_RAW_BASE: &str = "https://raw.githubusercontent.com";
+
+// API and raw bases resolved from the settings, filled on first use so requests do not re-read
+// settings.toml; `set_github_bases` replaces them when the user edits the hosts
+static BASE_CACHE: std::sync::Mutex<Option<(String, String)>> = std::sync::Mutex::new(None);
+
+// Settings override for a host, without a trailing slash
+fn resolve_base(configured: Option<&str>, default: &str) -> String {
+    configured.map(|b| b.trim().trim_end_matches('/').to_string())
+        .filter(|b| !b.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Check a GitHub host override: an absolute http(s) URL. Returns it trimmed, without a
+/// trailing slash.
+pub fn validate_github_base(value: &str) -> Result<String, String> {
+    let value = value.trim().trim_end_matches('/');
+    let url = reqwest::Url::parse(value).map_err(|e| e.to_string())?;
+    if !matches!(url.scheme(), "http" | "https") { return Err(format!("unsupported scheme '{}'", url.scheme())); }
+    if url.host_str().is_none_or(str::is_empty) { return Err("missing host".to_string()); }
+    Ok(value.to_string())
+}
+
+fn bases(settings: &crate::settings::AppSettings) -> (String, String) {
+    (resolve_base(settings.github_api_base.as_deref(), DEFAULT_GITHUB_API_BASE), resolve_base(settings.github_raw_base.as_deref(), DEFAULT_GITHUB_RAW_BASE))
+}
+
+fn configured_bases() -> (String, String) {
+    let mut cache = BASE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.get_or_insert_with(|| bases(&crate::settings::SettingsStore::new().and_then(|s| s.load()).unwrap_or_default())).clone()
+}
+
+/// Use the hosts in `settings` for later requests.
+pub fn set_github_bases(settings: &crate::settings::AppSettings) {
+    *BASE_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(bases(settings));
+}
+
+/// REST API root, e.g. `https://api.github.com` or `https://ghe.example.com/api/v3`.
+pub fn github_api_base() -> String { configured_bases().0 }
+
+/// Raw file host, e.g. `https://raw.githubusercontent.com` or `https://ghe.example.com/raw`.
+pub fn github_raw_base() -> String { configured_bases().1 }
+
+// Upper bound on releases collected across pages, to avoid runaway pagination
+const MAX_RELEASES: usize = 200;
+
+// Set when the last release fetch could not reach GitHub and answered from the cache
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the most recent `fetch_releases` fell back to cached data because GitHub was unreachable.
+pub fn is_offline() -> bool { OFFLINE.load(Ordering::Relaxed) }
+
+pub(crate) fn cache_dir() -> Result<PathBuf> {
     let dirs = ProjectDirs::from("com", "rtxlauncher", "rtxlauncher")
         .ok_or_else(|| anyhow::anyhow!("project dirs"))?;
     let dir = dirs.cache_dir().join("github");
@@ -45,21 +122,43 @@ fn token_path() -> Result<PathBuf> {
     Ok(dir.join("github_token.dat"))
 }
 
-pub fn set_personal_access_token(token: Option<String>) -> Result<()> {
+// Decrypted token, filled on first load; the settings tab asks for it every frame and
+// Secret Service lookups are too slow for that
+static TOKEN_CACHE: std::sync::Mutex<Option<Option<String>>> = std::sync::Mutex::new(None);
+
+pub fn set_personal_access_token(token: Option<String>) -> LauncherResult<()> {
     let path = token_path()?;
-    match token {
-        Some(t) if !t.is_empty() => fs::write(path, t).context("write token")?,
-        _ => { let _ = fs::remove_file(path); }
+    let token = token.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    match &token {
+        Some(t) => fs::write(path, crate::token_store::protect(t)).context("write token")?,
+        None => {
+            crate::token_store::clear();
+            let _ = fs::remove_file(path);
+        }
     }
+    *TOKEN_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(token);
     Ok(())
 }
 
 pub fn load_personal_access_token() -> Option<String> {
-    let path = token_path().ok()?;
-    fs::read_to_string(path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+    let mut cache = TOKEN_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(token) = cache.as_ref() { return token.clone(); }
+    let stored = token_path().ok().and_then(|p| fs::read_to_string(p).ok()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let token = stored.and_then(|s| match crate::token_store::unprotect(&s) {
+        Ok((token, plaintext)) => {
+            // Files written by older versions hold the raw token; re-save it protected
+            if plaintext {
+                if let Ok(path) = token_path() { let _ = fs::write(path, crate::token_store::protect(&token)); }
+            }
+            Some(token)
+        }
+        Err(e) => { tracing::warn!("Could not read the stored GitHub token: {}", e); None }
+    });
+    *cache = Some(token.clone());
+    token
 }
 
-fn cache_is_valid(p: &PathBuf, ttl: Duration) -> bool {
+fn cache_is_valid(p: &Path, ttl: Duration) -> bool {
     if let Ok(meta) = fs::metadata(p) {
         if let Ok(modified) = meta.modified() {
             if let Ok(elapsed) = modified.elapsed() { return elapsed < ttl; }
@@ -68,39 +167,199 @@ fn cache_is_valid(p: &PathBuf, ttl: Duration) -> bool {
     false
 }
 
-pub async fn fetch_releases(owner: &str, repo: &str, rate_limit: &mut GitHubRateLimit) -> Result<Vec<GitHubRelease>> {
-    let cache = cache_dir()?.join(format!("{}_{}_releases.json", owner, repo));
-    let ttl = Duration::from_secs(8 * 60);
-    if cache_is_valid(&cache, ttl) {
-        if let Ok(text) = fs::read_to_string(&cache) {
-            if let Ok(v) = serde_json::from_str::<Vec<GitHubRelease>>(&text) { return Ok(v); }
+// Keyed by API host too, so a mirror's list (and its ETag) never answers for another host
+fn releases_cache_name(api_base: &str, owner: &str, repo: &str) -> String {
+    let host = api_base.split_once("://").map_or(api_base, |(_, rest)| rest);
+    let host: String = host.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+    format!("{}_{}_{}_releases.json", host, owner, repo)
+}
+
+fn releases_cache_path(owner: &str, repo: &str) -> Result<PathBuf> {
+    Ok(cache_dir()?.join(releases_cache_name(&github_api_base(), owner, repo)))
+}
+
+/// Errors that mean GitHub could not be reached (or is down), as opposed to refusing the request.
+pub(crate) fn is_unreachable(err: &LauncherError) -> bool {
+    match err {
+        LauncherError::Network(_) => true,
+        LauncherError::Http(s) => s.is_server_error(),
+        _ => false,
+    }
+}
+
+/// Releases for `owner/repo`, newest first. When GitHub is unreachable a previously cached list is
+/// returned regardless of age and `is_offline` reports true until a fetch succeeds again.
+pub async fn fetch_releases(owner: &str, repo: &str, rate_limit: &mut GitHubRateLimit) -> LauncherResult<Vec<GitHubRelease>> {
+    let err: LauncherError = match fetch_releases_inner(owner, repo, rate_limit).await {
+        Ok(mut releases) => {
+            OFFLINE.store(false, Ordering::Relaxed);
+            sort_newest_first(&mut releases);
+            return Ok(releases);
+        }
+        Err(e) => e.into(),
+    };
+    if is_unreachable(&err) {
+        let stale: Option<Vec<GitHubRelease>> = releases_cache_path(owner, repo).ok()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|text| serde_json::from_str(&text).ok());
+        if let Some(mut releases) = stale {
+            sort_newest_first(&mut releases);
+            warn!("offline: using cached data for {}/{} ({})", owner, repo, err);
+            OFFLINE.store(true, Ordering::Relaxed);
+            return Ok(releases);
         }
     }
+    Err(err)
+}
 
-    let client = reqwest::Client::new();
-    let url = format!("https://api.github.com/repos/{owner}/{repo}/releases");
-    info!("GitHub fetch: {}", url);
-    let mut req = client.get(&url)
-        .header("User-Agent", "RTXLauncher-RS")
-        .header("Accept", "application/vnd.github.v3+json");
-    if let Some(token) = load_personal_access_token() {
-        req = req.bearer_auth(token);
+async fn fetch_releases_inner(owner: &str, repo: &str, rate_limit: &mut GitHubRateLimit) -> Result<Vec<GitHubRelease>> {
+    let cache = releases_cache_path(owner, repo)?;
+    let etag_path = cache.with_extension("etag");
+    // Short TTL only to absorb repeated opens; beyond that we revalidate with If-None-Match,
+    // which GitHub does not count against the rate limit when it answers 304.
+    let ttl = Duration::from_secs(60);
+    let cached: Option<Vec<GitHubRelease>> = fs::read_to_string(&cache).ok().and_then(|text| serde_json::from_str(&text).ok());
+    if let Some(v) = &cached {
+        if cache_is_valid(&cache, ttl) { return Ok(v.clone()); }
     }
-    let resp = req.send().await?;
+    let cached_etag = if cached.is_some() { fs::read_to_string(&etag_path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) } else { None };
+
+    let client = reqwest::Client::new();
+    let token = load_personal_access_token();
+    let mut releases: Vec<GitHubRelease> = Vec::new();
+    let mut first_page_etag: Option<String> = None;
+    let mut next_url = Some(format!("{}/repos/{owner}/{repo}/releases?per_page=100", github_api_base()));
+    while let Some(url) = next_url.take() {
+        info!("GitHub fetch: {}", url);
+        let mut req = client.get(&url)
+            .header("User-Agent", "RTXLauncher-RS")
+            .header("Accept", "application/vnd.github.v3+json");
+        if let Some(token) = &token {
+            req = req.bearer_auth(token);
+        }
+        // The ETag covers the first page, where new releases appear
+        let is_first_page = releases.is_empty();
+        if is_first_page {
+            if let Some(etag) = &cached_etag { req = req.header("If-None-Match", etag.as_str()); }
+        }
+        let resp = req.send().await?;
 
-    // capture rate limit
-    if let Some(v) = resp.headers().get("X-RateLimit-Limit") { rate_limit.limit = v.to_str().unwrap_or("0").parse().unwrap_or(0); }
-    if let Some(v) = resp.headers().get("X-RateLimit-Remaining") { rate_limit.remaining = v.to_str().unwrap_or("0").parse().unwrap_or(0); }
-    if let Some(v) = resp.headers().get("X-RateLimit-Reset") { rate_limit.reset_unix = v.to_str().unwrap_or("0").parse().unwrap_or(0); }
+        // capture rate limit (last response wins)
+        read_rate_limit(resp.headers(), rate_limit);
+        let link = resp.headers().get("Link").and_then(|v| v.to_str().ok()).map(|s| s.to_string());
+        if is_first_page {
+            if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+                if let Some(v) = cached {
+                    info!("GitHub releases not modified: {}/{}", owner, repo);
+                    let _ = filetime::set_file_mtime(&cache, filetime::FileTime::now());
+                    return Ok(v);
+                }
+            }
+            first_page_etag = resp.headers().get("ETag").and_then(|v| v.to_str().ok()).map(|s| s.to_string());
+        }
 
-    let status = resp.status();
-    let text = resp.text().await?;
-    if !status.is_success() {
-        anyhow::bail!("GitHub API error: {}", status);
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            let exhausted = rate_limit.limit > 0 && rate_limit.remaining == 0;
+            if exhausted && (status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::TOO_MANY_REQUESTS) {
+                return Err(LauncherError::RateLimited { reset_unix: rate_limit.reset_unix }.into());
+            }
+            return Err(LauncherError::Http(status).into());
+        }
+        let page: Vec<GitHubRelease> = serde_json::from_str(&text)?;
+        releases.extend(page);
+        if releases.len() >= MAX_RELEASES {
+            releases.truncate(MAX_RELEASES);
+            break;
+        }
+        next_url = link.as_deref().and_then(parse_next_link);
+    }
+    if let Ok(text) = serde_json::to_string(&releases) { fs::write(&cache, text).ok(); }
+    match first_page_etag {
+        Some(etag) => { fs::write(&etag_path, etag).ok(); }
+        None => { let _ = fs::remove_file(&etag_path); }
     }
-    fs::write(&cache, &text).ok();
-    let releases: Vec<GitHubRelease> = serde_json::from_str(&text)?;
     Ok(releases)
 }
 
+fn read_rate_limit(headers: &reqwest::header::HeaderMap, rate_limit: &mut GitHubRateLimit) {
+    if let Some(v) = headers.get("X-RateLimit-Limit") { rate_limit.limit = v.to_str().unwrap_or("0").parse().unwrap_or(0); }
+    if let Some(v) = headers.get("X-RateLimit-Remaining") { rate_limit.remaining = v.to_str().unwrap_or("0").parse().unwrap_or(0); }
+    if let Some(v) = headers.get("X-RateLimit-Reset") { rate_limit.reset_unix = v.to_str().unwrap_or("0").parse().unwrap_or(0); }
+}
+
+/// Check a personal access token against `/user`. Returns the account login and the
+/// token's rate limit, or an error describing why GitHub refused it.
+pub async fn validate_personal_access_token(token: &str) -> LauncherResult<(String, GitHubRateLimit)> {
+    #[derive(Deserialize)]
+    struct User { login: String }
+
+    let resp = reqwest::Client::new()
+        .get(format!("{}/user", github_api_base()))
+        .header("User-Agent", "RTXLauncher-RS")
+        .header("Accept", "application/vnd.github.v3+json")
+        .bearer_auth(token.trim())
+        .send().await?;
+    let mut rate_limit = GitHubRateLimit::default();
+    read_rate_limit(resp.headers(), &mut rate_limit);
+    match resp.status() {
+        reqwest::StatusCode::UNAUTHORIZED => return Err(anyhow::anyhow!("Token is invalid or expired (401 Unauthorized)").into()),
+        reqwest::StatusCode::FORBIDDEN => return Err(anyhow::anyhow!("Token was rejected (403 Forbidden); it may be rate limited or blocked by an organization policy").into()),
+        s if !s.is_success() => return Err(LauncherError::Http(s)),
+        _ => {}
+    }
+    let user: User = resp.json().await.context("parse /user response")?;
+    Ok((user.login, rate_limit))
+}
+
+/// Extract the `rel="next"` target from a GitHub `Link` header.
+fn parse_next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let mut pieces = part.split(';');
+        let url = pieces.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        pieces.any(|p| p.trim() == "rel=\"next\"").then(|| url.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_next_link, releases_cache_name, sort_newest_first, validate_github_base, GitHubRelease};
 
+    #[test]
+    fn next_link_is_found_among_relations() {
+        let h = r#"<https://api.github.com/repositories/1/releases?page=2>; rel="next", <https://api.github.com/repositories/1/releases?page=5>; rel="last""#;
+        assert_eq!(parse_next_link(h).as_deref(), Some("https://api.github.com/repositories/1/releases?page=2"));
+        let last_page = r#"<https://api.github.com/repositories/1/releases?page=1>; rel="prev", <https://api.github.com/repositories/1/releases?page=1>; rel="first""#;
+        assert_eq!(parse_next_link(last_page), None);
+    }
+
+    #[test]
+    fn github_base_must_be_http_url() {
+        assert_eq!(validate_github_base(" https://ghe.example.com/api/v3/ ").as_deref(), Ok("https://ghe.example.com/api/v3"));
+        assert!(validate_github_base("ghe.example.com").is_err());
+        assert!(validate_github_base("ftp://ghe.example.com").is_err());
+        assert!(validate_github_base("https://").is_err());
+    }
+
+    #[test]
+    fn releases_cache_is_per_host() {
+        assert_eq!(releases_cache_name("https://api.github.com", "NVIDIAGameWorks", "rtx-remix"), "api.github.com_NVIDIAGameWorks_rtx-remix_releases.json");
+        assert_eq!(releases_cache_name("https://ghe.example.com:8443/api/v3", "o", "r"), "ghe.example.com_8443_api_v3_o_r_releases.json");
+    }
+
+    #[test]
+    fn releases_sort_newest_first_with_undated_last() {
+        let rel = |tag: &str, at: Option<&str>| GitHubRelease { tag_name: Some(tag.into()), published_at: at.map(Into::into), ..Default::default() };
+        let mut list = vec![
+            rel("undated", None),
+            rel("old", Some("2023-01-05T10:00:00Z")),
+            rel("bad", Some("yesterday")),
+            rel("new", Some("2024-06-01T08:30:00Z")),
+        ];
+        sort_newest_first(&mut list);
+        let tags: Vec<_> = list.iter().map(|r| r.tag_name.as_deref().unwrap()).collect();
+        assert_eq!(tags, ["new", "old", "undated", "bad"]);
+        assert_eq!(list[0].published_date().as_deref(), Some("2024-06-01"));
+    }
+}
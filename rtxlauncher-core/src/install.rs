@@ -1,7 +1,9 @@
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 use std::fs;
-use crate::fs_linker::{link_dir_best_effort, link_file_best_effort, copy_dir_with_progress};
+use crate::fs_linker::{link_dir_best_effort, link_file_best_effort, sync_dir_with_progress, copy_file, LinkMethod, LinkStrategy, SyncStats};
+use crate::jobs::CancellationToken;
+use crate::progress::WeightedProgress;
 use tracing::info;
 
 fn flatten_if_nested(dir: &Path) -> Result<()> {
@@ -20,7 +22,7 @@ fn flatten_if_nested(dir: &Path) -> Result<()> {
                         let _ = crate::fs_linker::copy_dir_recursive(&from, &to);
                         let _ = std::fs::remove_dir_all(&from);
                     } else {
-                        let _ = std::fs::copy(&from, &to);
+                        let _ = copy_file(&from, &to);
                         let _ = std::fs::remove_file(&from);
                     }
                 }
@@ -31,77 +33,139 @@ fn flatten_if_nested(dir: &Path) -> Result<()> {
     Ok(())
 }
 
+// garrysmod subfolders that are linked back to the vanilla install rather than copied
+const LINKED_GARRYSMOD_DIRS: [&str; 11] = [
+    "saves", "dupes", "demos", "settings", "cache", "download",
+    "materials", "models", "maps", "screenshots", "videos"
+];
+
+/// Copy what differs from `src` into `dst`, mapping the byte progress onto `lo..=hi` of the current stage.
+/// Reports whenever the overall percent moves or a second has passed, so slow disks still show movement.
+fn copy_step(src: &Path, dst: &Path, label: &str, lo: u8, hi: u8, cancel: &CancellationToken, progress: &mut impl FnMut(&str, u8)) -> Result<SyncStats> {
+    let mut last_pct = lo;
+    let mut last_report = std::time::Instant::now();
+    let stats = sync_dir_with_progress(src, dst, cancel, |copied, total| {
+        if total == 0 { return; }
+        let pct = lo + ((hi - lo) as u64 * copied.min(total) / total) as u8;
+        if pct != last_pct || last_report.elapsed() >= std::time::Duration::from_secs(1) {
+            let msg = format!("{} ({} / {})", label,
+                humansize::format_size(copied, humansize::BINARY), humansize::format_size(total, humansize::BINARY));
+            progress(&msg, pct);
+            last_pct = pct;
+            last_report = std::time::Instant::now();
+        }
+    })?;
+    if stats.skipped_files > 0 {
+        progress(&format!("{}: {} file(s) already up to date, {} copied", label, stats.skipped_files, stats.copied_files), hi);
+    }
+    Ok(stats)
+}
+
+#[derive(Debug, Clone)]
 pub struct InstallPlan {
     pub vanilla: PathBuf,
     pub rtx: PathBuf,
+    pub link_strategy: LinkStrategy,
 }
 
-pub fn perform_basic_install(plan: &InstallPlan, mut progress_cb: impl FnMut(&str, u8)) -> Result<()> {
-    let mut progress = |m: &str, pct: u8| { info!("{}", m); progress_cb(m, pct); };
-    progress("Starting install", 0);
+/// What `perform_basic_install` wrote.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstallStats {
+    /// Files the folder copies wrote; files already up to date are not counted
+    pub copied_files: usize,
+    pub copied_bytes: u64,
+    /// Links that could not be created, not even as a copy
+    pub link_failures: usize,
+}
+
+impl InstallStats {
+    fn add(&mut self, s: SyncStats) {
+        self.copied_files += s.copied_files;
+        self.copied_bytes += s.copied_bytes;
+    }
+}
+
+/// Build the RTX install described by `plan`. `cancel` stops the folder copies part-way and
+/// is checked between steps; a cancelled install returns a `Cancelled` error.
+pub fn perform_basic_install(plan: &InstallPlan, cancel: &CancellationToken, mut progress_cb: impl FnMut(&str, u8)) -> Result<InstallStats> {
+    // The shares the steps have always had of the bar
+    let stages = WeightedProgress::new(&[("prepare", 10), ("bin", 10), ("executable", 10), ("vpks", 10), ("external", 20), ("garrysmod", 30), ("links", 10)]);
+    let mut report = |stage: &str, m: &str, pct: u8| { info!("{}", m); progress_cb(m, stages.overall(stage, pct)); };
+    report("prepare", "Starting install", 0);
+    let mut stats = InstallStats::default();
+    // Bytes duplicated because a link could not be created, reported at the end
+    let mut copied_bytes = 0u64;
+    let mut link_failures = 0;
+    let mut track = |r: Result<LinkMethod>, dst: &Path| match r {
+        Ok(LinkMethod::Copied { bytes }) => copied_bytes += bytes,
+        Ok(_) => {}
+        Err(e) => { link_failures += 1; tracing::warn!("Failed to link {}: {}", dst.display(), e) }
+    };
 
     // 1. Copy bin folder (ensure layout: <rtx>/bin/<files> and <rtx>/bin/win64/<files>)
-    progress("Copying bin folder", 10);
+    report("bin", "Copying bin folder", 0);
     let src_bin = plan.vanilla.join("bin");
     let dst_bin = plan.rtx.join("bin");
-    copy_dir_with_progress(&src_bin, &dst_bin, |_c, _t| {})?;
+    stats.add(copy_step(&src_bin, &dst_bin, "Copying bin folder", 0, 80, cancel, &mut |m, p| report("bin", m, p))?);
     // Fix nested copies if any (bin/bin)
     let _ = flatten_if_nested(&dst_bin);
     // If a win64 exists in the vanilla bin, ensure it is present in destination
     let src_win64 = src_bin.join("win64");
     if src_win64.exists() {
         let dst_win64 = dst_bin.join("win64");
-        copy_dir_with_progress(&src_win64, &dst_win64, |_c, _t| {})?;
+        stats.add(copy_step(&src_win64, &dst_win64, "Copying bin/win64 folder", 80, 100, cancel, &mut |m, p| report("bin", m, p))?);
         let _ = flatten_if_nested(&dst_win64);
     }
 
+    cancel.check()?;
+
     // 2. Ensure garrysmod folder
     let rtx_gm = plan.rtx.join("garrysmod");
     fs::create_dir_all(&rtx_gm)?;
     let _ = flatten_if_nested(&rtx_gm);
 
     // 3. Copy gmod.exe or fallback hl2.exe to root; if 64-bit layout present, prefer bin/win64 exe as well
-    progress("Copying executable", 20);
+    report("executable", "Copying executable", 0);
     let root_exe_src = if plan.vanilla.join("gmod.exe").exists() {
         plan.vanilla.join("gmod.exe")
     } else {
         plan.vanilla.join("hl2.exe")
     };
     let root_exe_dst = plan.rtx.join(root_exe_src.file_name().unwrap());
-    if root_exe_src.exists() { let _ = std::fs::copy(&root_exe_src, &root_exe_dst); }
+    if root_exe_src.exists() { let _ = copy_file(&root_exe_src, &root_exe_dst); }
     // Also copy win64 gmod.exe if present
     let win64_exe_src = plan.vanilla.join("bin").join("win64").join("gmod.exe");
     if win64_exe_src.exists() {
-        let _ = std::fs::copy(&win64_exe_src, &plan.rtx.join("bin").join("win64").join("gmod.exe"));
+        let _ = copy_file(&win64_exe_src, &plan.rtx.join("bin").join("win64").join("gmod.exe"));
     }
 
     // 4. Copy steam_appid.txt if present
     let appid_src = plan.vanilla.join("steam_appid.txt");
-    if appid_src.exists() { std::fs::copy(&appid_src, &plan.rtx.join("steam_appid.txt"))?; }
+    if appid_src.exists() { copy_file(&appid_src, &plan.rtx.join("steam_appid.txt"))?; }
 
     // 5. Symlink VPK files in garrysmod root
-    progress("Linking VPK files", 30);
+    report("vpks", "Linking VPK files", 0);
     for entry in fs::read_dir(plan.vanilla.join("garrysmod"))? {
         let entry = entry?;
         if let Some(ext) = entry.path().extension() {
             if ext.eq_ignore_ascii_case("vpk") {
                 let dst = rtx_gm.join(entry.file_name());
-                let _ = link_file_best_effort(&entry.path(), &dst);
+                track(link_file_best_effort(&entry.path(), &dst, plan.link_strategy, true), &dst);
             }
         }
     }
 
     // 6. Link external folders sourceengine, platform
-    progress("Linking external folders", 40);
+    report("external", "Linking external folders", 0);
     for folder in ["sourceengine", "platform"] {
         let src = plan.vanilla.join(folder);
         let dst = plan.rtx.join(folder);
-        if src.exists() { let _ = link_dir_best_effort(&src, &dst); }
+        if src.exists() { track(link_dir_best_effort(&src, &dst, plan.link_strategy, true), &dst); }
         let _ = flatten_if_nested(&dst);
     }
 
     // 7/8 Excluded folders and copy rest of garrysmod top-level files (except excluded ext)
-    progress("Copying garrysmod contents", 60);
+    report("garrysmod", "Copying garrysmod contents", 0);
     let excluded_dirs = [
         "addons","saves","dupes","demos","settings","cache",
         "materials","models","maps","screenshots","videos","download"
@@ -117,10 +181,11 @@ pub fn perform_basic_install(plan: &InstallPlan, mut progress_cb: impl FnMut(&st
                 if excluded_ext.iter().any(|x| x.eq_ignore_ascii_case(ext)) { continue; }
             }
             let dst = rtx_gm.join(entry.file_name());
-            if !dst.exists() { let _ = std::fs::copy(&p, &dst); }
+            if !dst.exists() { let _ = copy_file(&p, &dst); }
         }
     }
-    // directories in garrysmod
+    // directories in garrysmod, sharing the stage between them
+    let mut gm_dirs = Vec::new();
     for entry in fs::read_dir(plan.vanilla.join("garrysmod"))? {
         let entry = entry?;
         let p = entry.path();
@@ -128,28 +193,167 @@ pub fn perform_basic_install(plan: &InstallPlan, mut progress_cb: impl FnMut(&st
             let name = entry.file_name();
             let name_str = name.to_string_lossy();
             if excluded_dirs.iter().any(|d| d.eq_ignore_ascii_case(&name_str)) { continue; }
-            let dst = rtx_gm.join(&name);
-            let _ = copy_dir_with_progress(&p, &dst, |_c, _t| {});
-            let _ = flatten_if_nested(&dst);
+            gm_dirs.push((p, name));
+        }
+    }
+    let n = gm_dirs.len().max(1);
+    for (i, (p, name)) in gm_dirs.iter().enumerate() {
+        let dst = rtx_gm.join(name);
+        let (lo, hi) = ((100 * i / n) as u8, (100 * (i + 1) / n) as u8);
+        let label = format!("Copying garrysmod/{}", name.to_string_lossy());
+        // Other failures are skipped as before; cancelling stops the install
+        match copy_step(p, &dst, &label, lo, hi, cancel, &mut |m, p| report("garrysmod", m, p)) {
+            Ok(s) => stats.add(s),
+            Err(e) if cancel.is_cancelled() => return Err(e),
+            Err(_) => {}
         }
+        let _ = flatten_if_nested(&dst);
     }
 
     // 9. Create blank addons
     fs::create_dir_all(rtx_gm.join("addons"))?;
 
     // 10. Symlink selected garrysmod subfolders (match C# Quick Install behavior)
+    report("links", "Linking garrysmod content folders", 0);
     // Includes content-heavy folders to avoid duplicating large data
-    for folder in [
-        "saves", "dupes", "demos", "settings", "cache", "download",
-        "materials", "models", "maps", "screenshots", "videos"
-    ] {
+    for folder in LINKED_GARRYSMOD_DIRS {
         let src = plan.vanilla.join("garrysmod").join(folder);
         let dst = rtx_gm.join(folder);
-        if src.exists() { let _ = link_dir_best_effort(&src, &dst); }
+        if src.exists() { track(link_dir_best_effort(&src, &dst, plan.link_strategy, true), &dst); }
     }
 
-    progress("Install complete", 100);
-    Ok(())
+    if copied_bytes > 0 {
+        let why = if plan.link_strategy == LinkStrategy::CopyOnly { "copy-only linking is selected" } else { "symlinks require admin or Developer Mode" };
+        report("links", &format!("Copied {:.1} GB because {}", copied_bytes as f64 / 1e9, why), 90);
+    }
+    stats.link_failures = link_failures;
+    report("links", "Install complete", 100);
+    Ok(stats)
+}
+
+/// Room kept on the target for the RTX Remix and fixes downloads and their extracted files.
+pub const QUICK_INSTALL_DOWNLOAD_ALLOWANCE: u64 = 1536 * 1024 * 1024;
+
+/// Bytes `perform_basic_install` will copy onto the target: the bin folder, the executables and
+/// the unlinked garrysmod contents, minus what an earlier install already put there. With
+/// copy-only linking the linked folders are counted too.
+pub fn estimate_install_size(plan: &InstallPlan) -> u64 {
+    let size = |p: &Path| if p.exists() { fs_extra::dir::get_size(p).unwrap_or(0) } else { 0 };
+    // Overwriting an existing copy needs only the difference
+    let needed = |src: &Path, dst: &Path| size(src).saturating_sub(size(dst));
+    let gm = plan.vanilla.join("garrysmod");
+    let rtx_gm = plan.rtx.join("garrysmod");
+
+    let mut total = needed(&plan.vanilla.join("bin"), &plan.rtx.join("bin"));
+    let exe = if plan.vanilla.join("gmod.exe").exists() { "gmod.exe" } else { "hl2.exe" };
+    for file in [exe, "steam_appid.txt"] {
+        total += needed(&plan.vanilla.join(file), &plan.rtx.join(file));
+    }
+    let copy_only = plan.link_strategy == LinkStrategy::CopyOnly;
+    for entry in fs::read_dir(&gm).into_iter().flatten().flatten() {
+        let p = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let skip = if p.is_dir() {
+            name.eq_ignore_ascii_case("addons") || LINKED_GARRYSMOD_DIRS.iter().any(|d| d.eq_ignore_ascii_case(&name))
+        } else {
+            let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+            ["dem", "log"].iter().any(|x| x.eq_ignore_ascii_case(ext)) || (ext.eq_ignore_ascii_case("vpk") && !copy_only)
+        };
+        if !skip { total += needed(&p, &rtx_gm.join(&name)); }
+    }
+    if copy_only {
+        for folder in ["sourceengine", "platform"] { total += size(&plan.vanilla.join(folder)); }
+        for folder in LINKED_GARRYSMOD_DIRS { total += size(&gm.join(folder)); }
+    }
+    total
 }
 
+/// Free bytes on the drive holding `path` (or its nearest existing ancestor), when it can be read.
+pub fn free_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    free_space_at(existing)
+}
+
+#[cfg(unix)]
+fn free_space_at(path: &Path) -> Option<u64> {
+    let st = nix::sys::statvfs::statvfs(path).ok()?;
+    Some(st.blocks_available() as u64 * st.fragment_size() as u64)
+}
+
+#[cfg(windows)]
+fn free_space_at(path: &Path) -> Option<u64> {
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+    let dir = windows::core::HSTRING::from(path.as_os_str());
+    let mut available = 0u64;
+    unsafe { GetDiskFreeSpaceExW(&dir, Some(&mut available as *mut u64), None, None) }.ok()?;
+    Some(available)
+}
+
+/// Space needed for a quick install against what the target drive has free.
+#[derive(Debug, Clone, Copy)]
+pub struct SpaceCheck {
+    pub required: u64,
+    pub available: Option<u64>,
+}
+
+impl SpaceCheck {
+    /// True when there is room, or when free space could not be determined.
+    pub fn is_enough(&self) -> bool { self.available.is_none_or(|a| a >= self.required) }
+}
+
+/// `estimate_install_size` plus the download allowance, compared with the free space on `plan.rtx`.
+pub fn check_install_space(plan: &InstallPlan) -> SpaceCheck {
+    SpaceCheck { required: estimate_install_size(plan) + QUICK_INSTALL_DOWNLOAD_ALLOWANCE, available: free_space(&plan.rtx) }
+}
+
+/// A missing or broken part of an install found by `verify_install`.
+#[derive(Debug, Clone)]
+pub struct VerifyIssue {
+    pub path: PathBuf,
+    pub problem: String,
+    pub fix: String,
+}
+
+/// Check that an install made by `perform_basic_install` is still intact: the bin folders and
+/// executable exist and every link back to the vanilla install resolves.
+pub fn verify_install(rtx_root: &Path, vanilla: &Path) -> Vec<VerifyIssue> {
+    let mut issues = Vec::new();
+    let mut issue = |path: PathBuf, problem: &str, fix: &str| issues.push(VerifyIssue { path, problem: problem.to_string(), fix: fix.to_string() });
+    const REINSTALL: &str = "Run Quick Install again";
+
+    let bin = rtx_root.join("bin");
+    if !bin.is_dir() { issue(bin.clone(), "bin folder is missing", REINSTALL); }
+    if vanilla.join("bin").join("win64").is_dir() && !bin.join("win64").is_dir() {
+        issue(bin.join("win64"), "bin/win64 folder is missing", REINSTALL);
+    }
+    let exes = [rtx_root.join("gmod.exe"), rtx_root.join("hl2.exe"), bin.join("win64").join("gmod.exe")];
+    if !exes.iter().any(|p| p.exists()) {
+        issue(rtx_root.join("gmod.exe"), "no gmod.exe or hl2.exe found", "Run Quick Install again, or Update Base Game with bin selected");
+    }
+
+    // A link whose source still exists in the vanilla install must resolve in the RTX install
+    let mut check_link = |src: PathBuf, dst: PathBuf, what: &str| {
+        if !src.exists() || dst.exists() { return; }
+        let dangling = fs::symlink_metadata(&dst).is_ok();
+        let problem = if dangling { format!("{} link is broken", what) } else { format!("{} is missing", what) };
+        issues.push(VerifyIssue { path: dst, problem, fix: "Run Quick Install again to recreate links".to_string() });
+    };
+    let rtx_gm = rtx_root.join("garrysmod");
+    for folder in LINKED_GARRYSMOD_DIRS {
+        check_link(vanilla.join("garrysmod").join(folder), rtx_gm.join(folder), &format!("garrysmod/{}", folder));
+    }
+    for folder in ["sourceengine", "platform"] {
+        check_link(vanilla.join(folder), rtx_root.join(folder), folder);
+    }
+    if let Ok(read) = fs::read_dir(vanilla.join("garrysmod")) {
+        for entry in read.flatten() {
+            let is_vpk = entry.path().extension().map(|e| e.eq_ignore_ascii_case("vpk")).unwrap_or(false);
+            if is_vpk {
+                let name = entry.file_name().to_string_lossy().to_string();
+                check_link(entry.path(), rtx_gm.join(&name), &format!("garrysmod/{}", name));
+            }
+        }
+    }
+    issues
+}
 
@@ -1,16 +1,94 @@
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+use crate::error::LauncherError;
+
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct JobProgress {
     pub message: String,
     pub percent: u8,
 }
 
+/// Structured progress emitted alongside `JobProgress`, for consumers that want more than
+/// a message and a percentage (stage labels, byte counts, file counters).
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum JobEvent {
+    Stage(String),
+    Bytes { done: u64, total: u64 },
+    FileExtracted(PathBuf),
+    Warning(String),
+    Done,
+}
+
+/// Where a job reports to. Any `FnMut(&str, u8)` works and simply ignores events.
+pub trait ProgressSink {
+    fn progress(&mut self, message: &str, percent: u8);
+    fn event(&mut self, _event: JobEvent) {}
+}
+
+impl<F: FnMut(&str, u8)> ProgressSink for F {
+    fn progress(&mut self, message: &str, percent: u8) { self(message, percent) }
+}
+
+/// Error returned by long-running operations when their `CancellationToken` was triggered.
+/// Callers can detect it with `err.is::<Cancelled>()` on an `anyhow::Error`; the public API
+/// surfaces it as `LauncherError::Cancelled`.
+#[derive(Debug, thiserror::Error)]
+#[error("operation cancelled")]
+pub struct Cancelled;
+
+/// Shared flag polled by jobs between chunks/files. Cloning shares the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn cancel(&self) { self.0.store(true, Ordering::SeqCst); }
+
+    pub fn is_cancelled(&self) -> bool { self.0.load(Ordering::SeqCst) }
+
+    /// Returns `Err(Cancelled)` once the token has been triggered.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() { Err(Cancelled) } else { Ok(()) }
+    }
+}
+
+// Operation holding the job lock, if any
+static JOB_LOCK: Mutex<Option<&'static str>> = Mutex::new(None);
+
+/// Process-wide lock for jobs that write into the install (installs, base updates, patches,
+/// fixes), so two of them never touch the same files at once. Released when dropped; a job
+/// moves it into its task so it is held until the task ends.
+#[derive(Debug)]
+pub struct JobLock(());
+
+impl JobLock {
+    /// Take the lock for `operation`, or `LauncherError::Busy` naming the operation holding it.
+    pub fn acquire(operation: &'static str) -> Result<Self, LauncherError> {
+        let mut holder = JOB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(current) = *holder { return Err(LauncherError::Busy(current.to_string())); }
+        *holder = Some(operation);
+        Ok(Self(()))
+    }
+
+    /// The operation holding the lock, for disabling buttons and telling the user what is running.
+    pub fn holder() -> Option<&'static str> { *JOB_LOCK.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+
+impl Drop for JobLock {
+    fn drop(&mut self) { *JOB_LOCK.lock().unwrap_or_else(|e| e.into_inner()) = None; }
+}
+
 pub struct JobHandle {
     pub join: JoinHandle<()>,
     pub rx: Receiver<JobProgress>,
+    pub cancel: CancellationToken,
 }
 
 pub struct JobRunner;
@@ -18,14 +96,34 @@ pub struct JobRunner;
 impl JobRunner {
     pub fn spawn_dummy_job() -> JobHandle {
         let (tx, rx): (Sender<JobProgress>, Receiver<JobProgress>) = mpsc::channel();
+        let cancel = CancellationToken::new();
+        let token = cancel.clone();
         let join = thread::spawn(move || {
             for i in 0..=100u8 {
+                if token.is_cancelled() {
+                    let _ = tx.send(JobProgress { message: "Cancelled".into(), percent: 100 });
+                    return;
+                }
                 let _ = tx.send(JobProgress { message: format!("Working... {i}%"), percent: i });
                 thread::sleep(Duration::from_millis(30));
             }
         });
-        JobHandle { join, rx }
+        JobHandle { join, rx, cancel }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn job_lock_is_exclusive_until_dropped() {
+        let lock = JobLock::acquire("Patching").unwrap();
+        assert_eq!(JobLock::holder(), Some("Patching"));
+        let err = JobLock::acquire("Fixes install").unwrap_err();
+        assert_eq!(err.to_string(), "Patching is already running");
+        drop(lock);
+        assert_eq!(JobLock::holder(), None);
+        drop(JobLock::acquire("Fixes install").unwrap());
+    }
+}
@@ -1,7 +1,18 @@
 use crate::settings::AppSettings;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// How the game window is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WindowMode {
+    /// Windowed without a border, sized to the configured resolution
+    #[default]
+    Borderless,
+    Windowed,
+    Fullscreen,
+}
+
 fn split_args_quoted(src: &str) -> Vec<String> {
     let mut out: Vec<String> = Vec::new();
     let mut cur = String::new();
@@ -36,14 +47,19 @@ fn split_args_quoted(src: &str) -> Vec<String> {
 pub fn build_launch_args(settings: &AppSettings) -> Vec<String> {
     let mut args: Vec<String> = Vec::new();
     if settings.console_enabled { args.push("-console".into()); }
-    // Always enforce DX level 90 as requested (two separate argv entries)
+    // DX level defaults to 90, which Remix expects (two separate argv entries)
     args.push("-dxlevel".into());
-    args.push("90".into());
+    args.push(settings.dxlevel.unwrap_or(90).to_string());
     // D3D9Ex disable and windowing flags (each token separately)
-    args.push("+mat_disable_d3d9ex".into()); args.push("1".into());
-    args.push("-nod3d9ex".into());
-    args.push("-windowed".into());
-    args.push("-noborder".into());
+    if settings.disable_d3d9ex {
+        args.push("+mat_disable_d3d9ex".into()); args.push("1".into());
+        args.push("-nod3d9ex".into());
+    }
+    match settings.window_mode {
+        WindowMode::Borderless => { args.push("-windowed".into()); args.push("-noborder".into()); }
+        WindowMode::Windowed => args.push("-windowed".into()),
+        WindowMode::Fullscreen => args.push("-fullscreen".into()),
+    }
     if let (Some(w), Some(h)) = (settings.width, settings.height) {
         if w > 0 && h > 0 {
             args.push("-w".into()); args.push(w.to_string());
@@ -61,14 +77,72 @@ pub fn build_launch_args(settings: &AppSettings) -> Vec<String> {
     args
 }
 
+/// WINEDLLOVERRIDES for Linux launches when none is configured; Remix ships its own d3d9.dll.
+pub const DEFAULT_DLL_OVERRIDES: &str = "d3d9=n,b";
+
+/// Check a WINEDLLOVERRIDES value: `;`-separated `name[,name]=mode` entries where mode is
+/// empty (disabled) or a comma list of `n`/`b`. Returns it trimmed.
+pub fn validate_dll_overrides(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() { return Err("empty".to_string()); }
+    for entry in value.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let Some((names, modes)) = entry.split_once('=') else { return Err(format!("'{}' has no '='", entry)); };
+        let names_ok = names.split(',').all(|n| !n.trim().is_empty() && n.trim().chars().all(|c| c.is_ascii_alphanumeric() || "_-.*".contains(c)));
+        if !names_ok { return Err(format!("'{}' has an invalid DLL name", entry)); }
+        if !modes.trim().is_empty() && !modes.split(',').all(|m| matches!(m.trim(), "n" | "b")) {
+            return Err(format!("'{}' has an invalid mode (use n, b, n,b or b,n)", entry));
+        }
+    }
+    Ok(value.to_string())
+}
+
+/// `KEY=VALUE` pairs from newline-separated text; blank lines, `#` comments and lines
+/// without a key are skipped.
+pub fn parse_env_lines(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .filter(|(k, _)| !k.is_empty())
+        .collect()
+}
+
+/// A started game; wait on `child` to learn how it exited.
+pub struct LaunchedGame {
+    pub child: std::process::Child,
+    /// File receiving the game's stdout/stderr, when captured
+    pub output_log: Option<PathBuf>,
+}
+
+/// Game executable in an RTX install, on any platform: `bin/win64/gmod.exe` (64-bit layout),
+/// then `gmod.exe`, then `hl2.exe` (32-bit). None when the install has none of them.
+pub fn resolve_game_exe(rtx_root: &Path) -> Option<PathBuf> {
+    [rtx_root.join("bin").join("win64").join("gmod.exe"), rtx_root.join("gmod.exe"), rtx_root.join("hl2.exe")]
+        .into_iter()
+        .find(|p| p.is_file())
+}
+
+/// `exe` and `args` as one shell-pasteable line, quoting anything with spaces or quotes.
+pub fn format_command_line(exe: &Path, args: &[String]) -> String {
+    let quote = |s: &str| {
+        if !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'') { return s.to_string(); }
+        // Backslashes are left alone so Windows paths paste as-is
+        format!("\"{}\"", s.replace('"', "\\\""))
+    };
+    std::iter::once(quote(&exe.display().to_string()))
+        .chain(args.iter().map(|a| quote(a)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(windows)]
-pub fn launch_game(exe_path: PathBuf, settings: &AppSettings) -> std::io::Result<()> {
+pub fn launch_game(exe_path: PathBuf, settings: &AppSettings) -> std::io::Result<LaunchedGame> {
     let args = build_launch_args(settings);
     let mut cmd = Command::new(&exe_path);
     cmd.args(args);
     if let Some(dir) = exe_path.parent() { cmd.current_dir(dir); }
-    let _ = cmd.spawn()?;
-    Ok(())
+    Ok(LaunchedGame { child: cmd.spawn()?, output_log: None })
 }
 
 #[cfg(unix)]
@@ -77,20 +151,30 @@ fn detect_linux_steam_root(settings: &AppSettings) -> Option<PathBuf> {
         let p = PathBuf::from(override_path);
         if p.exists() { return Some(p); }
     }
-    let mut roots: Vec<PathBuf> = Vec::new();
-    if let Ok(home) = std::env::var("HOME") {
-        let home = PathBuf::from(home);
-        roots.push(home.join(".local/share/Steam"));
-        roots.push(home.join(".steam/steam"));
-        roots.push(home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"));
-    }
-    roots.push(PathBuf::from("/usr/lib/steam"));
-    roots.into_iter().find(|r| r.exists())
+    crate::steam::steam_roots().into_iter().find(|r| r.exists())
+}
+
+// compatibilitytools.d folders (Proton-GE and friends) of the active root and every other known root
+#[cfg(unix)]
+fn compat_tool_dirs(steam_root: &Path) -> Vec<PathBuf> {
+    std::iter::once(steam_root.to_path_buf())
+        .chain(crate::steam::steam_roots())
+        .map(|r| r.join("compatibilitytools.d"))
+        .filter(|d| d.is_dir())
+        .collect()
 }
 
 #[cfg(unix)]
-fn detect_linux_proton(settings: &AppSettings, steam_root: &PathBuf) -> Option<PathBuf> {
-    if let Some(user) = &settings.linux_proton_path { let p = PathBuf::from(user); if p.exists() { return Some(p); } }
+fn detect_linux_proton(settings: &AppSettings, steam_root: &Path) -> Option<PathBuf> {
+    if let Some(user) = &settings.linux_proton_path {
+        let p = PathBuf::from(user);
+        if p.exists() { return Some(p); }
+        tracing::warn!("Selected Proton {} no longer exists", p.display());
+        // The build may have moved (e.g. reinstalled to another library); find it again by label
+        if let Some(label) = &settings.linux_selected_proton_label {
+            if let Some((_, path)) = list_proton_builds(settings).into_iter().find(|(l, _)| l == label) { return Some(PathBuf::from(path)); }
+        }
+    }
     let mut candidates: Vec<PathBuf> = Vec::new();
     // Official Proton installs
     candidates.push(steam_root.join("steamapps/common/Proton - Experimental/proton"));
@@ -106,22 +190,11 @@ fn detect_linux_proton(settings: &AppSettings, steam_root: &PathBuf) -> Option<P
         }
     }
     // Proton-GE in user compatibilitytools.d
-    let compat_dirs = [
-        steam_root.join("compatibilitytools.d"),
-        PathBuf::from("~/.local/share/Steam/compatibilitytools.d"),
-        PathBuf::from("~/.steam/root/compatibilitytools.d"),
-        PathBuf::from("~/.steam/steam/compatibilitytools.d"),
-        PathBuf::from("~/.var/app/com.valvesoftware.Steam/.local/share/Steam/compatibilitytools.d"),
-    ];
-    for dir in compat_dirs.iter() {
-        let d = shellexpand::tilde(&dir.display().to_string()).to_string();
-        let d = PathBuf::from(d);
-        if d.is_dir() {
-            if let Ok(read) = std::fs::read_dir(&d) {
-                for entry in read.flatten() {
-                    let p = entry.path().join("proton");
-                    if p.exists() { candidates.push(p); }
-                }
+    for d in compat_tool_dirs(steam_root) {
+        if let Ok(read) = std::fs::read_dir(&d) {
+            for entry in read.flatten() {
+                let p = entry.path().join("proton");
+                if p.exists() { candidates.push(p); }
             }
         }
     }
@@ -148,23 +221,12 @@ pub fn list_proton_builds(settings: &AppSettings) -> Vec<(String, String)> {
             }
         }
         // Proton-GE
-        let compat_dirs = [
-            root.join("compatibilitytools.d"),
-            PathBuf::from("~/.local/share/Steam/compatibilitytools.d"),
-            PathBuf::from("~/.steam/root/compatibilitytools.d"),
-            PathBuf::from("~/.steam/steam/compatibilitytools.d"),
-            PathBuf::from("~/.var/app/com.valvesoftware.Steam/.local/share/Steam/compatibilitytools.d"),
-        ];
-        for dir in compat_dirs.iter() {
-            let d = shellexpand::tilde(&dir.display().to_string()).to_string();
-            let d = PathBuf::from(d);
-            if d.is_dir() {
-                if let Ok(read) = std::fs::read_dir(&d) {
-                    for entry in read.flatten() {
-                        let label = entry.file_name().to_string_lossy().to_string();
-                        let p = entry.path().join("proton");
-                        if p.exists() { out.push((label, p.display().to_string())); }
-                    }
+        for d in compat_tool_dirs(&root) {
+            if let Ok(read) = std::fs::read_dir(&d) {
+                for entry in read.flatten() {
+                    let label = entry.file_name().to_string_lossy().to_string();
+                    let p = entry.path().join("proton");
+                    if p.exists() { out.push((label, p.display().to_string())); }
                 }
             }
         }
@@ -178,33 +240,90 @@ pub fn list_proton_builds(settings: &AppSettings) -> Vec<(String, String)> {
     out
 }
 
+/// The GMod (app 4000) compatdata folder holding its Proton prefix.
 #[cfg(unix)]
-pub fn launch_game(exe_path: PathBuf, settings: &AppSettings) -> std::io::Result<()> {
-    let args = build_launch_args(settings);
-    let Some(parent_dir) = exe_path.parent().map(|p| p.to_path_buf()) else { return Err(std::io::Error::new(std::io::ErrorKind::Other, "invalid exe path")); };
+pub fn proton_prefix_dir(settings: &AppSettings) -> Option<PathBuf> {
+    detect_linux_steam_root(settings).map(|root| root.join("steamapps/compatdata/4000"))
+}
+
+/// True when the prefix was started but never finished (no `pfx/drive_c`), e.g. after an
+/// interrupted first run or a Proton upgrade gone wrong. A missing prefix is not broken.
+#[cfg(unix)]
+pub fn proton_prefix_is_broken(settings: &AppSettings) -> bool {
+    proton_prefix_dir(settings)
+        .filter(|compat| compat.join("pfx").exists() || compat.join("version").exists())
+        .is_some_and(|compat| !compat.join("pfx/drive_c").is_dir())
+}
+
+/// Delete the prefix and leave an empty compatdata folder; Proton rebuilds it on the next launch.
+#[cfg(unix)]
+pub fn recreate_proton_prefix(settings: &AppSettings) -> std::io::Result<PathBuf> {
+    let compat = proton_prefix_dir(settings)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Steam root not found"))?;
+    if compat.exists() { std::fs::remove_dir_all(&compat)?; }
+    std::fs::create_dir_all(&compat)?;
+    tracing::info!("Recreated Proton prefix at {}", compat.display());
+    Ok(compat)
+}
+
+// Enabled wrapper commands that are installed, outermost first; missing ones are skipped with a note
+#[cfg(unix)]
+fn launch_wrappers(settings: &AppSettings) -> Vec<PathBuf> {
+    [(settings.linux_use_gamemode, "gamemoderun"), (settings.linux_use_mangohud, "mangohud")]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .filter_map(|(_, name)| match which::which(name) {
+            Ok(p) => Some(p),
+            Err(_) => { tracing::info!("{} is enabled but not installed; launching without it", name); None }
+        })
+        .collect()
+}
+
+/// `proton run <exe>` in the GMod (4000) prefix, for running Windows binaries on Linux,
+/// optionally inside `wrappers` (e.g. `gamemoderun mangohud proton run ...`).
+#[cfg(unix)]
+pub(crate) fn proton_run_command(settings: &AppSettings, exe_path: &Path, wrappers: &[PathBuf]) -> std::io::Result<Command> {
     let steam_root = detect_linux_steam_root(settings)
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Steam root not found"))?;
     let compat = steam_root.join("steamapps/compatdata/4000");
-    // Ensure compatdata dir exists so Proton/Steam can set up the prefix
+    // Ensure compatdata dir exists so Proton/Steam can set up the prefix; an existing one is left as is
     let _ = std::fs::create_dir_all(&compat);
+    if proton_prefix_is_broken(settings) { tracing::warn!("Proton prefix {} looks incomplete (no pfx/drive_c); recreate it from Settings if launching fails", compat.display()); }
 
     // Direct Proton invocation
     let proton = detect_linux_proton(settings, &steam_root)
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Proton not found"))?;
+    let mut cmd = match wrappers.split_first() {
+        Some((outer, inner)) => { let mut c = Command::new(outer); c.args(inner).arg(&proton); c }
+        None => Command::new(&proton),
+    };
+    cmd.arg("run");
+    // Steam likes exe path relative to the game root; Proton `run` accepts abs. Keep abs path.
+    cmd.arg(exe_path);
+    cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", &steam_root);
+    cmd.env("STEAM_COMPAT_DATA_PATH", &compat);
+    Ok(cmd)
+}
+
+#[cfg(unix)]
+pub fn launch_game(exe_path: PathBuf, settings: &AppSettings) -> std::io::Result<LaunchedGame> {
+    let args = build_launch_args(settings);
+    let Some(parent_dir) = exe_path.parent().map(|p| p.to_path_buf()) else { return Err(std::io::Error::other("invalid exe path")); };
+    let mut cmd = proton_run_command(settings, &exe_path, &launch_wrappers(settings))?;
     // Best-effort ensure Steam client is running so SteamAPI can initialize
     if let Ok(steam_bin) = which::which("steam") {
         let _ = std::process::Command::new(steam_bin).arg("-silent").spawn();
         // a brief delay can help SteamAPI attach; non-blocking preferred, so skip sleep here
     }
-    let mut cmd = Command::new(&proton);
-    cmd.arg("run");
-    // Steam likes exe path relative to the game root; Proton `run` accepts abs. Keep abs path.
-    cmd.arg(&exe_path);
     cmd.args(args);
     cmd.current_dir(&parent_dir);
-    cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", &steam_root);
-    cmd.env("STEAM_COMPAT_DATA_PATH", &compat);
-    cmd.env("WINEDLLOVERRIDES", "d3d9=n,b");
+    let overrides = match settings.linux_dll_overrides.as_deref().map(validate_dll_overrides) {
+        Some(Ok(v)) => v,
+        Some(Err(e)) => { tracing::warn!("Ignoring invalid DLL overrides ({}); using {}", e, DEFAULT_DLL_OVERRIDES); DEFAULT_DLL_OVERRIDES.to_string() }
+        None => DEFAULT_DLL_OVERRIDES.to_string(),
+    };
+    tracing::info!("WINEDLLOVERRIDES={}", overrides);
+    cmd.env("WINEDLLOVERRIDES", overrides);
     // Provide Steam App ID hints and steam_appid.txt to satisfy SteamAPI
     cmd.env("SteamAppId", "4000");
     cmd.env("SteamAppID", "4000");
@@ -212,8 +331,114 @@ pub fn launch_game(exe_path: PathBuf, settings: &AppSettings) -> std::io::Result
     cmd.env("SteamOverlayGameId", "4000");
     let _ = std::fs::write(parent_dir.join("steam_appid.txt"), b"4000\n");
     if settings.linux_enable_proton_log { cmd.env("PROTON_LOG", "1"); }
-    let _ = cmd.spawn()?;
+    // User variables go last so they can override any of the above
+    for (key, value) in parse_env_lines(&settings.linux_extra_env) {
+        // Values can hold tokens or API keys, so only the name goes to the log
+        tracing::info!("Launch env {} set", key);
+        cmd.env(key, value);
+    }
+    // Proton and Wine report start-up failures on stdout/stderr; keep the latest run's output
+    let logs = crate::logging::logs_dir();
+    let _ = std::fs::create_dir_all(&logs);
+    let output_log = logs.join("game-output.log");
+    // Capturing is best effort; the game still launches with its output on the console
+    let output_log = match std::fs::File::create(&output_log).and_then(|out| Ok((out.try_clone()?, out))) {
+        Ok((out, err)) => {
+            cmd.stdout(out);
+            cmd.stderr(err);
+            Some(output_log)
+        }
+        Err(e) => {
+            tracing::warn!("Not capturing game output in {} ({})", output_log.display(), e);
+            None
+        }
+    };
+    Ok(LaunchedGame { child: cmd.spawn()?, output_log })
+}
+
+/// Open a folder in the platform file manager (Explorer, Finder, or xdg-open).
+pub fn open_in_file_manager(path: &Path) -> std::io::Result<()> {
+    #[cfg(windows)]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let program = "xdg-open";
+    Command::new(program).arg(path).spawn()?;
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> AppSettings {
+        AppSettings { console_enabled: false, width: None, height: None, load_workshop_addons: true, ..Default::default() }
+    }
+
+    #[test]
+    fn defaults_keep_rtx_flags() {
+        assert_eq!(build_launch_args(&base()), ["-dxlevel", "90", "+mat_disable_d3d9ex", "1", "-nod3d9ex", "-windowed", "-noborder"]);
+    }
+
+    #[test]
+    fn game_exe_prefers_64_bit_layout() {
+        let dir = std::env::temp_dir().join(format!("rtxlauncher-exe-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("bin/win64")).unwrap();
+        assert_eq!(resolve_game_exe(&dir), None);
+        std::fs::write(dir.join("hl2.exe"), b"").unwrap();
+        assert_eq!(resolve_game_exe(&dir), Some(dir.join("hl2.exe")));
+        std::fs::write(dir.join("gmod.exe"), b"").unwrap();
+        assert_eq!(resolve_game_exe(&dir), Some(dir.join("gmod.exe")));
+        std::fs::write(dir.join("bin/win64/gmod.exe"), b"").unwrap();
+        assert_eq!(resolve_game_exe(&dir), Some(dir.join("bin").join("win64").join("gmod.exe")));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn command_line_quotes_spaced_args() {
+        let line = format_command_line(Path::new("/games/GarrysMod RTX/gmod.exe"), &["-w".into(), "1280".into(), "gm construct".into()]);
+        assert_eq!(line, r#""/games/GarrysMod RTX/gmod.exe" -w 1280 "gm construct""#);
+    }
+
+    #[test]
+    fn dll_override_validation() {
+        assert_eq!(validate_dll_overrides(" d3d9=n,b;dxgi=n ").as_deref(), Ok("d3d9=n,b;dxgi=n"));
+        assert!(validate_dll_overrides("d3d9,d3d11=b;nvapi=").is_ok());
+        assert!(validate_dll_overrides("d3d9").is_err());
+        assert!(validate_dll_overrides("d3d9=native").is_err());
+        assert!(validate_dll_overrides("=n").is_err());
+    }
+
+    #[test]
+    fn env_lines_skip_comments_and_junk() {
+        let env = parse_env_lines("DXVK_HUD=fps\n# note\n\n MANGOHUD = 1\nnot a pair\n=x\nPROTON_NO_ESYNC=1");
+        assert_eq!(env, [
+            ("DXVK_HUD".to_string(), "fps".to_string()),
+            ("MANGOHUD".to_string(), "1".to_string()),
+            ("PROTON_NO_ESYNC".to_string(), "1".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn fullscreen_without_d3d9ex_override() {
+        let s = AppSettings { window_mode: WindowMode::Fullscreen, disable_d3d9ex: false, dxlevel: Some(95), ..base() };
+        assert_eq!(build_launch_args(&s), ["-dxlevel", "95", "-fullscreen"]);
+    }
 
+    #[test]
+    fn windowed_with_resolution_and_custom_args() {
+        let s = AppSettings {
+            window_mode: WindowMode::Windowed,
+            width: Some(1280),
+            height: Some(720),
+            custom_launch_options: Some("+map \"gm construct\"".into()),
+            ..base()
+        };
+        assert_eq!(build_launch_args(&s), [
+            "-dxlevel", "90", "+mat_disable_d3d9ex", "1", "-nod3d9ex", "-windowed",
+            "-w", "1280", "-h", "720", "+map", "gm construct",
+        ]);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod settings;
+pub mod error;
 pub mod jobs;
 pub mod elevation;
 pub mod steam;
@@ -6,6 +7,7 @@ pub mod fs_linker;
 pub mod install;
 pub mod mount;
 pub mod github;
+pub mod download;
 pub mod remix_installer;
 pub mod rtxio;
 pub mod usda;
@@ -13,23 +15,29 @@ pub mod update;
 pub mod launch;
 pub mod logging;
 pub mod patching;
+pub mod progress;
+pub mod sources;
+mod token_store;
 
-pub use settings::{AppSettings, SettingsStore};
-pub use jobs::{JobHandle, JobProgress, JobRunner};
+pub use settings::{AppSettings, SettingsLocation, SettingsStore};
+pub use error::{LauncherError, LauncherResult};
+pub use jobs::{JobHandle, JobProgress, JobEvent, ProgressSink, JobRunner, JobLock, CancellationToken, Cancelled};
 pub use elevation::{is_elevated, relaunch_as_admin};
-pub use steam::{detect_gmod_install_folder, detect_install_folder_path};
-pub use fs_linker::{link_dir_best_effort, link_file_best_effort, copy_dir_with_progress};
-pub use install::{InstallPlan, perform_basic_install};
-pub use mount::{mount_game, unmount_game, is_game_mounted};
-pub use github::{fetch_releases, GitHubAsset, GitHubRelease, GitHubRateLimit, set_personal_access_token, load_personal_access_token};
-pub use remix_installer::{select_best_asset, analyze_zip_for_layout, install_remix_from_release, install_fixes_from_release, select_best_package_asset};
+pub use steam::{detect_gmod_install_folder, detect_all_gmod_installs, detect_install_folder_path};
+pub use fs_linker::{link_dir_best_effort, link_file_best_effort, copy_dir_with_progress, LinkMethod, LinkStrategy};
+pub use install::{InstallPlan, InstallStats, perform_basic_install, verify_install, VerifyIssue, estimate_install_size, free_space, check_install_space, SpaceCheck, QUICK_INSTALL_DOWNLOAD_ALLOWANCE};
+pub use mount::{mount_game, unmount_game, unmount_all, list_mounts, is_game_mounted, MountableGame, MOUNTABLE_GAMES};
+pub use github::{fetch_releases, is_offline, latest_release, validate_personal_access_token, github_api_base, github_raw_base, set_github_bases, validate_github_base, DEFAULT_GITHUB_API_BASE, DEFAULT_GITHUB_RAW_BASE, GitHubAsset, GitHubRelease, GitHubRateLimit, set_personal_access_token, load_personal_access_token};
+pub use remix_installer::{DEFAULT_IGNORE_PATTERNS, select_best_asset, analyze_zip_for_layout, install_remix_from_release, install_remix_asset, install_fixes_from_release, select_best_package_asset, uninstall_fixes, UninstallReport, ConflictAction, ConflictHandler, FileConflict};
 pub use rtxio::{has_rtxio_packages, extract_packages};
-pub use usda::apply_usda_fixes;
-pub use update::{detect_updates, apply_updates, FileUpdateInfo};
-pub use launch::{build_launch_args, launch_game};
+pub use usda::{apply_usda_fixes, USDA_FIXES_SOURCES};
+pub use update::{detect_updates, scan_updates, update_selected, apply_updates, check_launcher_update, FileUpdateInfo, UpdateScan, UpdateCompare, LinkedEntry};
+pub use launch::{build_launch_args, launch_game, LaunchedGame, open_in_file_manager, WindowMode, resolve_game_exe, format_command_line, validate_dll_overrides, DEFAULT_DLL_OVERRIDES, parse_env_lines};
 #[cfg(unix)]
-pub use launch::list_proton_builds;
-pub use logging::init_logging;
-pub use patching::{apply_patches_from_repo, PatchResult};
+pub use launch::{list_proton_builds, proton_prefix_dir, proton_prefix_is_broken, recreate_proton_prefix};
+pub use logging::{init_logging, logs_dir, set_log_level, DEFAULT_LOG_RETENTION, LOG_LEVELS};
+pub use sources::{RepoSource, SourceLists};
+pub use progress::WeightedProgress;
+pub use patching::{apply_patches_from_repo, failed_patch_files, patch_report_path, reapply_failed, restore_patch_backup, short_hash, PatchOptions, PatchResult, PatchedFile};
 
 
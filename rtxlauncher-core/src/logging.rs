@@ -1,24 +1,60 @@
-use tracing_subscriber::{fmt, EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry, layer::SubscriberExt, util::SubscriberInitExt};
 use tracing_appender::{rolling, non_blocking::WorkerGuard};
 use once_cell::sync::OnceCell;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 static INIT: OnceCell<()> = OnceCell::new();
 static FILE_GUARD: OnceCell<WorkerGuard> = OnceCell::new();
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
 
-pub fn init_logging() {
+/// Levels offered in the settings dropdown, most to least severe.
+pub const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Number of daily log files kept when the setting is absent.
+pub const DEFAULT_LOG_RETENTION: usize = 7;
+
+/// Directory the daily log files are written to (relative to the working directory).
+pub fn logs_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_default().join("logs")
+}
+
+/// Delete all but the `keep` most recent `rtxlauncher.log.*` files in `dir`.
+/// The daily suffix is an ISO date, so name order is age order.
+pub fn prune_old_logs(dir: &Path, keep: usize) -> std::io::Result<usize> {
+    let mut logs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("rtxlauncher.log.") && e.path().is_file())
+        .map(|e| e.path())
+        .collect();
+    logs.sort();
+    let excess = logs.len().saturating_sub(keep.max(1));
+    let mut removed = 0;
+    for p in &logs[..excess] {
+        if fs::remove_file(p).is_ok() { removed += 1; }
+    }
+    Ok(removed)
+}
+
+/// Set up console and daily file logging, keeping at most `keep_logs` old log files.
+/// `level` is used unless `RUST_LOG` is set; it can be changed later with `set_log_level`.
+pub fn init_logging(keep_logs: usize, level: &str) {
     let _ = INIT.get_or_init(|| {
-        let _ = fs::create_dir_all("logs");
-        let file_appender = rolling::daily("logs", "rtxlauncher.log");
+        let dir = logs_dir();
+        let _ = fs::create_dir_all(&dir);
+        let _ = prune_old_logs(&dir, keep_logs);
+        let file_appender = rolling::daily(&dir, "rtxlauncher.log");
         let (nb_file, guard) = tracing_appender::non_blocking(file_appender);
         let _ = FILE_GUARD.set(guard); // keep guard alive for program lifetime
 
-        // Console layer
-        let console_layer = fmt::layer().with_target(false);
+        // Console layer; stderr keeps stdout free for --headless progress
+        let console_layer = fmt::layer().with_writer(std::io::stderr).with_target(false);
         // File layer
         let file_layer = fmt::layer().with_writer(nb_file).with_target(false);
 
-        let env = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+        let env = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+        let (env, handle) = reload::Layer::new(env);
+        let _ = FILTER_HANDLE.set(handle);
         tracing_subscriber::registry()
             .with(env)
             .with(console_layer)
@@ -27,6 +63,14 @@ pub fn init_logging() {
     });
 }
 
+/// Swap the active filter at runtime, e.g. after the level is changed in settings.
+pub fn set_log_level(level: &str) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_new(level)?;
+    let handle = FILTER_HANDLE.get().ok_or_else(|| anyhow::anyhow!("logging not initialized"))?;
+    handle.reload(filter)?;
+    Ok(())
+}
+
 /// Emit throttled progress updates to the UI and tracing logs.
 /// Ensures messages with the same prefix (e.g., "Downloading:") are not emitted more than once every `min_interval_ms`.
 pub struct ProgressThrottle {
@@ -52,4 +96,24 @@ impl ProgressThrottle {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn prune_keeps_newest_logs() {
+        let dir = std::env::temp_dir().join(format!("rtxlauncher-logs-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for day in ["2024-01-01", "2024-01-03", "2024-01-02", "2024-01-04"] {
+            fs::write(dir.join(format!("rtxlauncher.log.{day}")), b"").unwrap();
+        }
+        fs::write(dir.join("other.txt"), b"").unwrap();
+        assert_eq!(prune_old_logs(&dir, 2).unwrap(), 2);
+        assert!(dir.join("rtxlauncher.log.2024-01-03").exists());
+        assert!(dir.join("rtxlauncher.log.2024-01-04").exists());
+        assert!(!dir.join("rtxlauncher.log.2024-01-02").exists());
+        assert!(dir.join("other.txt").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
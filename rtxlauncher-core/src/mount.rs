@@ -1,55 +1,65 @@
 use anyhow::{Result, Context};
 use std::fs;
-use std::path::PathBuf;
-use crate::fs_linker::{link_dir_best_effort};
+use std::path::{Path, PathBuf};
+use crate::fs_linker::{link_dir_best_effort, LinkMethod};
+use crate::steam::detect_install_folder_path;
+use crate::settings::SettingsStore;
 use tracing::info;
+use crate::logging::ProgressThrottle;
+
+/// A Source game whose content can be linked into this install.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MountableGame {
+    pub display: &'static str,
+    /// Directory under steamapps/common
+    pub install_folder: &'static str,
+    /// Content directory inside the install (models, maps, materials)
+    pub game_folder: &'static str,
+    /// Directory under rtx-remix/mods, for games that ship a Remix mod
+    pub remix_mod: Option<&'static str>,
+}
+
+pub const MOUNTABLE_GAMES: &[MountableGame] = &[
+    MountableGame { display: "Half-Life 2 RTX", install_folder: "Half-Life 2 RTX", game_folder: "hl2rtx", remix_mod: Some("hl2rtx") },
+    MountableGame { display: "Portal RTX", install_folder: "Portal RTX", game_folder: "portalrtx", remix_mod: Some("portalrtx") },
+    MountableGame { display: "Half-Life 2", install_folder: "Half-Life 2", game_folder: "hl2", remix_mod: None },
+    MountableGame { display: "Half-Life 2: Episode One", install_folder: "Half-Life 2", game_folder: "episodic", remix_mod: None },
+    MountableGame { display: "Half-Life 2: Episode Two", install_folder: "Half-Life 2", game_folder: "ep2", remix_mod: None },
+    MountableGame { display: "Half-Life: Source", install_folder: "Half-Life 2", game_folder: "hl1", remix_mod: None },
+    MountableGame { display: "Portal", install_folder: "Portal", game_folder: "portal", remix_mod: None },
+    MountableGame { display: "Counter-Strike: Source", install_folder: "Counter-Strike Source", game_folder: "cstrike", remix_mod: None },
+];
 
 fn get_this_install_folder() -> Result<PathBuf> {
-    let exe = std::env::current_exe()?;
-    Ok(exe.parent().unwrap().to_path_buf())
+    // Mounts go into the configured RTX install, which may live outside the launcher's folder
+    Ok(SettingsStore::new()?.load()?.install_dir())
 }
 
-pub fn is_game_mounted(game_folder: &str, _install_folder: &str, remix_mod_folder: &str) -> bool {
+pub fn is_game_mounted(game_folder: &str, remix_mod_folder: &str) -> bool {
     if let Ok(gmod_path) = get_this_install_folder() {
         let src_mount = gmod_path.join("garrysmod").join("addons").join(format!("mount-{}", game_folder));
         let remix_mount = gmod_path.join("rtx-remix").join("mods").join(format!("mount-{}-{}", game_folder, remix_mod_folder));
-        return src_mount.exists() && remix_mount.exists();
+        return src_mount.exists() && (remix_mod_folder.is_empty() || remix_mount.exists());
     }
     false
 }
 
-pub fn mount_game(game_folder: &str, install_folder: &str, remix_mod_folder: &str, mut progress_cb: impl FnMut(&str)) -> Result<()> {
-    let mut progress = |m: &str| { info!("{}", m); progress_cb(m); };
-    progress("Mounting content...");
-    let gmod_path = get_this_install_folder()?;
-    let install_path = find_install_folder(install_folder).with_context(|| format!("Install folder '{}' not found", install_folder))?;
+/// Link a Source game's content into this install. `install_path` is the game's own
+/// directory when the caller already knows it; otherwise `install_folder` is looked up
+/// across all Steam libraries. An empty `remix_mod_folder` mounts game content only. Progress is reported as links made out of links planned.
+pub fn mount_game(game_folder: &str, install_folder: &str, remix_mod_folder: &str, install_path: Option<&Path>, mut progress_cb: impl FnMut(&str, u8)) -> Result<()> {
+    let mut progress = |m: &str, p: u8| { info!("{}", m); progress_cb(m, p); };
+    progress("Mounting content...", 0);
+    let settings = SettingsStore::new()?.load()?;
+    let gmod_path = settings.install_dir();
+    let install_path = resolve_install_path(install_folder, install_path).with_context(|| format!("Install folder '{}' not found", install_folder))?;
+    let addons = gmod_path.join("garrysmod").join("addons");
 
+    // Collect every (source, link) pair first so progress can be reported as a percentage
+    let mut links: Vec<(PathBuf, PathBuf)> = Vec::new();
     // Source content
     let source_content_path = install_path.join(game_folder);
-    let source_content_mount_path = gmod_path.join("garrysmod").join("addons").join(format!("mount-{}", game_folder));
-    fs::create_dir_all(&source_content_mount_path)?;
-
-    // Link models
-    let models = source_content_path.join("models");
-    if models.exists() { let _ = link_dir_best_effort(&models, &source_content_mount_path.join("models")); }
-    // Link maps
-    let maps = source_content_path.join("maps");
-    if maps.exists() { let _ = link_dir_best_effort(&maps, &source_content_mount_path.join("maps")); }
-    // Link materials subfolders except excluded
-    let materials = source_content_path.join("materials");
-    if materials.exists() {
-        let dst_mat = source_content_mount_path.join("materials");
-        fs::create_dir_all(&dst_mat).ok();
-        let dont_link = ["vgui", "dev", "editor", "perftest", "tools"];
-        for entry in fs::read_dir(&materials)? {
-            let entry = entry?;
-            if entry.path().is_dir() {
-                let name = entry.file_name();
-                if dont_link.iter().any(|x| x.eq_ignore_ascii_case(&name.to_string_lossy())) { continue; }
-                let _ = link_dir_best_effort(&entry.path(), &dst_mat.join(name));
-            }
-        }
-    }
+    plan_content_links(&source_content_path, &addons.join(format!("mount-{}", game_folder)), &mut links)?;
 
     // Custom content
     let custom = source_content_path.join("custom");
@@ -57,46 +67,68 @@ pub fn mount_game(game_folder: &str, install_folder: &str, remix_mod_folder: &st
         for entry in fs::read_dir(&custom)? {
             let entry = entry?;
             if entry.path().is_dir() {
-                let mount_dst = gmod_path.join("garrysmod").join("addons").join(format!("mount-{}-{}", game_folder, entry.file_name().to_string_lossy()));
-                fs::create_dir_all(&mount_dst).ok();
-                // link subfolders similar to base
-                let models = entry.path().join("models");
-                if models.exists() { let _ = link_dir_best_effort(&models, &mount_dst.join("models")); }
-                let maps = entry.path().join("maps");
-                if maps.exists() { let _ = link_dir_best_effort(&maps, &mount_dst.join("maps")); }
-                let materials = entry.path().join("materials");
-                if materials.exists() {
-                    let dst_mat = mount_dst.join("materials");
-                    fs::create_dir_all(&dst_mat).ok();
-                    let dont_link = ["vgui", "dev", "editor", "perftest", "tools"];
-                    for sub in fs::read_dir(&materials)? {
-                        let sub = sub?;
-                        if sub.path().is_dir() {
-                            let name = sub.file_name();
-                            if dont_link.iter().any(|x| x.eq_ignore_ascii_case(&name.to_string_lossy())) { continue; }
-                            let _ = link_dir_best_effort(&sub.path(), &dst_mat.join(name));
-                        }
-                    }
-                }
+                let mount_dst = addons.join(format!("mount-{}-{}", game_folder, entry.file_name().to_string_lossy()));
+                plan_content_links(&entry.path(), &mount_dst, &mut links)?;
             }
         }
     }
 
     // Remix mod link
-    let remix_mod_path = install_path.join("rtx-remix").join("mods").join(remix_mod_folder);
-    let remix_mod_mount_path = gmod_path.join("rtx-remix").join("mods").join(format!("mount-{}-{}", game_folder, remix_mod_folder));
-    fs::create_dir_all(remix_mod_mount_path.parent().unwrap()).ok();
-    if remix_mod_path.exists() {
-        let _ = link_dir_best_effort(&remix_mod_path, &remix_mod_mount_path);
+    if !remix_mod_folder.is_empty() {
+        let remix_mod_path = install_path.join("rtx-remix").join("mods").join(remix_mod_folder);
+        let remix_mod_mount_path = gmod_path.join("rtx-remix").join("mods").join(format!("mount-{}-{}", game_folder, remix_mod_folder));
+        fs::create_dir_all(remix_mod_mount_path.parent().unwrap()).ok();
+        if remix_mod_path.exists() { links.push((remix_mod_path, remix_mod_mount_path)); }
     }
 
-    progress("Mount complete");
+    let total = links.len();
+    let mut throttle = ProgressThrottle::new(100);
+    let mut copied_bytes = 0u64;
+    for (i, (src, dst)) in links.iter().enumerate() {
+        match link_dir_best_effort(src, dst, settings.link_strategy, false) {
+            Ok(LinkMethod::Copied { bytes }) => copied_bytes += bytes,
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to link {}: {}", dst.display(), e),
+        }
+        let pct = ((i + 1) * 99 / total) as u8;
+        throttle.emit("Linking:", format!("Linking: {}/{} folders", i + 1, total), pct, |m,p| progress(m,p));
+    }
+
+    if copied_bytes > 0 {
+        progress(&format!("Copied {:.1} GB because folders could not be linked", copied_bytes as f64 / 1e9), 99);
+    }
+    progress("Mount complete", 100);
     Ok(())
 }
 
-pub fn unmount_game(game_folder: &str, _install_folder: &str, remix_mod_folder: &str, mut progress_cb: impl FnMut(&str)) -> Result<()> {
-    let mut progress = |m: &str| { info!("{}", m); progress_cb(m); };
-    progress("Unmounting...");
+// Queue links for the models, maps and material subfolders of one content root
+fn plan_content_links(src_root: &Path, mount_root: &Path, links: &mut Vec<(PathBuf, PathBuf)>) -> Result<()> {
+    fs::create_dir_all(mount_root)?;
+    for sub in ["models", "maps"] {
+        let p = src_root.join(sub);
+        if p.exists() { links.push((p, mount_root.join(sub))); }
+    }
+    // Link materials subfolders except excluded
+    let materials = src_root.join("materials");
+    if materials.exists() {
+        let dst_mat = mount_root.join("materials");
+        fs::create_dir_all(&dst_mat).ok();
+        let dont_link = ["vgui", "dev", "editor", "perftest", "tools"];
+        for entry in fs::read_dir(&materials)? {
+            let entry = entry?;
+            if entry.path().is_dir() {
+                let name = entry.file_name();
+                if dont_link.iter().any(|x| x.eq_ignore_ascii_case(&name.to_string_lossy())) { continue; }
+                links.push((entry.path(), dst_mat.join(name)));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn unmount_game(game_folder: &str, remix_mod_folder: &str, mut progress_cb: impl FnMut(&str, u8)) -> Result<()> {
+    let mut progress = |m: &str, p: u8| { info!("{}", m); progress_cb(m, p); };
+    progress("Unmounting...", 0);
     let gmod_path = get_this_install_folder()?;
     let src_mount = gmod_path.join("garrysmod").join("addons").join(format!("mount-{}", game_folder));
     let remix_mount = gmod_path.join("rtx-remix").join("mods").join(format!("mount-{}-{}", game_folder, remix_mod_folder));
@@ -113,21 +145,55 @@ pub fn unmount_game(game_folder: &str, _install_folder: &str, remix_mod_folder:
             }
         }
     }
-    progress("Unmount complete");
+    progress("Unmount complete", 100);
     Ok(())
 }
 
-fn find_install_folder(install_folder: &str) -> Result<PathBuf> {
-    // Try steam default locations quickly; reuse the minimal heuristic from steam.rs
-    // For simplicity, check common library roots only.
-    let mut roots = Vec::new();
-    if let Ok(pf86) = std::env::var("ProgramFiles(x86)") { roots.push(PathBuf::from(pf86).join("Steam")); }
-    roots.push(PathBuf::from("C:/Program Files (x86)/Steam"));
-    for root in roots {
-        let p = root.join("steamapps").join("common").join(install_folder);
-        if p.exists() { return Ok(p); }
+// Names of mount-* entries in `dir`, with the prefix stripped
+fn mount_entries(dir: &Path) -> Vec<String> {
+    let Ok(read) = fs::read_dir(dir) else { return Vec::new() };
+    read.flatten()
+        .filter_map(|e| e.file_name().to_string_lossy().strip_prefix("mount-").map(|s| s.to_string()))
+        .collect()
+}
+
+/// Game folders that currently have content mounted. Custom-content mounts
+/// (`mount-<game>-<sub>`) and Remix mod mounts are folded into their game.
+pub fn list_mounts() -> Vec<String> {
+    let Ok(gmod_path) = get_this_install_folder() else { return Vec::new() };
+    let addons = mount_entries(&gmod_path.join("garrysmod").join("addons"));
+    let mods = mount_entries(&gmod_path.join("rtx-remix").join("mods"));
+    let is_child_of = |name: &str, parents: &[String]| parents.iter().any(|p| p != name && name.starts_with(&format!("{}-", p)));
+    let mut games: Vec<String> = addons.iter().filter(|a| !is_child_of(a, &addons)).cloned().collect();
+    for m in mods {
+        if !is_child_of(&m, &games) { games.push(m); }
     }
-    Err(anyhow::anyhow!("install folder not found"))
+    games.sort();
+    games.dedup();
+    games
 }
 
+/// Remove every mount created by this launcher. Returns how many entries were removed.
+pub fn unmount_all(mut progress_cb: impl FnMut(&str, u8)) -> Result<usize> {
+    let mut progress = |m: &str, p: u8| { info!("{}", m); progress_cb(m, p); };
+    progress("Unmounting everything...", 0);
+    let gmod_path = get_this_install_folder()?;
+    let mut removed = 0usize;
+    for dir in [gmod_path.join("garrysmod").join("addons"), gmod_path.join("rtx-remix").join("mods")] {
+        for name in mount_entries(&dir) {
+            let path = dir.join(format!("mount-{}", name));
+            match fs::remove_dir_all(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!("Failed to remove {}: {}", path.display(), e),
+            }
+        }
+    }
+    progress(&format!("Removed {} mount(s)", removed), 100);
+    Ok(removed)
+}
 
+fn resolve_install_path(install_folder: &str, install_path: Option<&Path>) -> Result<PathBuf> {
+    if let Some(p) = install_path.filter(|p| p.exists()) { return Ok(p.to_path_buf()); }
+    // Steam libraries, including those listed in libraryfolders.vdf
+    detect_install_folder_path(install_folder).ok_or_else(|| anyhow::anyhow!("install folder not found"))
+}
@@ -1,11 +1,74 @@
 use anyhow::{Result, Context};
 use reqwest::Client;
-use std::{collections::{HashMap}, path::Path};
+use std::{collections::{HashMap, HashSet}, path::{Path, PathBuf}};
+use crate::jobs::CancellationToken;
+use crate::error::{LauncherError, LauncherResult};
 
 #[derive(Debug, Clone, Default)]
 pub struct PatchResult {
     pub files_patched: usize,
     pub warnings: Vec<String>,
+    /// Every file that was processed, including missing ones
+    pub per_file: Vec<PatchedFile>,
+    /// Patch sets skipped because the target bytes already held the replacement
+    pub already_patched: usize,
+    /// Snapshot of the live files taken before deploying, when backups are enabled
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// Outcome for one patch target.
+#[derive(Debug, Clone, Default)]
+pub struct PatchedFile {
+    pub path: String,
+    /// Patch sets applied or already present
+    pub applied: usize,
+    pub total: usize,
+    /// `short_hash` of the source DLL, `None` when it was missing
+    pub before_hash: Option<String>,
+    /// `short_hash` of the patched output (the would-be output for a dry run)
+    pub after_hash: Option<String>,
+}
+
+impl PatchedFile {
+    pub fn is_complete(&self) -> bool { self.applied == self.total }
+}
+
+/// First 8 bytes of the SHA-256 as hex: short enough to paste, long enough to tell builds apart.
+pub fn short_hash(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(&Sha256::digest(data)[..8])
+}
+
+/// Knobs for `apply_patches_from_repo`.
+#[derive(Debug, Clone)]
+pub struct PatchOptions {
+    /// Copy the live target files to `<rtx_root>/patch-backups/<unix-time>/` before overwriting them
+    pub backup: bool,
+    /// Parse and match against the source DLLs but write nothing; would-be writes are reported as "DRY:" warnings
+    pub dry_run: bool,
+    /// Only process these install-relative targets (after the win64 rewrite); `None` processes every file in the script
+    pub only_files: Option<HashSet<String>>,
+    /// Read the unpatched DLLs from this vanilla install instead of the detected Steam one
+    pub vanilla_source: Option<PathBuf>,
+    /// Fetch the patch script from this branch; `None` tries `main` and then `master`
+    pub branch: Option<String>,
+    /// Read the patch script from this local file instead of fetching it
+    pub local_script: Option<PathBuf>,
+}
+
+impl Default for PatchOptions {
+    fn default() -> Self { Self { backup: true, dry_run: false, only_files: None, vanilla_source: None, branch: None, local_script: None } }
+}
+
+impl PatchOptions {
+    /// Defaults plus the user's backup choice and vanilla folder, the same one the installer copies from.
+    pub fn from_settings(settings: &crate::settings::AppSettings) -> Self {
+        Self {
+            backup: settings.patch_backup,
+            vanilla_source: settings.manually_specified_install_path.as_ref().map(PathBuf::from),
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -23,193 +86,233 @@ struct PatchSet {
 
 type PatchMap = HashMap<String, Vec<PatchSet>>;
 
-fn strip_comments(src: &str) -> String {
-    // Remove Python comments starting with '#', keep line breaks
-    src.lines().map(|l| {
-        if let Some(i) = l.find('#') { &l[..i] } else { l }
-    }).collect::<Vec<_>>().join("\n")
+// Tokens of the small Python subset used by applypatch.py's patch tables
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Int(i64),
+    // A numeric literal this subset does not evaluate (floats, exponents, binary, suffixes);
+    // only an error when a patch table uses it
+    Number(String),
+    Ident(String),
+    Punct(char),
 }
 
-fn parse_patches_from_python(src: &str) -> Result<(PatchMap, PatchMap)> {
-    // Very small, tailored parser that extracts two dict literals: patches32 = {...} and patches64 = {...}
-    // We convert them into our PatchMap structures. We assume the script structure used by SourceRTXTweaks.
-    let text = strip_comments(src);
-    let find_dict = |name: &str| -> Result<&str> {
-        let start_tag = format!("{} = {{", name);
-        let start_pos = if let Some(pos) = text.find(&start_tag) { pos + start_tag.len() - 1 } else {
-            // allow variant without spaces: name={
-            let alt = format!("{}={{", name);
-            text.find(&alt).ok_or_else(|| anyhow::anyhow!("{} not found", name))? + alt.len()-1
-        };
-        // naive brace matching
-        let bytes = text.as_bytes();
-        let mut depth = 0i32;
-        let mut end_idx = None;
-        for (i, &b) in bytes[start_pos..].iter().enumerate() {
-            let c = b as char;
-            if c == '{' { depth += 1; }
-            if c == '}' { depth -= 1; if depth == 0 { end_idx = Some(start_pos + i + 1); break; } }
-        }
-        let end = end_idx.ok_or_else(|| anyhow::anyhow!("{} unmatched braces", name))?;
-        Ok(&text[start_pos..end])
-    };
-
-    fn parse_dict(body: &str) -> Result<PatchMap> {
-        // We will scan keys '...': [ ... ] entries.
-        let mut map: PatchMap = HashMap::new();
-        // Strip outer braces if present
-        let trimmed = body.trim();
-        let slice: &str = if trimmed.starts_with('{') && trimmed.ends_with('}') {
-            &trimmed[1..trimmed.len()-1]
-        } else { trimmed };
-        // Split top-level entries by '],', account for nested brackets by depth counters.
-        let mut i = 0usize;
-        let chars: Vec<char> = slice.chars().collect();
-        while i < chars.len() {
-            // skip whitespace and commas
-            while i < chars.len() && chars[i].is_whitespace() { i += 1; }
-            if i >= chars.len() { break; }
-            if chars[i] == '}' { break; }
-            // expect key: '...'
-            if chars[i] != '\'' { return Err(anyhow::anyhow!("expected quoted key")); }
-            i += 1; let start_key = i; while i < chars.len() && chars[i] != '\'' { i += 1; }
-            let key = chars[start_key..i].iter().collect::<String>();
-            i += 1; // closing quote
-            // skip spaces and ':'
-            while i < chars.len() && (chars[i].is_whitespace() || chars[i] == ':' ) { if chars[i] == ':' { i += 1; break; } i += 1; }
-            while i < chars.len() && chars[i].is_whitespace() { i += 1; }
-            if i >= chars.len() || chars[i] != '[' { return Err(anyhow::anyhow!("expected [")); }
-            // capture value list with bracket matching
-            let mut depth = 0i32; let start_val = i; while i < chars.len() { let c = chars[i]; if c == '[' { depth += 1; } if c == ']' { depth -= 1; if depth == 0 { i += 1; break; } } i += 1; }
-            let val = chars[start_val..i].iter().collect::<String>();
-            // parse list of PatchSet
-            let sets = parse_patch_sets(&val)?;
-            map.insert(key, sets);
-            // move past comma
-            while i < chars.len() && chars[i] != '\'' { if chars[i] == ',' { i += 1; break; } i += 1; }
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0usize;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() { i += 1; continue; }
+        if c == '#' {
+            while i < chars.len() && chars[i] != '\n' { i += 1; }
+            continue;
         }
-        Ok(map)
-    }
-
-    fn parse_patch_sets(list_src: &str) -> Result<Vec<PatchSet>> {
-        // list_src is like [ entry, entry, ... ] where each entry itself is a [ ... ] list
-        let inner = &list_src[1..list_src.len()-1];
-        let mut out = Vec::new();
-        let mut i = 0usize; let chars: Vec<char> = inner.chars().collect();
-        while i < chars.len() {
-            while i < chars.len() && chars[i].is_whitespace() { i += 1; }
-            if i >= chars.len() { break; }
-            if chars[i] == '[' { // capture the whole entry list
-                let mut depth = 0i32; let start = i; while i < chars.len() { let c = chars[i]; if c == '[' { depth += 1; } if c == ']' { depth -= 1; if depth == 0 { i += 1; break; } } i += 1; }
-                let entry_src = chars[start..i].iter().collect::<String>();
-                out.push(parse_entry_list(&entry_src)?);
-            } else {
-                // skip unexpected token conservatively to next comma
-                while i < chars.len() && chars[i] != ',' { i += 1; }
+        if c == '\'' || c == '"' {
+            let triple = i + 2 < chars.len() && chars[i + 1] == c && chars[i + 2] == c;
+            let quote_len = if triple { 3 } else { 1 };
+            i += quote_len;
+            let mut s = String::new();
+            loop {
+                if i >= chars.len() { return Err(anyhow::anyhow!("unterminated string literal")); }
+                let ch = chars[i];
+                if ch == '\\' && i + 1 < chars.len() {
+                    let esc = chars[i + 1];
+                    s.push(match esc { 'n' => '\n', 't' => '\t', 'r' => '\r', other => other });
+                    i += 2;
+                    continue;
+                }
+                if ch == c && (!triple || (i + 2 < chars.len() && chars[i + 1] == c && chars[i + 2] == c)) {
+                    i += quote_len;
+                    break;
+                }
+                if ch == '\n' && !triple { return Err(anyhow::anyhow!("newline in string literal")); }
+                s.push(ch);
+                i += 1;
             }
-            // advance past comma if present
-            if i < chars.len() && chars[i] == ',' { i += 1; }
+            out.push(Token::Str(s));
+            continue;
         }
-        Ok(out)
-    }
-
-    fn parse_entry_list(entry_src: &str) -> Result<PatchSet> {
-        // entry_src like: [ ('hex', off), 'repl' ] OR [ [ ('hex',off), (...) ], 'repl'? ]
-        let inner = &entry_src[1..entry_src.len()-1];
-        let parts = split_top_level(inner, ',');
-        if parts.is_empty() { return Err(anyhow::anyhow!("empty entry")); }
-        let first = parts[0].trim();
-        let mut default_repl = None;
-        if parts.len() >= 2 {
-            let p1 = parts[1].trim();
-            if p1.starts_with('\'') { default_repl = Some(unquote(p1)?); }
+        let negative = c == '-' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit();
+        if c.is_ascii_digit() || negative {
+            let start = i;
+            if negative { i += 1; }
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') { i += 1; }
+            let lit: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            let (sign, digits) = match lit.strip_prefix('-') { Some(rest) => (-1, rest.to_string()), None => (1, lit.clone()) };
+            let value = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+                Some(hex) => i64::from_str_radix(hex, 16),
+                None => digits.parse::<i64>(),
+            };
+            out.push(match value { Ok(v) => Token::Int(sign * v), Err(_) => Token::Number(lit) });
+            continue;
         }
-        if first.starts_with('[') {
-            let patterns = parse_patterns_list(first)?;
-            Ok(PatchSet { patterns, default_replacement: default_repl })
-        } else if first.starts_with('(') {
-            let pat = parse_tuple_pattern(first)?;
-            Ok(PatchSet { patterns: vec![pat], default_replacement: default_repl })
-        } else {
-            Err(anyhow::anyhow!("entry must start with [ or ("))
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') { i += 1; }
+            out.push(Token::Ident(chars[start..i].iter().collect()));
+            continue;
         }
+        // Operators and punctuation; anything outside the patch tables is skipped by the parser
+        out.push(Token::Punct(c));
+        i += 1;
+    }
+    Ok(out)
+}
+
+// Literal values; lists and tuples are both `Seq`
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Int(i64),
+    Ident(String),
+    Seq(Vec<Value>),
+    Dict(Vec<(Value, Value)>),
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Result<&Token> {
+        let tok = self.tokens.get(self.pos).ok_or_else(|| anyhow::anyhow!("unexpected end of input"))?;
+        self.pos += 1;
+        Ok(tok)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.tokens.get(self.pos) == Some(&Token::Punct(c)) { self.pos += 1; true } else { false }
     }
 
-    fn parse_patterns_list(src: &str) -> Result<Vec<PatternSpec>> {
-        // src: like [ ('hex', off[, 'override']), ... ]
-        let inner = &src[1..src.len()-1];
-        let mut i = 0usize; let chars: Vec<char> = inner.chars().collect(); let mut out = Vec::new();
-        while i < chars.len() {
-            while i < chars.len() && chars[i].is_whitespace() { i += 1; }
-            if i >= chars.len() { break; }
-            if chars[i] != '(' { return Err(anyhow::anyhow!("expected tuple")); }
-            let mut depth = 0i32; let start = i; while i < chars.len() { let c = chars[i]; if c == '(' { depth += 1; } if c == ')' { depth -= 1; if depth == 0 { i += 1; break; } } i += 1; }
-            let tup = chars[start..i].iter().collect::<String>();
-            out.push(parse_tuple_pattern(&tup)?);
-            while i < chars.len() && chars[i] != '(' { if chars[i] == ',' { i += 1; break; } i += 1; }
+    fn value(&mut self) -> Result<Value> {
+        match self.next()?.clone() {
+            Token::Str(mut s) => {
+                // Adjacent string literals concatenate, as in Python
+                while let Some(Token::Str(more)) = self.tokens.get(self.pos) { s.push_str(more); self.pos += 1; }
+                Ok(Value::Str(s))
+            }
+            Token::Int(n) => Ok(Value::Int(n)),
+            Token::Number(lit) => Err(anyhow::anyhow!("invalid number {}", lit)),
+            Token::Ident(id) => Ok(Value::Ident(id)),
+            Token::Punct('[') => Ok(Value::Seq(self.seq(']')?)),
+            Token::Punct('(') => Ok(Value::Seq(self.seq(')')?)),
+            Token::Punct('{') => {
+                let mut entries = Vec::new();
+                while !self.eat('}') {
+                    let key = self.value()?;
+                    if !self.eat(':') { return Err(anyhow::anyhow!("expected ':' after dict key")); }
+                    entries.push((key, self.value()?));
+                    if !self.eat(',') {
+                        if self.eat('}') { break; }
+                        return Err(anyhow::anyhow!("expected ',' or '}}' in dict"));
+                    }
+                }
+                Ok(Value::Dict(entries))
+            }
+            other => Err(anyhow::anyhow!("unexpected token {:?}", other)),
         }
-        Ok(out)
     }
 
-    fn parse_tuple_pattern(src: &str) -> Result<PatternSpec> {
-        // src: ('hex', off[, 'override'])
-        let inner = &src[1..src.len()-1];
-        let parts = split_top_level(inner, ',');
-        if parts.len() < 2 { return Err(anyhow::anyhow!("tuple too short")); }
-        let hex = unquote(parts[0].trim())?;
-        let offset: isize = parts[1].trim().parse().unwrap_or(0);
-        let override_hex = if parts.len() >= 3 { Some(unquote(parts[2].trim())?) } else { None };
-        Ok(PatternSpec { hex_mask: hex, offset, override_hex })
+    // Comma separated values up to `close`; a trailing comma is allowed
+    fn seq(&mut self, close: char) -> Result<Vec<Value>> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(self.value()?);
+            if !self.eat(',') {
+                if self.eat(close) { break; }
+                return Err(anyhow::anyhow!("expected ',' or '{}'", close));
+            }
+        }
+        Ok(items)
     }
+}
+
+// Parse the value of the first top-level `name = ...` assignment
+fn find_assignment(tokens: &[Token], name: &str) -> Option<Result<Value>> {
+    let pos = tokens.windows(2).position(|w| w[0] == Token::Ident(name.to_string()) && w[1] == Token::Punct('='))?;
+    Some(Parser { tokens, pos: pos + 2 }.value())
+}
 
-    fn parse_string(chars: &[char], mut i: usize) -> Result<(String, usize)> {
-        if chars[i] != '\'' { return Err(anyhow::anyhow!("expected string")); }
-        i += 1; let start = i; while i < chars.len() && chars[i] != '\'' { i += 1; }
-        let s = chars[start..i].iter().collect::<String>();
-        Ok((s, i+1))
+fn patch_map_from_value(value: &Value) -> Result<PatchMap> {
+    let Value::Dict(entries) = value else { return Err(anyhow::anyhow!("patch table is not a dict")); };
+    let mut map: PatchMap = HashMap::new();
+    for (key, sets) in entries {
+        let Value::Str(key) = key else { return Err(anyhow::anyhow!("patch table key is not a string")); };
+        let Value::Seq(sets) = sets else { return Err(anyhow::anyhow!("patches for {} are not a list", key)); };
+        let sets = sets.iter().map(patch_set_from_value).collect::<Result<Vec<_>>>().with_context(|| format!("patches for {}", key))?;
+        map.insert(key.clone(), sets);
     }
+    Ok(map)
+}
 
-    fn split_top_level(s: &str, delim: char) -> Vec<String> {
-        let mut res = Vec::new(); let mut depth = 0i32; let mut cur = String::new();
-        for c in s.chars() {
-            match c { '[' | '(' | '{' => { depth += 1; cur.push(c); }, ']' | ')' | '}' => { depth -= 1; cur.push(c); }, d if d == delim && depth == 0 => { res.push(cur.trim().to_string()); cur.clear(); }, _ => cur.push(c) }
+// [ ('hex', off[, 'override']), 'repl' ] or [ [ (...), (...) ], 'repl' ]
+fn patch_set_from_value(value: &Value) -> Result<PatchSet> {
+    let Value::Seq(items) = value else { return Err(anyhow::anyhow!("patch set is not a list")); };
+    let first = items.first().ok_or_else(|| anyhow::anyhow!("empty patch set"))?;
+    let default_replacement = match items.get(1) { Some(Value::Str(s)) => Some(s.clone()), _ => None };
+    let patterns = match first {
+        Value::Seq(inner) if matches!(inner.first(), Some(Value::Seq(_))) => inner.iter().map(pattern_from_value).collect::<Result<Vec<_>>>()?,
+        Value::Seq(_) => vec![pattern_from_value(first)?],
+        _ => return Err(anyhow::anyhow!("patch set must start with a pattern tuple or list")),
+    };
+    Ok(PatchSet { patterns, default_replacement })
+}
+
+fn pattern_from_value(value: &Value) -> Result<PatternSpec> {
+    let Value::Seq(parts) = value else { return Err(anyhow::anyhow!("pattern is not a tuple")); };
+    match parts.as_slice() {
+        [Value::Str(hex), Value::Int(off), rest @ ..] => {
+            let override_hex = match rest.first() { Some(Value::Str(s)) => Some(s.clone()), _ => None };
+            Ok(PatternSpec { hex_mask: hex.clone(), offset: *off as isize, override_hex })
         }
-        if !cur.trim().is_empty() { res.push(cur.trim().to_string()); }
-        res
+        _ => Err(anyhow::anyhow!("pattern must be ('hex', offset[, 'override'])")),
     }
+}
 
-    fn unquote(s: &str) -> Result<String> { Ok(s.trim_matches('\'').to_string()) }
+fn parse_patches_from_python(src: &str) -> Result<(PatchMap, PatchMap)> {
+    // Extracts the two dict literals patches32 = {...} and patches64 = {...} from the SourceRTXTweaks script
+    let tokens = tokenize(src)?;
+    let table = |names: [&str; 2]| -> Result<PatchMap> {
+        match find_assignment(&tokens, names[0]).or_else(|| find_assignment(&tokens, names[1])) {
+            Some(value) => patch_map_from_value(&value.with_context(|| format!("parse {}", names[0]))?),
+            None => Ok(HashMap::new()),
+        }
+    };
+    Ok((table(["patches32", "patches_32"])?, table(["patches64", "patches_64"])?))
+}
 
-    let d32 = find_dict("patches32").or_else(|_| find_dict("patches_32")).unwrap_or("{}");
-    let d64 = find_dict("patches64").or_else(|_| find_dict("patches_64")).unwrap_or("{}");
-    Ok((parse_dict(d32)?, parse_dict(d64)?))
+/// Parses a hex mask into bytes, with `None` for each `??` wildcard byte.
+fn parse_mask(hex_mask: &str) -> Option<Vec<Option<u8>>> {
+    let chars = hex_mask.as_bytes();
+    if !chars.len().is_multiple_of(2) { return None; }
+    chars.chunks(2).map(|pair| {
+        if pair == b"??" { return Some(None); }
+        let s = std::str::from_utf8(pair).ok()?;
+        u8::from_str_radix(s, 16).ok().map(Some)
+    }).collect()
 }
 
-fn findmask(data: &[u8], hex_mask: &str, mut start: usize) -> Option<usize> {
+fn findmask(data: &[u8], hex_mask: &str, start: usize) -> Option<usize> {
     // Python-compatible masked search with '??' as single-byte wildcard.
+    if start > data.len() { return None; }
     if !hex_mask.contains("??") {
         let needle = hex::decode(hex_mask).ok()?;
         return twoway::find_bytes(&data[start..], &needle).map(|p| start + p);
     }
-    let parts: Vec<&str> = hex_mask.split("??").collect();
-    loop {
-        let anchor = hex::decode(parts[0]).ok()?;
-        let findpos = twoway::find_bytes(&data[start..], &anchor).map(|p| start + p)?;
-        let mut good = true;
-        let mut checkpos = findpos;
-        for part in &parts {
-            if !part.is_empty() {
-                let b = hex::decode(part).ok()?;
-                if checkpos + b.len() > data.len() || &data[checkpos..checkpos + b.len()] != b.as_slice() { good = false; break; }
-            }
-            checkpos += (part.len() / 2) + 1; // advance past this literal and one wildcard byte
-        }
-        if good { return Some(findpos); }
-        start = findpos + 1;
-    }
+    let mask = parse_mask(hex_mask)?;
+    if mask.is_empty() || mask.len() > data.len() { return None; }
+    (start..=data.len() - mask.len()).find(|&pos| {
+        mask.iter().zip(&data[pos..pos + mask.len()]).all(|(m, b)| m.is_none_or(|m| m == *b))
+    })
 }
 
-fn apply_patchsets_to_file(orig: &[u8], out: &mut [u8], sets: &[PatchSet], warnings: &mut Vec<String>) {
+/// Returns how many of `sets` were written to `out` and how many were already present.
+fn apply_patchsets_to_file(orig: &[u8], out: &mut [u8], sets: &[PatchSet], dry_run: bool, warnings: &mut Vec<String>) -> (usize, usize) {
+    let mut applied = 0usize;
+    let mut already = 0usize;
     for set in sets {
         // Choose first matching pattern with exactly one match
         let mut chosen: Option<(usize, &PatternSpec)> = None;
@@ -222,13 +325,26 @@ fn apply_patchsets_to_file(orig: &[u8], out: &mut [u8], sets: &[PatchSet], warni
             let repl_hex = pat.override_hex.as_ref().or(set.default_replacement.as_ref());
             if let Some(hexs) = repl_hex {
                 if let Ok(repl) = hex::decode(hexs) {
-                    let off = if pat.offset >= 0 { (base as isize + pat.offset) as usize } else { base.saturating_sub(pat.offset.unsigned_abs()) };
-                    if off + repl.len() <= out.len() {
+                    // Signed target so offsets pointing before the start of the file are rejected, not clamped to 0
+                    let start = base as i64 + pat.offset as i64;
+                    let in_range = start >= 0 && start as usize + repl.len() <= out.len();
+                    if !in_range {
+                        warnings.push(format!("Write out of range for pattern {} (match at 0x{:X}, offset {}, len {})", pat.hex_mask, base, pat.offset, repl.len()));
+                        continue;
+                    }
+                    let off = start as usize;
+                    if orig[off..off+repl.len()] == repl[..] {
+                        warnings.push(format!("Already patched at 0x{:X}, skipping", off));
+                        already += 1;
+                    } else {
                         out[off..off+repl.len()].copy_from_slice(&repl);
                         // Log applied patch summary as a warning entry (UI prints these now)
-                        warnings.push(format!("Applied patch at 0x{:X}, len {}", off, repl.len()));
-                    } else {
-                        warnings.push(format!("Write out of range for pattern {}", pat.hex_mask));
+                        if dry_run {
+                            warnings.push(format!("DRY: would patch 0x{:X}, len {}: {}", off, repl.len(), hexs));
+                        } else {
+                            warnings.push(format!("Applied patch at 0x{:X}, len {}", off, repl.len()));
+                        }
+                        applied += 1;
                     }
                 }
             }
@@ -246,6 +362,17 @@ fn apply_patchsets_to_file(orig: &[u8], out: &mut [u8], sets: &[PatchSet], warni
             }
         }
     }
+    (applied, already)
+}
+
+/// Install-relative file a patch key targets. On a 64-bit install a plain `bin/<name>.dll` key usually
+/// means `bin/win64/<name>.dll`, but some 32-bit DLLs remain in `bin/`, so the win64 path is only
+/// chosen when it exists under `source_root` or the key's own path does not.
+fn resolve_target(rel: &str, is64: bool, source_root: &Path) -> String {
+    let Some(tail) = rel.strip_prefix("bin/") else { return rel.to_string(); };
+    if !is64 || tail.contains('/') || !rel.ends_with(".dll") { return rel.to_string(); }
+    let win64 = format!("bin/win64/{}", tail);
+    if source_root.join(&win64).exists() || !source_root.join(rel).exists() { win64 } else { rel.to_string() }
 }
 
 fn write_patched_file(dest_root: &Path, rel_path: &str, content: &[u8]) -> Result<()> {
@@ -254,42 +381,140 @@ fn write_patched_file(dest_root: &Path, rel_path: &str, content: &[u8]) -> Resul
     std::fs::write(out, content).context("write patched file")
 }
 
-pub async fn apply_patches_from_repo(owner: &str, repo: &str, file_path: &str, rtx_root: &Path, mut progress: impl FnMut(&str, u8)) -> Result<PatchResult> {
+pub async fn apply_patches_from_repo(owner: &str, repo: &str, file_path: &str, rtx_root: &Path, options: &PatchOptions, cancel: &CancellationToken, progress: impl FnMut(&str, u8)) -> LauncherResult<PatchResult> {
+    apply_patches_inner(owner, repo, file_path, rtx_root, options, cancel, progress).await.map_err(Into::into)
+}
+
+/// Where the last non-dry patch run wrote its report.
+pub fn patch_report_path(rtx_root: &Path) -> PathBuf { rtx_root.join("patched").join("patch-report.txt") }
+
+/// Files the report at `report_path` lists as missing or only partly patched, as install-relative paths.
+pub fn failed_patch_files(report_path: &Path) -> LauncherResult<Vec<String>> {
+    let text = std::fs::read_to_string(report_path).with_context(|| format!("read {}", report_path.display()))?;
+    Ok(parse_failed_files(&text))
+}
+
+fn parse_failed_files(report: &str) -> Vec<String> {
+    let mut failed: Vec<String> = Vec::new();
+    let mut add = |f: &str| if !failed.iter().any(|x| x == f) { failed.push(f.to_string()); };
+    for line in report.lines() {
+        // Warnings name their file in a trailing "[rel]"
+        if line.starts_with("Missing file [") || line.starts_with("Failed to locate pattern [") {
+            if let Some(f) = line.rsplit_once('[').and_then(|(_, r)| r.strip_suffix(']')) { add(f); }
+            continue;
+        }
+        let Some((file, counts)) = line.rsplit_once(": ") else { continue; };
+        let Some((counts, _hashes)) = counts.split_once(" patch set(s) applied") else { continue; };
+        let Some((applied, total)) = counts.split_once('/') else { continue; };
+        if let (Ok(a), Ok(t)) = (applied.parse::<usize>(), total.parse::<usize>()) {
+            if a < t { add(file); }
+        }
+    }
+    failed
+}
+
+/// Rerun the patch script for only the files the last report (`patch_report_path`) lists as failed,
+/// leaving files that already patched cleanly alone. The retried files' entries replace theirs in the report.
+pub async fn reapply_failed(owner: &str, repo: &str, file_path: &str, rtx_root: &Path, options: &PatchOptions, cancel: &CancellationToken, mut progress: impl FnMut(&str, u8)) -> LauncherResult<PatchResult> {
+    let failed: HashSet<String> = failed_patch_files(&patch_report_path(rtx_root))?.into_iter().collect();
+    if failed.is_empty() {
+        progress("No failed files in the last patch report", 100);
+        return Ok(PatchResult::default());
+    }
+    progress(&format!("Retrying {} failed file(s)", failed.len()), 0);
+    let options = PatchOptions { only_files: Some(failed), ..options.clone() };
+    apply_patches_inner(owner, repo, file_path, rtx_root, &options, cancel, progress).await.map_err(Into::into)
+}
+
+// Last successfully fetched copy of a script, used when GitHub cannot be reached
+fn script_cache_path(owner: &str, repo: &str, file_path: &str, branch: Option<&str>) -> Result<PathBuf> {
+    let name = format!("{}_{}_{}_{}", owner, repo, branch.unwrap_or("default"), file_path).replace(['/', '\\'], "_");
+    let dir = crate::github::cache_dir()?.join("patch-scripts");
+    std::fs::create_dir_all(&dir).ok();
+    Ok(dir.join(name))
+}
+
+async fn fetch_patch_script(owner: &str, repo: &str, file_path: &str, options: &PatchOptions, progress: &mut impl FnMut(&str, u8)) -> Result<String> {
     progress("Fetching patch script", 5);
-    // Try default branch path first, then a simple fallback if the repo uses master
-    let url = format!("https://raw.githubusercontent.com/{}/{}/refs/heads/main/{}", owner, repo, file_path);
+    let cache = script_cache_path(owner, repo, file_path, options.branch.as_deref());
+    let fetched = fetch_remote_script(owner, repo, file_path, options.branch.as_deref(), progress).await;
+    match fetched {
+        Ok(text) => {
+            if let Ok(path) = &cache { std::fs::write(path, &text).ok(); }
+            Ok(text)
+        }
+        Err(e) => {
+            let err = LauncherError::from(e);
+            if crate::github::is_unreachable(&err) {
+                if let Some(text) = cache.ok().and_then(|p| std::fs::read_to_string(p).ok()) {
+                    progress(&format!("offline: using cached patch script ({})", err), 5);
+                    return Ok(text);
+                }
+            }
+            Err(err.into())
+        }
+    }
+}
+
+async fn fetch_remote_script(owner: &str, repo: &str, file_path: &str, branch: Option<&str>, progress: &mut impl FnMut(&str, u8)) -> Result<String> {
+    let raw_base = crate::github::github_raw_base();
     let client = Client::new();
-    let resp = client.get(&url).header("User-Agent", "RTXLauncher-RS").send().await?;
-    let text = if resp.status().is_success() {
-        resp.text().await?
+    let text = if let Some(branch) = branch {
+        let url = format!("{}/{}/{}/{}/{}", raw_base, owner, repo, branch, file_path);
+        progress(&format!("Using branch {}", branch), 5);
+        client.get(&url).header("User-Agent", "RTXLauncher-RS").send().await?.error_for_status()
+            .with_context(|| format!("fetch {} from branch {}", file_path, branch))?.text().await?
     } else {
-        let alt = format!("https://raw.githubusercontent.com/{}/{}/master/{}", owner, repo, file_path);
-        client.get(&alt).header("User-Agent", "RTXLauncher-RS").send().await?.error_for_status()?.text().await?
+        // Try default branch path first, then a simple fallback if the repo uses master
+        let url = format!("{}/{}/{}/refs/heads/main/{}", raw_base, owner, repo, file_path);
+        let resp = client.get(&url).header("User-Agent", "RTXLauncher-RS").send().await?;
+        if resp.status().is_success() {
+            resp.text().await?
+        } else {
+            let alt = format!("{}/{}/{}/master/{}", raw_base, owner, repo, file_path);
+            client.get(&alt).header("User-Agent", "RTXLauncher-RS").send().await?.error_for_status()?.text().await?
+        }
+    };
+    Ok(text)
+}
+
+async fn apply_patches_inner(owner: &str, repo: &str, file_path: &str, rtx_root: &Path, options: &PatchOptions, cancel: &CancellationToken, mut progress: impl FnMut(&str, u8)) -> Result<PatchResult> {
+    let text = match &options.local_script {
+        Some(path) => {
+            progress(&format!("Reading patch script {}", path.display()), 5);
+            std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?
+        }
+        None => fetch_patch_script(owner, repo, file_path, options, &mut progress).await?,
     };
 
+    cancel.check()?;
     progress("Parsing patch definitions", 10);
-    let (map32, map64) = parse_patches_from_python(&text)?;
+    let (map32, map64) = parse_patches_from_python(&text).map_err(|e| LauncherError::Parse(format!("{}: {:#}", file_path, e)))?;
 
     // Determine 32/64 via existing detection: prefer explicit win64 presence
     let is64 = rtx_root.join("bin").join("win64").exists();
     let map = if is64 { &map64 } else { &map32 };
 
-    let mut warnings: Vec<String> = Vec::new();
-    let mut files_patched = 0usize;
+    // Source DLLs: the caller's vanilla folder, else the detected Steam install, else the RTX install itself
+    let vanilla_root = options.vanilla_source.clone()
+        .or_else(crate::steam::detect_gmod_install_folder)
+        .unwrap_or_else(|| rtx_root.to_path_buf());
+    progress(&format!("Reading source DLLs from {}", vanilla_root.display()), 11);
+
+    let mut result = PatchResult::default();
     let mut patched_files: Vec<String> = Vec::new();
     let keys: Vec<String> = map.keys().cloned().collect();
     let total = keys.len().max(1);
     for (i, rel) in keys.iter().enumerate() {
+        if cancel.is_cancelled() {
+            // Outputs so far only exist under <rtx_root>/patched; nothing live has been replaced yet
+            progress(&format!("Cancelled after {}/{} file(s); live binaries were not modified", i, keys.len()), 100);
+            return Err(crate::jobs::Cancelled.into());
+        }
         let pct = 12 + ((i as f32 / total as f32) * 80.0) as u8;
-        progress(&format!("Patching {}", rel), pct.min(90));
-        // Force 64-bit targets if this is a 64-bit install: rewrite known 32-bit DLL keys to win64 equivalents
-        let effective_rel = if is64 && rel.starts_with("bin/") && !rel.contains("/win64/") && rel.ends_with(".dll") {
-            // Upgrade to win64 path when appropriate (e.g., bin/engine.dll -> bin/win64/engine.dll)
-            let tail = rel.trim_start_matches("bin/");
-            format!("bin/win64/{}", tail)
-        } else { rel.clone() };
-        // Prefer vanilla game's DLLs (from Steam install) as source when available
-        let vanilla_root = crate::steam::detect_gmod_install_folder().unwrap_or_else(|| rtx_root.to_path_buf());
+        let effective_rel = resolve_target(rel, is64, &vanilla_root);
+        if options.only_files.as_ref().is_some_and(|o| !o.contains(&effective_rel)) { continue; }
+        if effective_rel == *rel { progress(&format!("Patching {}", rel), pct.min(90)); } else { progress(&format!("Patching {} -> {}", rel, effective_rel), pct.min(90)); }
         let path = vanilla_root.join(&effective_rel);
         if !path.exists() {
             // Try client.dll search behavior if needed
@@ -300,48 +525,292 @@ pub async fn apply_patches_from_repo(owner: &str, repo: &str, file_path: &str, r
                         let try_p = ent.path().join(&effective_rel);
                         if try_p.exists() { found = Some(try_p); break; }
                     }
-                    if let Some(p) = found { patch_file(&p, &effective_rel, &map[rel], rtx_root, &mut warnings, &mut files_patched)?; continue; }
+                    if let Some(p) = found { patch_file(&p, &effective_rel, &map[rel], rtx_root, options.dry_run, &mut result)?; continue; }
                 }
             }
-            warnings.push(format!("Missing file [{}]", effective_rel));
+            result.warnings.push(format!("Missing file [{}]", effective_rel));
+            result.per_file.push(PatchedFile { path: effective_rel, total: map[rel].len(), ..Default::default() });
             continue;
         }
-        patch_file(&path, &effective_rel, &map[rel], rtx_root, &mut warnings, &mut files_patched)?;
+        patch_file(&path, &effective_rel, &map[rel], rtx_root, options.dry_run, &mut result)?;
         patched_files.push(effective_rel);
     }
 
+    if options.dry_run {
+        progress(&format!("Dry run complete: {} file(s) would be patched; nothing was written", result.files_patched), 100);
+        return Ok(result);
+    }
+
     progress("Writing outputs", 95);
     // Deploy patched files to live bin/bin/win64
+    if options.backup && !patched_files.is_empty() {
+        progress("Backing up original files", 96);
+        result.backup_dir = Some(backup_live_files(rtx_root, &patched_files, &mut result.warnings)?);
+    }
     progress("Deploying patched files", 97);
     for rel in &patched_files {
         let src = rtx_root.join("patched").join(rel);
         let dst = rtx_root.join(rel);
         if let Some(parent) = dst.parent() { let _ = std::fs::create_dir_all(parent); }
-        if let Err(e) = std::fs::copy(&src, &dst) { warnings.push(format!("Failed to deploy {}: {}", rel, e)); }
+        if let Err(e) = std::fs::copy(&src, &dst) { result.warnings.push(format!("Failed to deploy {}: {}", rel, e)); }
     }
     
     progress("Writing report", 98);
     // Write a report next to outputs for debugging
     if let Some(report_dir) = std::path::Path::new(rtx_root).join("patched").to_str().map(|s| s.to_string()) {
-        let report_path = std::path::Path::new(&report_dir).join("patch-report.txt");
-        let mut text = String::new();
-        text.push_str(&format!("Patched {} file(s)\n", files_patched));
-        for f in &patched_files { text.push_str(&format!("Patched: {}\n", f)); }
-        for w in &warnings { text.push_str(&format!("{}\n", w)); }
+        let report_path = patch_report_path(rtx_root);
+        // A retry only reran some files; the rest of the last report still stands
+        let text = match options.only_files.as_ref().zip(std::fs::read_to_string(&report_path).ok()) {
+            Some((retried, previous)) => merge_report(&previous, retried, &result),
+            None => report_text(&result),
+        };
         let _ = std::fs::create_dir_all(std::path::Path::new(&report_dir));
         let _ = std::fs::write(&report_path, text);
     }
     progress("Done", 100);
-    Ok(PatchResult { files_patched, warnings })
+    Ok(result)
+}
+
+fn report_file_line(f: &PatchedFile) -> String {
+    let mut line = format!("{}: {}/{} patch set(s) applied", f.path, f.applied, f.total);
+    if let (Some(before), Some(after)) = (&f.before_hash, &f.after_hash) { line.push_str(&format!(", sha256 {} -> {}", before, after)); }
+    line
 }
 
-fn patch_file(path: &Path, rel: &str, sets: &[PatchSet], install_dir: &Path, warnings: &mut Vec<String>, files_patched: &mut usize) -> Result<()> {
+fn report_text(result: &PatchResult) -> String {
+    let mut text = format!("Patched {} file(s), {} patch set(s) already up to date\n", result.files_patched, result.already_patched);
+    if let Some(dir) = &result.backup_dir { text.push_str(&format!("Backup: {}\n", dir.display())); }
+    for f in &result.per_file { text.push_str(&report_file_line(f)); text.push('\n'); }
+    for w in &result.warnings { text.push_str(&format!("{}\n", w)); }
+    text
+}
+
+// The previous report with every line about a retried file replaced by the retry's results;
+// earlier summary and backup lines are kept above the retry's own
+fn merge_report(previous: &str, retried: &HashSet<String>, result: &PatchResult) -> String {
+    let about_retried = |line: &str| retried.iter().any(|p| line.starts_with(&format!("{}: ", p)) || line.contains(&format!("[{}]", p)) || line.starts_with(&format!("Failed to deploy {}:", p)));
+    let (mut head, mut files, mut warnings) = (Vec::new(), Vec::new(), Vec::new());
+    for line in previous.lines().filter(|l| !l.is_empty()) {
+        if line.starts_with("Patched ") || line.starts_with("Retried ") || line.starts_with("Backup: ") { head.push(line.to_string()); }
+        else if about_retried(line) { continue; }
+        else if line.contains(" patch set(s) applied") { files.push(line.to_string()); }
+        else { warnings.push(line.to_string()); }
+    }
+    head.push(format!("Retried {} file(s): patched {}, {} patch set(s) already up to date", retried.len(), result.files_patched, result.already_patched));
+    if let Some(dir) = &result.backup_dir { head.push(format!("Backup: {}", dir.display())); }
+    files.extend(result.per_file.iter().map(report_file_line));
+    warnings.extend(result.warnings.iter().cloned());
+    let mut text = [head, files, warnings].concat().join("\n");
+    text.push('\n');
+    text
+}
+
+fn backups_root(rtx_root: &Path) -> PathBuf { rtx_root.join("patch-backups") }
+
+// Copy the current live version of each target so a bad patch set can be rolled back
+fn backup_live_files(rtx_root: &Path, rels: &[String], warnings: &mut Vec<String>) -> Result<PathBuf> {
+    let stamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let dir = backups_root(rtx_root).join(stamp.to_string());
+    std::fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    for rel in rels {
+        let src = rtx_root.join(rel);
+        if !src.exists() { continue; }
+        let dst = dir.join(rel);
+        if let Some(parent) = dst.parent() { std::fs::create_dir_all(parent).ok(); }
+        if let Err(e) = std::fs::copy(&src, &dst) { warnings.push(format!("Failed to back up {}: {}", rel, e)); }
+    }
+    Ok(dir)
+}
+
+/// Copy the newest backup set from `<rtx_root>/patch-backups` back over the live files.
+/// Returns the restored backup directory and the number of files copied, or `None` when no backup exists.
+pub fn restore_patch_backup(rtx_root: &Path) -> LauncherResult<Option<(PathBuf, usize)>> {
+    let root = backups_root(rtx_root);
+    let Ok(entries) = std::fs::read_dir(&root) else { return Ok(None); };
+    let newest = entries.flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_string_lossy().parse::<u64>().ok().map(|t| (t, e.path())))
+        .max_by_key(|(t, _)| *t)
+        .map(|(_, p)| p);
+    let Some(dir) = newest else { return Ok(None); };
+    let mut restored = 0usize;
+    for entry in walkdir::WalkDir::new(&dir).into_iter().flatten() {
+        if !entry.file_type().is_file() { continue; }
+        let rel = entry.path().strip_prefix(&dir).context("backup entry outside backup dir")?;
+        let dst = rtx_root.join(rel);
+        if let Some(parent) = dst.parent() { std::fs::create_dir_all(parent).ok(); }
+        std::fs::copy(entry.path(), &dst).with_context(|| format!("restore {}", dst.display()))?;
+        restored += 1;
+    }
+    Ok(Some((dir, restored)))
+}
+
+fn patch_file(path: &Path, rel: &str, sets: &[PatchSet], install_dir: &Path, dry_run: bool, result: &mut PatchResult) -> Result<()> {
     let data = std::fs::read(path).with_context(|| format!("read {}", path.display()))?;
     let mut out = data.clone();
-    apply_patchsets_to_file(&data, &mut out, sets, warnings);
-    write_patched_file(install_dir, rel, &out)?;
-    *files_patched += 1;
+    let first_new = result.warnings.len();
+    let (applied, already) = apply_patchsets_to_file(&data, &mut out, sets, dry_run, &mut result.warnings);
+    // Name the file so a retry can find it from the report
+    for w in &mut result.warnings[first_new..] {
+        if w == "Failed to locate pattern" { w.push_str(&format!(" [{}]", rel)); }
+    }
+    result.per_file.push(PatchedFile {
+        path: rel.to_string(),
+        applied: applied + already,
+        total: sets.len(),
+        before_hash: Some(short_hash(&data)),
+        after_hash: Some(short_hash(&out)),
+    });
+    result.already_patched += already;
+    if !dry_run { write_patched_file(install_dir, rel, &out)?; }
+    result.files_patched += 1;
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::{apply_patchsets_to_file, findmask, merge_report, parse_failed_files, parse_patches_from_python, resolve_target, PatchResult, PatchSet, PatchedFile, PatternSpec};
+
+    const DATA: &[u8] = &[0x10, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xAA, 0xBB, 0x00, 0xDD];
+
+    #[test]
+    fn leading_wildcard() {
+        assert_eq!(findmask(DATA, "??BBCC", 0), Some(1));
+        assert_eq!(findmask(DATA, "??AABB", 2), Some(5));
+    }
+
+    #[test]
+    fn consecutive_wildcards() {
+        assert_eq!(findmask(DATA, "AA????DD", 0), Some(1));
+        assert_eq!(findmask(DATA, "BB????DD", 0), None);
+        assert_eq!(findmask(DATA, "AABB????DD", 0), None);
+    }
+
+    #[test]
+    fn trailing_wildcard() {
+        assert_eq!(findmask(DATA, "AABB??", 0), Some(1));
+        assert_eq!(findmask(DATA, "AABB??", 2), Some(6));
+        assert_eq!(findmask(DATA, "00DD??", 0), None);
+    }
+
+    #[test]
+    fn parses_applypatch_tables() {
+        let src = r#"
+import sys  # not part of the tables
+patches32 = {}
+patches64 = {
+    'bin/win64/client.dll': [
+        # single pattern with a default replacement
+        [('8B0D????????85C9', 6), '9090'],
+        # alternatives, one with its own replacement, trailing commas everywhere
+        [[('AABB??DD', -2), ("CCDD", 0x10, 'EB'),], 'C3',],
+    ],
+    "bin/win64/engine\'s.dll": [[('0102', 0)]],
+}
+def main():
+    print("patches64 = {")
+"#;
+        let (p32, p64) = parse_patches_from_python(src).unwrap();
+        assert!(p32.is_empty());
+        let client = &p64["bin/win64/client.dll"];
+        assert_eq!(client.len(), 2);
+        assert_eq!(client[0].patterns[0].hex_mask, "8B0D????????85C9");
+        assert_eq!(client[0].patterns[0].offset, 6);
+        assert_eq!(client[0].default_replacement.as_deref(), Some("9090"));
+        assert_eq!(client[1].patterns.len(), 2);
+        assert_eq!(client[1].patterns[0].offset, -2);
+        assert_eq!(client[1].patterns[1].offset, 16);
+        assert_eq!(client[1].patterns[1].override_hex.as_deref(), Some("EB"));
+        assert_eq!(client[1].default_replacement.as_deref(), Some("C3"));
+        let engine = &p64["bin/win64/engine's.dll"];
+        assert_eq!(engine[0].default_replacement, None);
+    }
+
+    #[test]
+    fn unknown_numbers_only_fail_inside_tables() {
+        let src = r#"
+TIMEOUT = 1e5
+CHUNK = 1_000
+FLAGS = 0b101 | 0x10L
+patches64 = { 'bin/win64/client.dll': [[('AABB', 1_000)]] }
+"#;
+        let (_, p64) = parse_patches_from_python(src).unwrap();
+        assert_eq!(p64["bin/win64/client.dll"][0].patterns[0].offset, 1000);
+        let err = parse_patches_from_python("patches64 = { 'a.dll': [[('AABB', 1e5)]] }").unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid number 1e5"));
+    }
+
+    fn set(hex_mask: &str, offset: isize, repl: &str) -> PatchSet {
+        PatchSet { patterns: vec![PatternSpec { hex_mask: hex_mask.into(), offset, override_hex: None }], default_replacement: Some(repl.into()) }
+    }
+
+    #[test]
+    fn negative_offsets_near_start_are_rejected() {
+        let orig = DATA.to_vec();
+        let mut out = orig.clone();
+        let mut warnings = Vec::new();
+        // Match at 1, offset -2 would start before the buffer
+        let (applied, _) = apply_patchsets_to_file(&orig, &mut out, &[set("AABBCC", -2, "9090")], false, &mut warnings);
+        assert_eq!(applied, 0);
+        assert_eq!(out, orig);
+        assert!(warnings[0].starts_with("Write out of range for pattern AABBCC"));
+        // Offset -1 lands exactly on byte 0
+        let (applied, _) = apply_patchsets_to_file(&orig, &mut out, &[set("AABBCC", -1, "9090")], false, &mut warnings);
+        assert_eq!(applied, 1);
+        assert_eq!(&out[..3], &[0x90, 0x90, 0xBB]);
+    }
 
+    #[test]
+    fn failed_files_from_report() {
+        let report = "Patched 2 file(s), 0 patch set(s) already up to date\n\
+            bin/win64/engine.dll: 3/3 patch set(s) applied\n\
+            bin/win64/client.dll: 1/2 patch set(s) applied, sha256 0011223344556677 -> 8899aabbccddeeff\n\
+            bin/win64/server.dll: 0/1 patch set(s) applied\n\
+            Failed to locate pattern [bin/win64/client.dll]\n\
+            Missing file [bin/win64/server.dll]\n";
+        assert_eq!(parse_failed_files(report), vec!["bin/win64/client.dll", "bin/win64/server.dll"]);
+    }
+
+    #[test]
+    fn retry_report_replaces_retried_entries() {
+        let previous = "Patched 2 file(s), 0 patch set(s) already up to date\n\
+            Backup: patch-backups/1700000000\n\
+            bin/win64/engine.dll: 3/3 patch set(s) applied\n\
+            bin/win64/client.dll: 1/2 patch set(s) applied\n\
+            bin/win64/server.dll: 0/1 patch set(s) applied\n\
+            Failed to locate pattern [bin/win64/client.dll]\n\
+            Missing file [bin/win64/server.dll]\n";
+        let retried = ["bin/win64/client.dll".to_string()].into_iter().collect();
+        let result = PatchResult {
+            files_patched: 1,
+            per_file: vec![PatchedFile { path: "bin/win64/client.dll".into(), applied: 2, total: 2, ..Default::default() }],
+            ..Default::default()
+        };
+        let merged = merge_report(previous, &retried, &result);
+        assert!(merged.contains("Backup: patch-backups/1700000000\n"));
+        assert!(merged.contains("bin/win64/engine.dll: 3/3"));
+        assert!(merged.contains("bin/win64/client.dll: 2/2"));
+        assert!(!merged.contains("bin/win64/client.dll: 1/2"));
+        assert!(!merged.contains("[bin/win64/client.dll]"));
+        assert_eq!(parse_failed_files(&merged), vec!["bin/win64/server.dll"]);
+    }
+
+    #[test]
+    fn win64_target_chosen_per_file() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-patch-target-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("bin/win64")).unwrap();
+        std::fs::write(root.join("bin/win64/engine.dll"), b"64").unwrap();
+        std::fs::write(root.join("bin/engine.dll"), b"32").unwrap();
+        std::fs::write(root.join("bin/d3d8.dll"), b"32").unwrap();
+
+        assert_eq!(resolve_target("bin/engine.dll", true, &root), "bin/win64/engine.dll");
+        // Only a 32-bit copy exists, so the key keeps its own path
+        assert_eq!(resolve_target("bin/d3d8.dll", true, &root), "bin/d3d8.dll");
+        // Neither exists: report it under the win64 path
+        assert_eq!(resolve_target("bin/server.dll", true, &root), "bin/win64/server.dll");
+        assert_eq!(resolve_target("bin/engine.dll", false, &root), "bin/engine.dll");
+        assert_eq!(resolve_target("garrysmod/bin/client.dll", true, &root), "garrysmod/bin/client.dll");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
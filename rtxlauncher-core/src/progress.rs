@@ -0,0 +1,58 @@
+//! One 0..=100 bar for a job made of several steps that each report their own 0..=100.
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Maps each named stage's own percent onto its share of an overall bar, sized by the stage
+/// weights. The overall value never goes down: a stage reporting less than what was already
+/// shown (a step restarting its count, or a late message from an earlier stage) holds the bar.
+#[derive(Debug)]
+pub struct WeightedProgress {
+    stages: Vec<(&'static str, u32)>,
+    shown: AtomicU8,
+}
+
+impl WeightedProgress {
+    /// `stages` in the order they run, with their relative weights. Leave out steps that will
+    /// not run so the bar does not jump over their share.
+    pub fn new(stages: &[(&'static str, u32)]) -> Self {
+        Self { stages: stages.to_vec(), shown: AtomicU8::new(0) }
+    }
+
+    /// Overall percent with `stage` at `percent` of its own range. An unknown stage keeps the
+    /// current value.
+    pub fn overall(&self, stage: &str, percent: u8) -> u8 {
+        let total = self.stages.iter().map(|(_, w)| w).sum::<u32>().max(1);
+        let mut before = 0;
+        for (name, weight) in &self.stages {
+            if *name == stage {
+                let pct = ((before * 100 + weight * percent.min(100) as u32) / total).min(100) as u8;
+                return self.shown.fetch_max(pct, Ordering::SeqCst).max(pct);
+            }
+            before += weight;
+        }
+        self.current()
+    }
+
+    pub fn current(&self) -> u8 { self.shown.load(Ordering::SeqCst) }
+
+    /// A progress callback for `stage` that forwards to `progress` with the overall percent.
+    pub fn stage<'a>(&'a self, stage: &'static str, mut progress: impl FnMut(&str, u8) + 'a) -> impl FnMut(&str, u8) + 'a {
+        move |m, p| progress(m, self.overall(stage, p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_stages_and_never_goes_back() {
+        let wp = WeightedProgress::new(&[("base", 25), ("remix", 35), ("fixes", 25), ("patches", 15)]);
+        assert_eq!(wp.overall("base", 100), 25);
+        assert_eq!(wp.overall("remix", 50), 42);
+        // A late report from an earlier stage, or an unknown one, holds the bar
+        assert_eq!(wp.overall("base", 10), 42);
+        assert_eq!(wp.overall("missing", 0), 42);
+        assert_eq!(wp.overall("fixes", 0), 60);
+        assert_eq!(wp.overall("patches", 100), 100);
+    }
+}
@@ -1,16 +1,17 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use crate::github::{GitHubRelease, GitHubAsset};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 use reqwest::Client;
-use futures_util::StreamExt;
-use std::io::Cursor;
 use std::io::Read;
 use std::fs::File;
-use std::io::Write;
 use std::fs::create_dir_all;
 use tracing::info;
 use crate::logging::ProgressThrottle;
+use crate::jobs::{CancellationToken, Cancelled, JobEvent, ProgressSink};
+use crate::error::{LauncherError, LauncherResult};
+use crate::download::{download_to_file, parse_sha256_manifest, sha256_file, DownloadEvent, DownloadProgress, RateMeter, TempDownload};
 
 pub fn select_best_asset(release: &GitHubRelease, prefer_gmod_zip: bool) -> Option<&GitHubAsset> {
     if prefer_gmod_zip {
@@ -18,11 +19,16 @@ pub fn select_best_asset(release: &GitHubRelease, prefer_gmod_zip: bool) -> Opti
     }
     let patterns = ["-release.zip", "-debugoptimized.zip", "-debug.zip", ".zip"];
     for pat in patterns {
-        if let Some(a) = release.assets.iter().find(|a| a.name.contains(pat) && !a.name.contains("-symbols")) { return Some(a); }
+        if let Some(a) = release.assets.iter().find(|a| a.name.contains(pat) && !a.name.contains("-symbols") && !a.name.ends_with(".sha256")) { return Some(a); }
     }
     None
 }
 
+// Name for messages: the release title, else its tag
+fn release_label(release: &GitHubRelease) -> String {
+    release.name.clone().or_else(|| release.tag_name.clone()).unwrap_or_else(|| "untitled release".into())
+}
+
 pub fn analyze_zip_for_layout<R: std::io::Read + std::io::Seek>(zip: &mut ZipArchive<R>) -> (bool, bool) {
     let mut has_trex = false;
     let mut has_d3d9 = false;
@@ -38,61 +44,87 @@ pub fn analyze_zip_for_layout<R: std::io::Read + std::io::Seek>(zip: &mut ZipArc
 
 pub async fn install_remix_from_release(
     release: &GitHubRelease,
-    rtx_root: &PathBuf,
-    mut progress: impl FnMut(&str, u8),
+    rtx_root: &Path,
+    cancel: &CancellationToken,
+    progress: impl ProgressSink,
+) -> LauncherResult<()> {
+    install_remix_inner(release, None, rtx_root, cancel, progress).await.map_err(Into::into)
+}
+
+/// `install_remix_from_release` with `asset` (one of `release.assets`) instead of the auto-selected one,
+/// e.g. a `-debug` build.
+pub async fn install_remix_asset(
+    release: &GitHubRelease,
+    asset: &GitHubAsset,
+    rtx_root: &Path,
+    cancel: &CancellationToken,
+    progress: impl ProgressSink,
+) -> LauncherResult<()> {
+    install_remix_inner(release, Some(asset), rtx_root, cancel, progress).await.map_err(Into::into)
+}
+
+async fn install_remix_inner(
+    release: &GitHubRelease,
+    chosen: Option<&GitHubAsset>,
+    rtx_root: &Path,
+    cancel: &CancellationToken,
+    progress: impl ProgressSink,
 ) -> Result<()> {
-    let mut progress_cb = |m: &str, pct: u8| { info!("{}", m); progress(m, pct); };
-    progress_cb("Analyzing release assets", 5);
+    let mut progress = Logged(progress);
+    progress.progress("Analyzing release assets", 5);
     // Prefer gmod zip for 64-bit if available
     let is64 = rtx_root.join("bin").join("win64").exists();
-    let asset = select_best_asset(release, is64)
-        .ok_or_else(|| anyhow::anyhow!("no suitable asset"))?;
+    let asset = match chosen {
+        Some(a) => a,
+        None => select_best_asset(release, is64)
+            .ok_or_else(|| LauncherError::NoAsset(release_label(release)))?,
+    };
     let url = asset.browser_download_url.clone().ok_or_else(|| anyhow::anyhow!("asset has no download url"))?;
 
-    progress_cb(&format!("Downloading {}", asset.name), 10);
+    progress.event(JobEvent::Stage("Downloading".into()));
+    progress.progress(&format!("Downloading {}", asset.name), 10);
     let mut throttler = ProgressThrottle::new(150);
+    let mut rate = RateMeter::new(150);
     let client = Client::new();
-    let resp = client.get(&url).header("User-Agent", "RTXLauncher-RS").send().await?;
-    let total = resp.content_length().unwrap_or(0);
-    let mut bytes = resp.bytes_stream();
-    let mut data: Vec<u8> = Vec::with_capacity(total as usize);
-    let mut downloaded: u64 = 0;
-    while let Some(chunk_res) = bytes.next().await {
-        let chunk = chunk_res?;
-        data.extend_from_slice(&chunk);
-        downloaded += chunk.len() as u64;
-        if total > 0 {
-            let pct = 10 + ((downloaded as f32 / total as f32) * 50.0) as u8;
-            let msg = format!("Downloading: {}/{} MB", downloaded/1_048_576, total/1_048_576);
-            throttler.emit("Downloading:", msg, pct.min(60), |m,p| progress_cb(m,p));
+    // Stream to a temp file instead of holding the whole archive in memory; an interrupted
+    // download is kept so the next attempt resumes it
+    let mut tmp = TempDownload::resumable(&format!("{}-{}", release.tag_name.as_deref().unwrap_or("untagged"), asset.name));
+    download_to_file(&client, &url, tmp.path(), cancel, |ev| match ev {
+        DownloadEvent::Progress(p) => {
+            rate.update(p.downloaded);
+            if p.total > 0 {
+                let pct = 10 + ((p.downloaded as f32 / p.total as f32) * 50.0) as u8;
+                throttler.emit("Downloading:", download_message(&p, &rate), pct.min(60), |m, pct| progress.progress_bytes(m, pct, &p));
+            }
         }
-    }
+        DownloadEvent::Retrying { attempt, attempts, error } => progress.progress(&format!("Download interrupted ({}), retrying ({}/{})...", error, attempt, attempts), 10),
+    }).await?;
+    tmp.complete();
 
-    progress_cb("Analyzing package", 65);
-    let mut cursor = Cursor::new(&data);
-    let mut zip = ZipArchive::new(&mut cursor)?;
+    progress.event(JobEvent::Stage("Verifying".into()));
+    verify_asset_checksum(&client, release, asset, tmp.path(), &mut progress).await?;
+
+    progress.progress("Analyzing package", 65);
+    let mut zip = ZipArchive::new(File::open(tmp.path())?)?;
     let (_has_trex, _has_d3d9) = analyze_zip_for_layout(&mut zip);
-    // reset cursor to re-open archive for extraction
-    cursor.set_position(0);
-    let mut zip = ZipArchive::new(cursor)?;
 
     let dest_path = if is64 { rtx_root.join("bin").join("win64") } else { rtx_root.join("bin") };
     create_dir_all(&dest_path).ok();
 
-    progress_cb("Extracting files", 70);
+    progress.event(JobEvent::Stage("Extracting".into()));
+    progress.progress("Extracting files", 70);
     let total_files = zip.len();
     for i in 0..total_files {
+        if cancel.is_cancelled() { return Err(cancelled_during_extraction(i, total_files, &dest_path, &mut progress)); }
         let mut file = zip.by_index(i)?;
         let raw_name = file.name().to_string();
         let name_norm = raw_name.replace('\\', "/");
         // For 64-bit installs, only extract content inside .trex/, stripping the prefix
-        if is64 {
-            if !name_norm.starts_with(".trex/") && !file.is_dir() { continue; }
-        }
+        if is64 && !name_norm.starts_with(".trex/") && !file.is_dir() { continue; }
         // Determine relative path
         let rel = if is64 && name_norm.starts_with(".trex/") { &name_norm[6..] } else { &name_norm };
         if rel.is_empty() { continue; }
-        let outpath = dest_path.join(rel.replace(':', "_"));
+        let Some(outpath) = sanitize_zip_path(&dest_path, rel) else { continue };
 
         if file.is_dir() {
             create_dir_all(&outpath).ok();
@@ -100,15 +132,182 @@ pub async fn install_remix_from_release(
             if let Some(parent) = outpath.parent() { create_dir_all(parent).ok(); }
             let mut outfile = File::create(&outpath)?;
             std::io::copy(&mut file, &mut outfile)?;
+            progress.event(JobEvent::FileExtracted(outpath));
         }
         let pct = 70 + (((i as f32 + 1.0) / (total_files as f32)) * 25.0) as u8;
-        progress_cb("Extracting...", pct.min(95));
+        progress.progress("Extracting...", pct.min(95));
     }
 
-    progress_cb("RTX Remix installed", 100);
+    progress.event(JobEvent::Done);
+    progress.progress("RTX Remix installed", 100);
+    Ok(())
+}
+
+/// Hash the downloaded archive and, when the release ships a `<asset>.sha256` manifest, compare against it.
+/// The computed hash is always reported so it shows up in the logs.
+async fn verify_asset_checksum(
+    client: &Client,
+    release: &GitHubRelease,
+    asset: &GitHubAsset,
+    archive: &Path,
+    progress: &mut impl ProgressSink,
+) -> Result<()> {
+    let actual = sha256_file(archive)?;
+    let manifest_name = format!("{}.sha256", asset.name);
+    let Some(url) = release.assets.iter().find(|a| a.name == manifest_name).and_then(|a| a.browser_download_url.clone()) else {
+        progress.event(JobEvent::Warning(format!("{} has no published SHA-256 manifest", asset.name)));
+        progress.progress(&format!("SHA-256 {}: {} (no manifest published)", asset.name, actual), 62);
+        return Ok(());
+    };
+    let text = client.get(&url).header("User-Agent", "RTXLauncher-RS").send().await?.error_for_status()?.text().await?;
+    let expected = parse_sha256_manifest(&text).ok_or_else(|| anyhow::anyhow!("{} is not a valid SHA-256 manifest", manifest_name))?;
+    if expected != actual {
+        anyhow::bail!("Checksum mismatch for {}: expected {}, got {}. The download is likely truncated or corrupted; please retry.", asset.name, expected, actual);
+    }
+    progress.progress(&format!("SHA-256 {}: {} (verified)", asset.name, actual), 62);
     Ok(())
 }
 
+// Logs every progress message before passing it on
+struct Logged<S>(S);
+
+impl<S: ProgressSink> Logged<S> {
+    // A throttled download line together with the byte counts it describes
+    fn progress_bytes(&mut self, message: &str, percent: u8, p: &DownloadProgress) {
+        self.progress(message, percent);
+        self.event(JobEvent::Bytes { done: p.downloaded, total: p.total });
+    }
+}
+
+impl<S: ProgressSink> ProgressSink for Logged<S> {
+    fn progress(&mut self, message: &str, percent: u8) {
+        info!("{}", message);
+        self.0.progress(message, percent);
+    }
+
+    fn event(&mut self, event: JobEvent) { self.0.event(event) }
+}
+
+// Download progress line: MB so far, rate and ETA, and where a resumed download picked up
+fn download_message(p: &DownloadProgress, rate: &RateMeter) -> String {
+    let msg = format!("Downloading: {}/{} MB{}", p.downloaded/1_048_576, p.total/1_048_576, rate.suffix(p.downloaded, p.total));
+    if p.resumed_from > 0 { format!("{} (resumed from {} MB)", msg, p.resumed_from/1_048_576) } else { msg }
+}
+
+/// Resolves a zip entry name under `dest`, or returns `None` (and logs) when the entry
+/// is absolute or climbs out of `dest` with `..`.
+pub(crate) fn sanitize_zip_path(dest: &Path, name: &str) -> Option<PathBuf> {
+    let norm = name.replace('\\', "/");
+    let mut out = dest.to_path_buf();
+    let escapes = norm.starts_with('/') || norm.split('/').any(|c| c == "..");
+    if escapes {
+        tracing::warn!("Skipping zip entry outside the destination: {}", name);
+        return None;
+    }
+    for comp in norm.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        out.push(comp.replace(':', "_"));
+    }
+    Some(out)
+}
+
+// Extraction overwrites files in place, so a cancelled run cannot be rolled back; make the state explicit in the log.
+fn cancelled_during_extraction(done: usize, total: usize, dest: &Path, progress: &mut impl ProgressSink) -> anyhow::Error {
+    let msg = format!("Cancelled after extracting {}/{} entries into {}; the install is incomplete, re-run the install to repair it", done, total, dest.display());
+    tracing::warn!("{}", msg);
+    progress.event(JobEvent::Warning(msg.clone()));
+    progress.progress(&msg, 100);
+    Cancelled.into()
+}
+
+/// Name of the file in the install dir listing what the last fixes install extracted.
+pub const FIXES_MANIFEST: &str = ".fixes-manifest.json";
+
+/// One file written by a fixes install, with its mtime right after extraction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub mtime: u64,
+    /// The user's modified copy was kept instead of extracting; `mtime` is that copy's
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub kept: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FixesManifest {
+    pub release: Option<String>,
+    pub files: Vec<ManifestEntry>,
+}
+
+/// What to do with a file the user changed since the last fixes install.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    Overwrite,
+    Skip,
+    /// Keep the user's copy as `<name>.bak`, then overwrite
+    Backup,
+}
+
+/// A file about to be overwritten that the user changed since the last fixes install.
+#[derive(Debug, Clone)]
+pub struct FileConflict {
+    /// Install-relative path with '/' separators
+    pub path: String,
+    pub modified: std::time::SystemTime,
+}
+
+/// Asked once before extracting, with every conflicting file; returns an action per file in the
+/// same order. Files it gives no action for are kept.
+pub type ConflictHandler<'a> = &'a mut dyn FnMut(&[FileConflict]) -> Vec<ConflictAction>;
+
+/// Outcome of `uninstall_fixes`.
+#[derive(Debug, Clone, Default)]
+pub struct UninstallReport {
+    pub removed: usize,
+    /// Files changed since the install; left in place
+    pub kept_modified: Vec<String>,
+    pub missing: usize,
+}
+
+fn mtime_secs(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs())
+}
+
+fn write_fixes_manifest(install_dir: &Path, manifest: &FixesManifest) -> Result<()> {
+    let text = serde_json::to_string_pretty(manifest)?;
+    std::fs::write(install_dir.join(FIXES_MANIFEST), text).context("write fixes manifest")
+}
+
+/// Delete the files recorded by the last fixes install, skipping any modified since,
+/// then prune directories left empty. Returns `None` when no manifest exists.
+pub fn uninstall_fixes(install_dir: &Path) -> LauncherResult<Option<UninstallReport>> {
+    let manifest_path = install_dir.join(FIXES_MANIFEST);
+    let Ok(text) = std::fs::read_to_string(&manifest_path) else { return Ok(None); };
+    let manifest: FixesManifest = serde_json::from_str(&text).context("parse fixes manifest")?;
+    let mut report = UninstallReport::default();
+    let mut dirs = std::collections::BTreeSet::new();
+    for entry in &manifest.files {
+        let Some(path) = sanitize_zip_path(install_dir, &entry.path) else { continue };
+        match mtime_secs(&path) {
+            None => report.missing += 1,
+            Some(m) if entry.kept || m != entry.mtime => report.kept_modified.push(entry.path.clone()),
+            Some(_) => {
+                std::fs::remove_file(&path).with_context(|| format!("remove {}", path.display()))?;
+                report.removed += 1;
+                let mut dir = path.parent();
+                while let Some(d) = dir.filter(|d| *d != install_dir && d.starts_with(install_dir)) {
+                    dirs.insert(d.to_path_buf());
+                    dir = d.parent();
+                }
+            }
+        }
+    }
+    // Deepest first so parents see their children gone; non-empty dirs just fail to remove
+    for d in dirs.iter().rev() { let _ = std::fs::remove_dir(d); }
+    std::fs::remove_file(&manifest_path).ok();
+    info!("Uninstalled fixes: {} removed, {} modified kept, {} already missing", report.removed, report.kept_modified.len(), report.missing);
+    Ok(Some(report))
+}
 
 // Select a package asset prioritizing "-launcher.zip" then any ".zip"
 pub fn select_best_package_asset(release: &GitHubRelease) -> Option<&GitHubAsset> {
@@ -116,6 +315,41 @@ pub fn select_best_package_asset(release: &GitHubRelease) -> Option<&GitHubAsset
     release.assets.iter().find(|a| a.name.ends_with(".zip"))
 }
 
+/// Files a fixes package must not overwrite: the Remix runtime it ships alongside.
+/// Same syntax as `.launcherignore`.
+pub const DEFAULT_IGNORE_PATTERNS: &str = r#"
+# 32bit Bridge
+bin/.trex/*
+bin/d3d8to9.dll
+bin/d3d9.dll
+bin/LICENSE.txt
+bin/NvRemixLauncher32.exe
+bin/ThirdPartyLicenses-bridge.txt
+bin/ThirdPartyLicenses-d3d8to9.txt
+bin/ThirdPartyLicenses-dxvk.txt
+
+# Remix in 64 install
+bin/win64/usd/*
+bin/win64/artifacts_readme.txt
+bin/win64/cudart64_12.dll
+bin/win64/d3d9.dll
+bin/win64/d3d9.pdb
+bin/win64/GFSDK_Aftermath_Lib.x64.dll
+bin/win64/NRC_Vulkan.dll
+bin/win64/NRD.dll
+bin/win64/NvLowLatencyVk.dll
+bin/win64/nvngx_dlss.dll
+bin/win64/nvngx_dlssd.dll
+bin/win64/nvngx_dlssg.dll
+bin/win64/NvRemixBridge.exe
+bin/win64/nvrtc64_120_0.dll
+bin/win64/nvrtc-builtins64_125.dll
+bin/win64/rtxio.dll
+bin/win64/tbb.dll
+bin/win64/tbbmalloc.dll
+bin/win64/usd_ms.dll
+"#;
+
 fn normalize_path_for_match(p: &str) -> String {
     let mut s = p.replace('\\', "/");
     if s.starts_with('/') { s = s.trim_start_matches('/').to_string(); }
@@ -132,57 +366,120 @@ fn parse_ignore_patterns(text: &str) -> std::collections::HashSet<String> {
     set
 }
 
-fn should_ignore(path: &str, ignored: &std::collections::HashSet<String>) -> bool {
-    let norm = normalize_path_for_match(path);
-    if ignored.contains(&norm) { return true; }
-    for pat in ignored.iter() {
-        if let Some(prefix) = pat.strip_suffix("/*") {
-            if norm.starts_with(prefix) { return true; }
+/// Compiled patterns from one ignore source. Entries are globs (`*` and `?` also match
+/// across `/`, `**/` matches any depth); an entry that is not a valid glob matches literally.
+struct PatternSet {
+    literals: std::collections::HashSet<String>,
+    globs: globset::GlobSet,
+}
+
+impl PatternSet {
+    fn new<'a>(patterns: impl Iterator<Item = &'a str>) -> Self {
+        let mut literals = std::collections::HashSet::new();
+        let mut builder = globset::GlobSetBuilder::new();
+        for pat in patterns {
+            match globset::Glob::new(pat) {
+                Ok(g) => { builder.add(g); }
+                Err(e) => {
+                    tracing::warn!("Ignore pattern '{}' is not a valid glob ({}); matching it literally", pat, e);
+                    literals.insert(pat.to_string());
+                }
+            }
+        }
+        let globs = builder.build().unwrap_or_else(|_| globset::GlobSet::empty());
+        Self { literals, globs }
+    }
+
+    fn is_match(&self, norm: &str) -> bool {
+        self.literals.contains(norm) || self.globs.is_match(norm)
+    }
+}
+
+/// Ignore sources layered by precedence. A `!pattern` entry re-includes paths a
+/// lower-precedence source ignored; the first layer with an opinion on a path decides.
+struct IgnoreMatcher {
+    // (ignore, include) per source, highest precedence first
+    layers: Vec<(PatternSet, PatternSet)>,
+}
+
+impl IgnoreMatcher {
+    fn new(sources: &[&std::collections::HashSet<String>]) -> Self {
+        let layers = sources.iter().map(|set| {
+            let ignore = PatternSet::new(set.iter().filter(|p| !p.starts_with('!')).map(|p| p.as_str()));
+            let include = PatternSet::new(set.iter().filter_map(|p| p.strip_prefix('!')).map(|p| p.trim_start_matches('/')));
+            (ignore, include)
+        }).collect();
+        Self { layers }
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        let norm = normalize_path_for_match(path);
+        for (ignore, include) in &self.layers {
+            if include.is_match(&norm) { return false; }
+            if ignore.is_match(&norm) { return true; }
         }
+        false
     }
-    false
 }
 
 /// Install a generic fixes package from a GitHub release into the install directory
-/// Respects default ignore patterns and optional .launcherignore contained inside the zip
+/// Respects default ignore patterns, a .launcherignore inside the zip and one in `install_dir`
+/// (local entries win over the package's, which win over the defaults)
+/// Without `on_conflict`, files changed since the last install are overwritten.
 pub async fn install_fixes_from_release(
     release: &GitHubRelease,
-    install_dir: &PathBuf,
+    install_dir: &Path,
     default_ignore_patterns: Option<&str>,
-    mut progress: impl FnMut(&str, u8),
+    on_conflict: Option<ConflictHandler<'_>>,
+    cancel: &CancellationToken,
+    progress: impl ProgressSink,
+) -> LauncherResult<()> {
+    install_fixes_inner(release, install_dir, default_ignore_patterns, on_conflict, cancel, progress).await.map_err(Into::into)
+}
+
+async fn install_fixes_inner(
+    release: &GitHubRelease,
+    install_dir: &Path,
+    default_ignore_patterns: Option<&str>,
+    on_conflict: Option<ConflictHandler<'_>>,
+    cancel: &CancellationToken,
+    progress: impl ProgressSink,
 ) -> Result<()> {
-    let mut progress_cb = |m: &str, pct: u8| { info!("{}", m); progress(m, pct); };
-    progress_cb("Analyzing release assets", 5);
+    let mut progress = Logged(progress);
+    progress.progress("Analyzing release assets", 5);
     let asset = select_best_package_asset(release)
-        .ok_or_else(|| anyhow::anyhow!("no suitable package asset"))?;
+        .ok_or_else(|| LauncherError::NoAsset(release_label(release)))?;
     let url = asset.browser_download_url.clone().ok_or_else(|| anyhow::anyhow!("asset has no download url"))?;
 
-    progress_cb(&format!("Downloading {}", asset.name), 10);
+    progress.event(JobEvent::Stage("Downloading".into()));
+    progress.progress(&format!("Downloading {}", asset.name), 10);
     let mut throttler = ProgressThrottle::new(150);
+    let mut rate = RateMeter::new(150);
     let client = Client::new();
-    let resp = client.get(&url).header("User-Agent", "RTXLauncher-RS").send().await?;
-    let total = resp.content_length().unwrap_or(0);
-    let mut bytes = resp.bytes_stream();
-    let mut data: Vec<u8> = Vec::with_capacity(total as usize);
-    let mut downloaded: u64 = 0;
-    while let Some(chunk_res) = bytes.next().await {
-        let chunk = chunk_res?;
-        data.extend_from_slice(&chunk);
-        downloaded += chunk.len() as u64;
-        if total > 0 {
-            let pct = 10 + ((downloaded as f32 / total as f32) * 40.0) as u8;
-            let msg = format!("Downloading: {}/{} MB", downloaded/1_048_576, total/1_048_576);
-            throttler.emit("Downloading:", msg, pct.min(50), |m,p| progress_cb(m,p));
+    let mut tmp = TempDownload::resumable(&format!("{}-{}", release.tag_name.as_deref().unwrap_or("untagged"), asset.name));
+    download_to_file(&client, &url, tmp.path(), cancel, |ev| match ev {
+        DownloadEvent::Progress(p) => {
+            rate.update(p.downloaded);
+            if p.total > 0 {
+                let pct = 10 + ((p.downloaded as f32 / p.total as f32) * 40.0) as u8;
+                throttler.emit("Downloading:", download_message(&p, &rate), pct.min(50), |m, pct| progress.progress_bytes(m, pct, &p));
+            }
         }
-    }
+        DownloadEvent::Retrying { attempt, attempts, error } => progress.progress(&format!("Download interrupted ({}), retrying ({}/{})...", error, attempt, attempts), 10),
+    }).await?;
+    tmp.complete();
+
+    progress.event(JobEvent::Stage("Verifying".into()));
+    verify_asset_checksum(&client, release, asset, tmp.path(), &mut progress).await?;
 
-    progress_cb("Checking package contents", 52);
-    let mut cursor = Cursor::new(&data);
-    let mut zip = ZipArchive::new(&mut cursor)?;
+    progress.progress("Checking package contents", 52);
+    let mut zip = ZipArchive::new(File::open(tmp.path())?)?;
 
-    // Build ignore set: default + .launcherignore if present
-    let mut ignored = std::collections::HashSet::new();
-    if let Some(def) = default_ignore_patterns { ignored.extend(parse_ignore_patterns(def)); }
+    // Build ignore sets: defaults, the package's .launcherignore, and a local one in the install dir
+    let default_ignored = default_ignore_patterns.map(parse_ignore_patterns).unwrap_or_default();
+    let mut package_ignored = std::collections::HashSet::new();
+    let local_ignored = std::fs::read_to_string(install_dir.join(".launcherignore"))
+        .map(|s| parse_ignore_patterns(&s)).unwrap_or_default();
 
     // Attempt to read .launcherignore without extracting to disk
     for i in 0..zip.len() {
@@ -191,36 +488,216 @@ pub async fn install_fixes_from_release(
         if name == ".launcherignore" || name.ends_with("/.launcherignore") {
             let mut s = String::new();
             let _ = f.read_to_string(&mut s);
-            for p in parse_ignore_patterns(&s) { ignored.insert(p); }
+            package_ignored = parse_ignore_patterns(&s);
             break;
         }
     }
+    info!("Ignore patterns: {} local (install dir .launcherignore), {} from package .launcherignore, {} defaults; applied in that order of precedence",
+        local_ignored.len(), package_ignored.len(), default_ignored.len());
+    let ignored = IgnoreMatcher::new(&[&local_ignored, &package_ignored, &default_ignored]);
 
-    // Reset to extract pass
-    cursor.set_position(0);
-    let mut zip = ZipArchive::new(cursor)?;
+    extract_fixes(&mut zip, install_dir, release.tag_name.clone(), &ignored, on_conflict, cancel, &mut progress)?;
+    progress.event(JobEvent::Done);
+    progress.progress("Fixes package installed", 100);
+    Ok(())
+}
+
+/// Extract a fixes package, settling conflicts with files changed since the last install,
+/// and record what landed in the fixes manifest.
+fn extract_fixes<R: Read + std::io::Seek>(
+    zip: &mut ZipArchive<R>,
+    install_dir: &Path,
+    release: Option<String>,
+    ignored: &IgnoreMatcher,
+    mut on_conflict: Option<ConflictHandler<'_>>,
+    cancel: &CancellationToken,
+    progress: &mut impl ProgressSink,
+) -> Result<()> {
+    // Entries from the previous install; a differing mtime means the user edited the file,
+    // and a kept entry is one they already chose to keep
+    let previous: std::collections::HashMap<String, ManifestEntry> = std::fs::read_to_string(install_dir.join(FIXES_MANIFEST)).ok()
+        .and_then(|t| serde_json::from_str::<FixesManifest>(&t).ok())
+        .map(|m| m.files.into_iter().map(|e| (e.path.clone(), e)).collect())
+        .unwrap_or_default();
+
+    // Settle every modified file up front so the user answers once, not mid-extraction
+    let is_modified = |rel: &str, outpath: &Path| previous.get(rel)
+        .is_some_and(|e| mtime_secs(outpath).is_some_and(|cur| e.kept || cur != e.mtime));
+    let mut decisions = std::collections::HashMap::new();
+    if let Some(handler) = on_conflict.as_mut() {
+        let mut conflicts = Vec::new();
+        for i in 0..zip.len() {
+            let file = zip.by_index(i)?;
+            if file.is_dir() || ignored.is_match(file.name()) { continue; }
+            let Some(outpath) = sanitize_zip_path(install_dir, file.name()) else { continue };
+            let rel = outpath.strip_prefix(install_dir).map(|r| r.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+            if is_modified(&rel, &outpath) {
+                let modified = std::fs::metadata(&outpath).and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH);
+                conflicts.push(FileConflict { path: rel, modified });
+            }
+        }
+        if !conflicts.is_empty() {
+            let actions = handler(&conflicts);
+            let actions = actions.into_iter().chain(std::iter::repeat(ConflictAction::Skip));
+            decisions = conflicts.into_iter().map(|c| c.path).zip(actions).collect();
+        }
+    }
 
-    progress_cb("Extracting files", 60);
+    progress.event(JobEvent::Stage("Extracting".into()));
+    progress.progress("Extracting files", 60);
     let total_files = zip.len();
+    let mut manifest = FixesManifest { release, files: Vec::new() };
+    let mut skipped_modified = 0usize;
     for i in 0..total_files {
+        if cancel.is_cancelled() {
+            // Record what did land so it can still be uninstalled
+            let _ = write_fixes_manifest(install_dir, &manifest);
+            return Err(cancelled_during_extraction(i, total_files, install_dir, progress));
+        }
         let mut file = zip.by_index(i)?;
         let name = file.name().to_string();
-        if should_ignore(&name, &ignored) { continue; }
+        if ignored.is_match(&name) { continue; }
 
-        let outpath = install_dir.join(name.replace(':', "_").replace("\\", "/"));
+        let Some(outpath) = sanitize_zip_path(install_dir, &name) else { continue };
         if file.is_dir() {
             create_dir_all(&outpath).ok();
         } else {
+            let rel = outpath.strip_prefix(install_dir).map(|r| r.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+            if let Some(action) = decisions.get(&rel) {
+                match action {
+                    ConflictAction::Overwrite => {}
+                    ConflictAction::Skip => {
+                        info!("Keeping modified {}", rel);
+                        skipped_modified += 1;
+                        // Still ours to track: the next install asks again and uninstall leaves it
+                        if let Some(mtime) = mtime_secs(&outpath) {
+                            manifest.files.push(ManifestEntry { path: rel, mtime, kept: true });
+                        }
+                        continue;
+                    }
+                    ConflictAction::Backup => {
+                        let mut bak = outpath.clone().into_os_string();
+                        bak.push(".bak");
+                        std::fs::copy(&outpath, &bak).with_context(|| format!("back up {}", outpath.display()))?;
+                        info!("Backed up modified {} to {}.bak", rel, rel);
+                    }
+                }
+            }
             if let Some(parent) = outpath.parent() { create_dir_all(parent).ok(); }
             let mut outfile = File::create(&outpath)?;
             std::io::copy(&mut file, &mut outfile)?;
+            drop(outfile);
+            if let Some(mtime) = mtime_secs(&outpath) {
+                manifest.files.push(ManifestEntry { path: rel, mtime, kept: false });
+            }
+            progress.event(JobEvent::FileExtracted(outpath));
         }
         let pct = 60 + (((i as f32 + 1.0) / (total_files as f32)) * 35.0) as u8;
-        progress_cb("Extracting...", pct.min(95));
+        progress.progress("Extracting...", pct.min(95));
     }
+    write_fixes_manifest(install_dir, &manifest)?;
 
-    progress_cb("Fixes package installed", 100);
+    if skipped_modified > 0 {
+        let msg = format!("Kept {} file(s) you modified", skipped_modified);
+        progress.event(JobEvent::Warning(msg.clone()));
+        progress.progress(&msg, 99);
+    }
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::{extract_fixes, parse_ignore_patterns, sanitize_zip_path, ConflictAction, FileConflict, FixesManifest, IgnoreMatcher, DEFAULT_IGNORE_PATTERNS, FIXES_MANIFEST};
+    use crate::jobs::CancellationToken;
+    use std::io::Write;
+    use std::path::Path;
+
+    #[test]
+    fn default_ignore_patterns_still_match() {
+        let m = IgnoreMatcher::new(&[&parse_ignore_patterns(DEFAULT_IGNORE_PATTERNS)]);
+        assert!(m.is_match("bin/d3d9.dll"));
+        assert!(m.is_match("bin\\NvRemixLauncher32.exe"));
+        assert!(m.is_match("bin/.trex/d3d9.dll"));
+        assert!(m.is_match("bin/win64/usd/plugins/foo/plugInfo.json"));
+        assert!(m.is_match("/bin/win64/d3d9.dll"));
+        assert!(!m.is_match("bin/win64/gmod.exe"));
+        assert!(!m.is_match("garrysmod/cfg/autoexec.cfg"));
+        assert!(!m.is_match("bin/d3d9.dll.bak"));
+    }
 
+    #[test]
+    fn glob_ignore_patterns() {
+        let m = IgnoreMatcher::new(&[&parse_ignore_patterns("bin/win64/*.pdb\n**/thumbs.db\nlogs/log?.txt\nrtx-remix/[bad\n")]);
+        assert!(m.is_match("bin/win64/d3d9.pdb"));
+        assert!(!m.is_match("bin/d3d9.pdb"));
+        assert!(m.is_match("thumbs.db"));
+        assert!(m.is_match("garrysmod/materials/thumbs.db"));
+        assert!(m.is_match("logs/log1.txt"));
+        assert!(!m.is_match("logs/log10.txt"));
+        assert!(m.is_match("rtx-remix/[bad"));
+    }
+
+    #[test]
+    fn local_ignore_entries_take_precedence() {
+        let defaults = parse_ignore_patterns(DEFAULT_IGNORE_PATTERNS);
+        let package = parse_ignore_patterns("garrysmod/cfg/*\n");
+        let local = parse_ignore_patterns("!bin/d3d9.dll\n!garrysmod/cfg/rtx.cfg\ngarrysmod/lua/my_edit.lua\n");
+        let m = IgnoreMatcher::new(&[&local, &package, &defaults]);
+        assert!(!m.is_match("bin/d3d9.dll"));
+        assert!(m.is_match("bin/d3d8to9.dll"));
+        assert!(!m.is_match("garrysmod/cfg/rtx.cfg"));
+        assert!(m.is_match("garrysmod/cfg/other.cfg"));
+        assert!(m.is_match("garrysmod/lua/my_edit.lua"));
+    }
+
+    #[test]
+    fn zip_entries_cannot_escape_destination() {
+        let dest = Path::new("install");
+        assert_eq!(sanitize_zip_path(dest, "bin/d3d9.dll"), Some(dest.join("bin").join("d3d9.dll")));
+        assert_eq!(sanitize_zip_path(dest, "bin\\./a:b.txt"), Some(dest.join("bin").join("a_b.txt")));
+        assert_eq!(sanitize_zip_path(dest, "../evil.dll"), None);
+        assert_eq!(sanitize_zip_path(dest, "bin\\..\\..\\evil.dll"), None);
+        assert_eq!(sanitize_zip_path(dest, "/etc/passwd"), None);
+    }
+
+    #[test]
+    fn skipped_modified_files_stay_in_manifest() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-fixes-skip-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer.start_file("garrysmod/cfg/rtx.cfg", zip::write::FileOptions::default()).unwrap();
+        writer.write_all(b"packaged").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        let ignored = IgnoreMatcher::new(&[]);
+        let cancel = CancellationToken::new();
+        let cfg = root.join("garrysmod").join("cfg").join("rtx.cfg");
+        let mut asked = Vec::new();
+        let install = |asked: &mut Vec<Vec<String>>| {
+            let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes.clone())).unwrap();
+            let mut handler = |c: &[FileConflict]| {
+                asked.push(c.iter().map(|c| c.path.clone()).collect());
+                vec![ConflictAction::Skip; c.len()]
+            };
+            extract_fixes(&mut zip, &root, None, &ignored, Some(&mut handler), &cancel, &mut |_: &str, _: u8| {}).unwrap();
+            let text = std::fs::read_to_string(root.join(FIXES_MANIFEST)).unwrap();
+            serde_json::from_str::<FixesManifest>(&text).unwrap()
+        };
+
+        install(&mut asked);
+        // Edit the installed file, with an mtime clearly apart from the recorded one
+        std::fs::write(&cfg, "mine").unwrap();
+        let earlier = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&cfg).unwrap().set_modified(earlier).unwrap();
+
+        for _ in 0..2 {
+            let manifest = install(&mut asked);
+            assert_eq!(manifest.files.len(), 1);
+            assert_eq!(manifest.files[0].path, "garrysmod/cfg/rtx.cfg");
+            assert!(manifest.files[0].kept);
+        }
+        assert_eq!(asked, vec![vec!["garrysmod/cfg/rtx.cfg".to_string()]; 2]);
+        assert_eq!(std::fs::read_to_string(&cfg).unwrap(), "mine");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,8 +1,14 @@
 use anyhow::{Result, Context};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::process::Command;
 use tracing::info;
+use crate::settings::{AppSettings, SettingsStore};
+
+const EXTRACTOR_EXE: &str = "RtxIoResourceExtractor.exe";
+// Extractor processes run at once; each package gets its own
+const MAX_PARALLEL_EXTRACTIONS: usize = 4;
 
 pub fn has_rtxio_packages(game_install_path: &Path, remix_mod_folder: &str) -> bool {
     let remix_mod_path = game_install_path.join("rtx-remix").join("mods").join(remix_mod_folder);
@@ -10,21 +16,129 @@ pub fn has_rtxio_packages(game_install_path: &Path, remix_mod_folder: &str) -> b
     fs::read_dir(&remix_mod_path).map(|it| it.filter_map(|e| e.ok()).any(|e| e.path().extension().map(|x| x.eq("pkg")).unwrap_or(false))).unwrap_or(false)
 }
 
-fn default_extractor_path() -> PathBuf {
-    let base = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_else(|| PathBuf::from("."));
-    base.join("launcherdeps").join("rtxio").join("bin").join("RtxIoResourceExtractor.exe")
+// Places the bundled extractor may live: next to the launcher or in the RTX install
+fn extractor_candidates(settings: &AppSettings) -> Vec<PathBuf> {
+    let bundle = Path::new("launcherdeps").join("rtxio").join("bin");
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())) {
+        dirs.push(exe_dir.join(&bundle));
+        dirs.push(exe_dir);
+    }
+    dirs.push(settings.install_dir().join(&bundle));
+    let mut out: Vec<PathBuf> = dirs.iter().map(|d| d.join(EXTRACTOR_EXE)).collect();
+    // A native build, if someone made one
+    #[cfg(unix)]
+    {
+        out.extend(dirs.iter().map(|d| d.join("RtxIoResourceExtractor")));
+        if let Ok(p) = which::which("RtxIoResourceExtractor") { out.push(p); }
+    }
+    out
+}
+
+// A located extractor; Windows builds on unix run through Proton and take Wine paths
+struct Extractor {
+    path: PathBuf,
+    via_proton: bool,
+}
+
+impl Extractor {
+    fn command(&self, _settings: &AppSettings) -> Result<Command> {
+        #[cfg(unix)]
+        if self.via_proton {
+            return crate::launch::proton_run_command(_settings, &self.path, &[])
+                .with_context(|| format!("{} needs Proton to run on this system", self.path.display()));
+        }
+        Ok(Command::new(&self.path))
+    }
+
+    fn path_arg(&self, p: &Path) -> OsString {
+        // Proton maps the unix root to Z:
+        if self.via_proton { return format!("Z:{}", p.display()).replace('/', "\\").into(); }
+        p.as_os_str().to_os_string()
+    }
+}
+
+fn is_windows_exe(p: &Path) -> bool {
+    p.extension().map(|x| x.eq_ignore_ascii_case("exe")).unwrap_or(false)
+}
+
+// Why `p` can't be run directly, if it can't
+fn not_runnable_reason(p: &Path) -> Option<String> {
+    let meta = match fs::metadata(p) {
+        Ok(m) => m,
+        Err(_) => return Some("not found".to_string()),
+    };
+    if !meta.is_file() { return Some("not a file".to_string()); }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if !is_windows_exe(p) && meta.permissions().mode() & 0o111 == 0 { return Some("not executable (chmod +x it)".to_string()); }
+    }
+    None
+}
+
+fn locate_extractor(settings: &AppSettings) -> Result<Extractor> {
+    let configured = settings.rtxio_extractor_path.as_deref().map(str::trim).filter(|p| !p.is_empty());
+    let path = if let Some(p) = configured {
+        // A configured path is used as-is so a typo isn't masked by a bundled copy
+        let p = PathBuf::from(p);
+        if let Some(reason) = not_runnable_reason(&p) { anyhow::bail!("Configured RTXIO extractor {}: {}", p.display(), reason); }
+        p
+    } else {
+        let candidates = extractor_candidates(settings);
+        match candidates.iter().find(|p| not_runnable_reason(p).is_none()) {
+            Some(p) => p.clone(),
+            None => {
+                let tried: Vec<String> = candidates.iter().map(|p| format!("{} ({})", p.display(), not_runnable_reason(p).unwrap_or_default())).collect();
+                anyhow::bail!("RTXIO extractor not found. Set its path in Settings or place it at ./launcherdeps/rtxio/bin/{}. Tried: {}", EXTRACTOR_EXE, tried.join("; "));
+            }
+        }
+    };
+    let via_proton = cfg!(unix) && is_windows_exe(&path);
+    Ok(Extractor { path, via_proton })
+}
+
+// Run the extractor on one package into its own output folder
+fn extract_one(extractor: &Extractor, settings: &AppSettings, pkg: &Path, out: &Path) -> Result<()> {
+    fs::create_dir_all(out)?;
+    let status = extractor.command(settings)?
+        .arg(extractor.path_arg(pkg))
+        .arg("--force")
+        .arg("-o")
+        .arg(extractor.path_arg(out))
+        .status()
+        .with_context(|| format!("run extractor for {}", pkg.display()))?;
+    if !status.success() { anyhow::bail!("extractor exited with {}", status); }
+    Ok(())
+}
+
+// Merge the contents of `src` into `dst` (copy_dir_recursive would nest `src` itself)
+fn merge_into(src: &Path, dst: &Path) -> Result<()> {
+    for entry in fs::read_dir(src)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            crate::fs_linker::copy_dir_recursive(&path, dst)?;
+        } else {
+            fs::copy(&path, dst.join(entry.file_name())).with_context(|| format!("copy {}", path.display()))?;
+        }
+    }
+    Ok(())
 }
 
 pub fn extract_packages(game_install_path: &Path, remix_mod_folder: &str, mut progress_cb: impl FnMut(&str, u8)) -> Result<bool> {
     let remix_mod_path = game_install_path.join("rtx-remix").join("mods").join(remix_mod_folder);
     if !remix_mod_path.exists() { return Ok(true); }
 
-    let extractor = default_extractor_path();
-    if !extractor.exists() {
-        info!("RTXIO extractor not found: {}", extractor.display());
-        progress_cb("RTXIO extractor not found. Place it at ./launcherdeps/rtxio/bin/RtxIoResourceExtractor.exe", 0);
-        return Ok(false);
-    }
+    let settings = SettingsStore::new()?.load()?;
+    let extractor = match locate_extractor(&settings) {
+        Ok(e) => e,
+        Err(e) => {
+            info!("{:#}", e);
+            progress_cb(&format!("{:#}", e), 0);
+            return Ok(false);
+        }
+    };
+    info!("RTXIO extractor: {}{}", extractor.path.display(), if extractor.via_proton { " (via Proton)" } else { "" });
 
     let pkg_files: Vec<PathBuf> = fs::read_dir(&remix_mod_path)?
         .filter_map(|e| e.ok())
@@ -37,28 +151,64 @@ pub fn extract_packages(game_install_path: &Path, remix_mod_folder: &str, mut pr
     if temp_out.exists() { let _ = fs::remove_dir_all(&temp_out); }
     fs::create_dir_all(&temp_out).ok();
 
-    for (i, pkg) in pkg_files.iter().enumerate() {
-        let msg = format!("Extracting {} ({}/{})", pkg.file_name().unwrap().to_string_lossy(), i+1, pkg_files.len());
-        info!("{}", msg);
-        progress_cb(&msg, (i as u8 * 100 / pkg_files.len() as u8).min(95));
-        let status = Command::new(&extractor)
-            .arg(pkg)
-            .arg("--force")
-            .arg("-o")
-            .arg(&temp_out)
-            .status()
-            .with_context(|| format!("run extractor for {}", pkg.display()))?;
-        if !status.success() {
-            progress_cb("RTXIO extractor failed", 0);
-            return Ok(false);
+    // Progress is weighted by package size so one large package counts for what it is
+    let sizes: Vec<u64> = pkg_files.iter().map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).collect();
+    let total_bytes = sizes.iter().sum::<u64>().max(1);
+    // Proton runs share one prefix, so keep those serial
+    let workers = if extractor.via_proton { 1 } else {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1).min(MAX_PARALLEL_EXTRACTIONS).min(pkg_files.len())
+    };
+    let msg = format!("Extracting {} package(s), {} MB", pkg_files.len(), total_bytes / 1_048_576);
+    info!("{} with {} worker(s)", msg, workers);
+    progress_cb(&msg, 0);
+
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let mut results: Vec<Option<Result<()>>> = pkg_files.iter().map(|_| None).collect();
+    std::thread::scope(|s| {
+        let (tx, rx) = std::sync::mpsc::channel::<(usize, Result<()>)>();
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, extractor, settings, pkg_files, temp_out) = (&next, &extractor, &settings, &pkg_files, &temp_out);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let Some(pkg) = pkg_files.get(i) else { break };
+                let _ = tx.send((i, extract_one(extractor, settings, pkg, &temp_out.join(i.to_string()))));
+            });
         }
-    }
+        drop(tx);
+        let mut done_bytes = 0u64;
+        let mut done = 0usize;
+        for (i, result) in rx {
+            done_bytes += sizes[i];
+            done += 1;
+            let name = pkg_files[i].file_name().unwrap_or_default().to_string_lossy();
+            let msg = match &result {
+                Ok(()) => format!("Extracted {} ({}/{})", name, done, pkg_files.len()),
+                Err(e) => format!("Failed to extract {}: {:#} ({}/{})", name, e, done, pkg_files.len()),
+            };
+            info!("{}", msg);
+            progress_cb(&msg, ((done_bytes as f64 / total_bytes as f64) * 90.0) as u8);
+            results[i] = Some(result);
+        }
+    });
 
-    // Copy extracted files into remix mod path
-    let _ = crate::fs_linker::copy_dir_recursive(&temp_out, &remix_mod_path)?;
-    // Remove pkgs
-    for pkg in pkg_files { let _ = fs::remove_file(pkg); }
+    // Copy what extracted into the remix mod path and drop those packages; failed ones stay for a retry
+    progress_cb("Copying extracted files", 95);
+    let mut failed: Vec<String> = Vec::new();
+    for (i, (pkg, result)) in pkg_files.iter().zip(results).enumerate() {
+        let name = pkg.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let result = result.unwrap_or_else(|| Err(anyhow::anyhow!("extractor did not report back")))
+            .and_then(|()| merge_into(&temp_out.join(i.to_string()), &remix_mod_path));
+        match result {
+            Ok(()) => { let _ = fs::remove_file(pkg); }
+            Err(e) => { info!("RTXIO {} failed: {:#}", name, e); failed.push(name); }
+        }
+    }
     let _ = fs::remove_dir_all(&temp_out);
+    if !failed.is_empty() {
+        progress_cb(&format!("RTXIO extraction failed for {} of {} package(s): {}", failed.len(), pkg_files.len(), failed.join(", ")), 100);
+        return Ok(false);
+    }
     progress_cb("RTXIO package extraction completed", 100);
     Ok(true)
 }
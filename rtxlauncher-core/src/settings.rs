@@ -1,10 +1,19 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}};
 use std::env;
+use crate::fs_linker::LinkStrategy;
+use crate::launch::WindowMode;
+
+/// Bumped whenever a settings field is renamed or changes meaning; see `migrate`.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppSettings {
+    // Files written before versioning have no key and read as 0
+    #[serde(default)]
+    pub schema_version: u32,
     pub manually_specified_install_path: Option<String>,
     pub width: Option<u32>,
     pub height: Option<u32>,
@@ -15,22 +24,63 @@ pub struct AppSettings {
     pub developer_mode: bool,
     pub tools_mode: bool,
     pub custom_launch_options: Option<String>,
+    pub window_mode: WindowMode,
+    // Pass +mat_disable_d3d9ex 1 -nod3d9ex, which Remix needs
+    pub disable_d3d9ex: bool,
     // Linux-specific launch settings
     pub linux_proton_path: Option<String>,
     pub linux_steam_root_override: Option<String>,
     pub linux_enable_proton_log: bool,
     pub linux_selected_proton_label: Option<String>,
+    // WINEDLLOVERRIDES used verbatim when set; None uses d3d9=n,b
+    pub linux_dll_overrides: Option<String>,
+    // Extra KEY=VALUE lines set on Linux launches, after (and overriding) the launcher's own
+    pub linux_extra_env: String,
+    // Wrap the Proton launch in gamemoderun / mangohud when installed
+    pub linux_use_gamemode: bool,
+    pub linux_use_mangohud: bool,
     // Recorded installed component versions
     pub installed_remix_version: Option<String>,
     pub installed_fixes_version: Option<String>,
     pub installed_patches_commit: Option<String>,
+    // When (RFC3339) and from which owner/repo each component was installed
+    pub installed_remix_at: Option<String>,
+    pub installed_remix_source: Option<String>,
+    pub installed_fixes_at: Option<String>,
+    pub installed_fixes_source: Option<String>,
+    pub installed_patches_at: Option<String>,
+    pub installed_patches_source: Option<String>,
+    // Release tags selected in the repositories tab; None follows the latest release
+    pub remix_pinned_tag: Option<String>,
+    pub fixes_pinned_tag: Option<String>,
+    // List pre-releases in the version dropdowns and let quick install pick them
+    pub show_prereleases: bool,
+    // Where the RTX install is created; None uses the launcher's own folder
+    pub install_target_path: Option<String>,
+    // GitHub mirror / Enterprise hosts; None uses api.github.com and raw.githubusercontent.com
+    pub github_api_base: Option<String>,
+    pub github_raw_base: Option<String>,
+    // tracing filter: error/warn/info/debug/trace
+    pub log_level: String,
+    // Daily log files kept in logs/ before older ones are deleted
+    pub log_retention: usize,
+    // How content folders are linked back to the vanilla install
+    pub link_strategy: LinkStrategy,
+    // Snapshot live binaries before deploying patches
+    pub patch_backup: bool,
+    // RtxIoResourceExtractor location; None searches the usual places
+    pub rtxio_extractor_path: Option<String>,
     // Setup completion tracking
     pub setup_completed: Option<bool>,
+    // Launcher window position and inner size (logical points) when it was last closed
+    pub window_pos: Option<[f32; 2]>,
+    pub window_size: Option<[f32; 2]>,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             manually_specified_install_path: None,
             width: Some(1920),
             height: Some(1080),
@@ -42,31 +92,132 @@ impl Default for AppSettings {
             developer_mode: false,
             tools_mode: false,
             custom_launch_options: None,
+            window_mode: WindowMode::Borderless,
+            disable_d3d9ex: true,
             linux_proton_path: None,
             linux_steam_root_override: None,
             linux_enable_proton_log: false,
             linux_selected_proton_label: None,
+            linux_dll_overrides: None,
+            linux_extra_env: String::new(),
+            linux_use_gamemode: false,
+            linux_use_mangohud: false,
             installed_remix_version: None,
             installed_fixes_version: None,
             installed_patches_commit: None,
+            installed_remix_at: None,
+            installed_remix_source: None,
+            installed_fixes_at: None,
+            installed_fixes_source: None,
+            installed_patches_at: None,
+            installed_patches_source: None,
+            remix_pinned_tag: None,
+            fixes_pinned_tag: None,
+            show_prereleases: false,
+            install_target_path: None,
+            link_strategy: LinkStrategy::Auto,
+            github_api_base: None,
+            github_raw_base: None,
+            log_level: "info".into(),
+            log_retention: crate::logging::DEFAULT_LOG_RETENTION,
+            patch_backup: true,
+            rtxio_extractor_path: None,
             setup_completed: None,
+            window_pos: None,
+            window_size: None,
         }
     }
 }
 
+impl AppSettings {
+    /// Root of the RTX install: `install_target_path` when set, otherwise the launcher's folder.
+    pub fn install_dir(&self) -> PathBuf {
+        if let Some(p) = self.install_target_path.as_ref().filter(|p| !p.trim().is_empty()) { return PathBuf::from(p); }
+        env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default()
+    }
+}
+
 #[derive(Clone)]
 pub struct SettingsStore {
     path: PathBuf,
+    location: SettingsLocation,
+}
+
+/// Where `settings.toml` lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsLocation {
+    /// Per-user config directory (default); works when the launcher folder is read-only
+    User,
+    /// Next to the launcher executable, selected by a marker file there
+    Portable,
+}
+
+// Presence of this file next to the executable selects portable mode
+const PORTABLE_MARKER: &str = "rtxlauncher.portable";
+
+fn exe_dir() -> Result<PathBuf> {
+    env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .ok_or_else(|| anyhow::anyhow!("failed to resolve launcher directory"))
+}
+
+fn user_config_dir() -> Result<PathBuf> {
+    let dirs = directories::ProjectDirs::from("com", "rtxlauncher", "rtxlauncher")
+        .ok_or_else(|| anyhow::anyhow!("project dirs"))?;
+    Ok(dirs.config_dir().to_path_buf())
 }
 
 impl SettingsStore {
     pub fn new() -> Result<Self> {
-        let exe_dir = env::current_exe()
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .ok_or_else(|| anyhow::anyhow!("failed to resolve launcher directory"))?;
-        fs::create_dir_all(&exe_dir)?;
-        Ok(Self { path: exe_dir.join("settings.toml") })
+        Self::at(Self::configured_location())
+    }
+
+    /// The location selected on this machine: portable if the marker exists, otherwise the user config dir.
+    pub fn configured_location() -> SettingsLocation {
+        match exe_dir() {
+            Ok(dir) if dir.join(PORTABLE_MARKER).exists() => SettingsLocation::Portable,
+            _ => SettingsLocation::User,
+        }
+    }
+
+    fn at(location: SettingsLocation) -> Result<Self> {
+        let dir = match location {
+            SettingsLocation::User => user_config_dir()?,
+            SettingsLocation::Portable => exe_dir()?,
+        };
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("settings.toml");
+        // First run with the user config dir: carry over settings from the launcher folder
+        if location == SettingsLocation::User && !path.exists() {
+            let legacy = exe_dir()?.join("settings.toml");
+            if legacy.exists() {
+                fs::copy(&legacy, &path)?;
+                tracing::info!("Imported settings from {} to {}", legacy.display(), path.display());
+            }
+        }
+        Ok(Self { path, location })
+    }
+
+    pub fn location(&self) -> SettingsLocation { self.location }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// The repository source lists kept in `sources.toml` beside the settings file.
+    pub fn load_sources(&self) -> crate::sources::SourceLists {
+        crate::sources::SourceLists::load(&self.path.with_file_name("sources.toml"))
+    }
+
+    /// Switch to `location`, writing `settings` there, and return the store for it.
+    pub fn relocate(&self, location: SettingsLocation, settings: &AppSettings) -> Result<Self> {
+        let marker = exe_dir()?.join(PORTABLE_MARKER);
+        match location {
+            SettingsLocation::Portable => fs::write(&marker, b"Settings for this launcher are stored in settings.toml next to it.\n")?,
+            SettingsLocation::User => { if marker.exists() { fs::remove_file(&marker)?; } }
+        }
+        let store = Self::at(location)?;
+        store.save(settings)?;
+        Ok(store)
     }
 
     pub fn load(&self) -> Result<AppSettings> {
@@ -74,7 +225,18 @@ impl SettingsStore {
             return Ok(AppSettings::default());
         }
         let text = fs::read_to_string(&self.path)?;
-        let settings: AppSettings = toml::from_str(&text)?;
+        let (mut settings, salvaged) = match toml::from_str::<AppSettings>(&text) {
+            Ok(s) => (s, false),
+            Err(e) => {
+                tracing::warn!("settings.toml did not parse ({}); salvaging known keys", e);
+                let _ = fs::write(self.path.with_extension("toml.bak"), &text);
+                (salvage(&text), true)
+            }
+        };
+        if salvaged || settings.schema_version < SETTINGS_SCHEMA_VERSION {
+            migrate(&mut settings);
+            self.save(&settings)?;
+        }
         Ok(settings)
     }
 
@@ -85,4 +247,69 @@ impl SettingsStore {
     }
 }
 
+/// Upgrade settings written by an older schema in place.
+fn migrate(settings: &mut AppSettings) {
+    // 0 -> 1: versioning introduced, no field changes
+    settings.schema_version = SETTINGS_SCHEMA_VERSION;
+}
+
+/// Best-effort parse of a settings file that no longer matches `AppSettings`: every key
+/// that still deserializes is kept, the rest fall back to defaults.
+fn salvage(text: &str) -> AppSettings {
+    let Ok(toml::Value::Table(parsed)) = text.parse::<toml::Value>() else {
+        tracing::warn!("settings.toml is not valid TOML; using defaults");
+        return AppSettings::default();
+    };
+    let mut merged = match toml::Value::try_from(AppSettings::default()) {
+        Ok(toml::Value::Table(t)) => t,
+        _ => return AppSettings::default(),
+    };
+    for (key, value) in parsed {
+        let mut candidate = merged.clone();
+        candidate.insert(key.clone(), value);
+        if toml::Value::Table(candidate.clone()).try_into::<AppSettings>().is_ok() {
+            merged = candidate;
+        } else {
+            tracing::warn!("Dropping unreadable setting '{}'", key);
+        }
+    }
+    toml::Value::Table(merged).try_into().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn salvage_keeps_readable_keys() {
+        let text = "manually_specified_install_path = \"D:/Steam/GarrysMod\"\nwidth = \"wide\"\nconsole_enabled = false\n";
+        assert!(toml::from_str::<AppSettings>(text).is_err());
+        let s = salvage(text);
+        assert_eq!(s.manually_specified_install_path.as_deref(), Some("D:/Steam/GarrysMod"));
+        assert_eq!(s.width, AppSettings::default().width);
+        assert!(!s.console_enabled);
+    }
 
+    #[test]
+    fn installed_versions_round_trip() {
+        let s = AppSettings {
+            installed_remix_version: Some("remix-1.0.0".into()),
+            installed_fixes_version: Some("v2024.1".into()),
+            installed_patches_commit: Some("abc1234".into()),
+            ..Default::default()
+        };
+        let back: AppSettings = toml::from_str(&toml::to_string_pretty(&s).unwrap()).unwrap();
+        assert_eq!(back.installed_remix_version, s.installed_remix_version);
+        assert_eq!(back.installed_fixes_version, s.installed_fixes_version);
+        assert_eq!(back.installed_patches_commit, s.installed_patches_commit);
+        // Files from before these fields existed still load
+        let old: AppSettings = toml::from_str("console_enabled = true").unwrap();
+        assert!(old.installed_remix_version.is_none());
+    }
+
+    #[test]
+    fn unversioned_files_read_as_schema_zero() {
+        let s: AppSettings = toml::from_str("console_enabled = true").unwrap();
+        assert_eq!(s.schema_version, 0);
+    }
+}
@@ -0,0 +1,100 @@
+//! Repositories offered in the RTX Remix, fixes and patch source dropdowns. The lists live in
+//! `sources.toml` next to `settings.toml` so a fork can be added without rebuilding; a missing
+//! file is created with the built-in entries, and a missing or empty list falls back to them.
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoSource {
+    pub owner: String,
+    pub repo: String,
+    /// Dropdown text; `owner/repo` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl RepoSource {
+    fn new(owner: &str, repo: &str, label: Option<&str>) -> Self {
+        Self { owner: owner.into(), repo: repo.into(), label: label.map(Into::into) }
+    }
+
+    /// `owner/repo`, as recorded in the installed-from settings.
+    pub fn slug(&self) -> String { format!("{}/{}", self.owner, self.repo) }
+
+    pub fn label(&self) -> String { self.label.clone().unwrap_or_else(|| self.slug()) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceLists {
+    pub remix: Vec<RepoSource>,
+    pub fixes: Vec<RepoSource>,
+    pub patches: Vec<RepoSource>,
+}
+
+impl Default for SourceLists {
+    fn default() -> Self {
+        Self {
+            remix: vec![
+                RepoSource::new("sambow23", "dxvk-remix-gmod", None),
+                RepoSource::new("NVIDIAGameWorks", "rtx-remix", Some("(OFFICIAL) NVIDIAGameWorks/rtx-remix")),
+            ],
+            fixes: vec![
+                RepoSource::new("Xenthio", "gmod-rtx-fixes-2", Some("Xenthio/gmod-rtx-fixes-2 (Any)")),
+                RepoSource::new("Xenthio", "RTXFixes", Some("Xenthio/RTXFixes (gmod_main)")),
+            ],
+            patches: vec![
+                RepoSource::new("sambow23", "SourceRTXTweaks", None),
+                RepoSource::new("BlueAmulet", "SourceRTXTweaks", None),
+                RepoSource::new("Xenthio", "SourceRTXTweaks", None),
+            ],
+        }
+    }
+}
+
+impl SourceLists {
+    /// Read `path`, writing the built-in lists there first when it does not exist. A file that does
+    /// not parse is left alone and the built-ins are used.
+    pub fn load(path: &Path) -> Self {
+        let Ok(text) = fs::read_to_string(path) else {
+            let defaults = Self::default();
+            if let Ok(text) = toml::to_string_pretty(&defaults) { let _ = fs::write(path, text); }
+            return defaults;
+        };
+        match toml::from_str::<Self>(&text) {
+            Ok(lists) => lists.with_fallbacks(),
+            Err(e) => {
+                tracing::warn!("{} did not parse ({}); using the built-in sources", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    // Every list needs at least one entry for the dropdowns and `pick`
+    fn with_fallbacks(mut self) -> Self {
+        let defaults = Self::default();
+        if self.remix.is_empty() { self.remix = defaults.remix; }
+        if self.fixes.is_empty() { self.fixes = defaults.fixes; }
+        if self.patches.is_empty() { self.patches = defaults.patches; }
+        self
+    }
+
+    /// Entry `idx` of `list`, clamped to the last one so a stale index from a shorter file stays valid.
+    pub fn pick(list: &[RepoSource], idx: usize) -> &RepoSource { &list[idx.min(list.len() - 1)] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_entries_replace_lists_and_empty_lists_fall_back() {
+        let text = "[[patches]]\nowner = \"me\"\nrepo = \"SourceRTXTweaks\"\nlabel = \"My fork\"\n";
+        let lists = toml::from_str::<SourceLists>(text).unwrap().with_fallbacks();
+        assert_eq!(lists.patches.len(), 1);
+        assert_eq!(lists.patches[0].label(), "My fork");
+        assert_eq!(lists.remix, SourceLists::default().remix);
+        assert_eq!(SourceLists::pick(&lists.patches, 5).slug(), "me/SourceRTXTweaks");
+        assert_eq!(lists.remix[0].label(), "sambow23/dxvk-remix-gmod");
+    }
+}
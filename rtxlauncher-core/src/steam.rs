@@ -1,208 +1,202 @@
 use std::path::PathBuf;
 use std::fs;
 
-/// Parse Steam library folders from the contents of a libraryfolders.vdf file.
-///
-/// Supports both old and new VDF layouts:
-/// - Old: "1" "D:\\SteamLibrary"
-/// - New: nested blocks with a "path" entry.
-#[cfg(windows)]
-fn parse_libraryfolders_vdf_paths(text: &str) -> Vec<PathBuf> {
-    fn unescape_vdf_value_windows(raw: &str) -> String {
-        let mut out = String::with_capacity(raw.len());
-        let mut it = raw.chars().peekable();
-        while let Some(c) = it.next() {
-            if c == '\\' {
-                if let Some(n) = it.next() {
+#[derive(Debug, PartialEq)]
+enum VdfToken {
+    Str(String),
+    Open,
+    Close,
+}
+
+/// Split VDF/ACF text into quoted strings (unescaped) and braces, skipping `//` comments.
+/// Unquoted words are accepted as strings too, as Steam's own parser does.
+fn vdf_tokens(text: &str) -> Vec<VdfToken> {
+    let mut tokens = Vec::new();
+    let mut it = text.chars().peekable();
+    while let Some(c) = it.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '/' if it.peek() == Some(&'/') => { for n in it.by_ref() { if n == '\n' { break; } } }
+            '"' => {
+                let mut s = String::new();
+                while let Some(n) = it.next() {
                     match n {
-                        '\\' => out.push('\\'),
-                        '"' => out.push('"'),
-                        'n' => out.push('\n'),
-                        'r' => out.push('\r'),
-                        't' => out.push('\t'),
-                        _ => out.push(n),
+                        '"' => break,
+                        '\\' => match it.next() {
+                            Some('n') => s.push('\n'),
+                            Some('r') => s.push('\r'),
+                            Some('t') => s.push('\t'),
+                            Some(other) => s.push(other),
+                            None => s.push('\\'),
+                        },
+                        _ => s.push(n),
                     }
-                } else {
-                    out.push('\\');
                 }
-            } else {
-                out.push(c);
+                tokens.push(VdfToken::Str(s));
             }
-        }
-        out
-    }
-    let mut results: Vec<PathBuf> = Vec::new();
-    for line in text.lines() {
-        let l = line.trim();
-        // New format: ... "path" "C:\\..." ...
-        if l.contains("\"path\"") {
-            if let Some(first_q) = l.find('"') {
-                if let Some(rest) = l.get(first_q + 1..) {
-                    if let Some(second_rel) = rest.find('"') {
-                        if let Some(value_seg) = rest.get(second_rel + 1..) {
-                            if let Some(value_start) = value_seg.find('"') {
-                                if let Some(value_end_rel) = value_seg.get(value_start + 1..).and_then(|s| s.find('"')) {
-                                    let raw = &value_seg[value_start + 1..value_start + 1 + value_end_rel];
-                                    let unescaped = unescape_vdf_value_windows(raw);
-                                    let normalized = unescaped.replace('/', "\\");
-                                    let p = PathBuf::from(normalized);
-                                    if !results.contains(&p) { results.push(p); }
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        // Old format: "<digits>" "<path>"
-        if l.starts_with('"') {
-            let mut parts = l.split('"').filter(|s| !s.is_empty());
-            if let (Some(key), Some(val)) = (parts.next(), parts.nth(1)) { // skip the quote between key and value
-                if key.chars().all(|c| c.is_ascii_digit()) {
-                    let unescaped = unescape_vdf_value_windows(val);
-                    let normalized = unescaped.replace('/', "\\");
-                    let p = PathBuf::from(normalized);
-                    if !results.contains(&p) { results.push(p); }
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut s = String::from(c);
+                while let Some(&n) = it.peek() {
+                    if n.is_whitespace() || n == '{' || n == '}' || n == '"' { break; }
+                    s.push(n);
+                    it.next();
                 }
+                tokens.push(VdfToken::Str(s));
             }
         }
     }
-    results
+    tokens
 }
 
-#[cfg(unix)]
-fn parse_libraryfolders_vdf_paths(text: &str) -> Vec<PathBuf> {
-    let mut results: Vec<PathBuf> = Vec::new();
-    for line in text.lines() {
-        let l = line.trim();
-        // New format: ... "path" "/home/..." ...
-        if l.contains("\"path\"") {
-            if let Some(first_q) = l.find('"') {
-                if let Some(rest) = l.get(first_q + 1..) {
-                    if let Some(second_rel) = rest.find('"') {
-                        if let Some(value_seg) = rest.get(second_rel + 1..) {
-                            if let Some(value_start) = value_seg.find('"') {
-                                if let Some(value_end_rel) = value_seg.get(value_start + 1..).and_then(|s| s.find('"')) {
-                                    let raw = &value_seg[value_start + 1..value_start + 1 + value_end_rel];
-                                    let p = PathBuf::from(raw);
-                                    if !results.contains(&p) { results.push(p); }
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        // Old format: "<digits>" "<path>"
-        if l.starts_with('"') {
-            let mut parts = l.split('"').filter(|s| !s.is_empty());
-            if let (Some(key), Some(val)) = (parts.next(), parts.nth(1)) {
-                if key.chars().all(|c| c.is_ascii_digit()) {
-                    let p = PathBuf::from(val);
-                    if !results.contains(&p) { results.push(p); }
-                }
+/// Every `"key" "value"` pair in VDF text with its block depth (0 = top level), in order.
+fn vdf_pairs(text: &str) -> Vec<(usize, String, String)> {
+    let tokens = vdf_tokens(text);
+    let mut pairs = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1)) {
+            (VdfToken::Str(key), Some(VdfToken::Str(val))) => {
+                pairs.push((depth, key.clone(), val.clone()));
+                i += 2;
+                continue;
             }
+            (VdfToken::Open, _) => depth += 1,
+            (VdfToken::Close, _) => depth = depth.saturating_sub(1),
+            _ => {}
         }
+        i += 1;
     }
-    results
+    pairs
 }
 
-// Minimal Windows-only heuristic: default Program Files (x86) Steam, parse libraryfolders.vdf quickly.
-#[cfg(windows)]
-pub fn detect_gmod_install_folder() -> Option<PathBuf> {
-    let mut candidates = Vec::new();
-    // Default Steam path
-    if let Some(pf86) = option_env!("ProgramFiles(x86)").map(PathBuf::from) {
-        let def = pf86.join("Steam");
-        if def.exists() { candidates.push(def); }
-    }
-    // Fallback: C:\Program Files (x86)\Steam
-    candidates.push(PathBuf::from("C:/Program Files (x86)/Steam"));
-
-    for steam_root in candidates {
-        let common = steam_root.join("steamapps").join("common");
-        let gmod = common.join("GarrysMod");
-        if gmod.exists() { return Some(gmod); }
-        // Parse libraryfolders.vdf for additional libraries
-        let vdf = steam_root.join("steamapps").join("libraryfolders.vdf");
-        if let Ok(text) = fs::read_to_string(&vdf) {
-            for lib_root in parse_libraryfolders_vdf_paths(&text) {
-                let gmod = lib_root.join("steamapps").join("common").join("GarrysMod");
-                if gmod.exists() { return Some(gmod); }
-            }
-        }
+/// Parse Steam library folders from the contents of a libraryfolders.vdf file.
+///
+/// Supports both old and new VDF layouts:
+/// - Old: "1" "D:\\SteamLibrary"
+/// - New: nested blocks with a "path" entry.
+fn parse_libraryfolders_vdf_paths(text: &str) -> Vec<PathBuf> {
+    let mut results: Vec<PathBuf> = Vec::new();
+    for (depth, key, val) in vdf_pairs(text) {
+        // New format: "path" "<path>"; old format: "<digits>" "<path>" directly in the root block
+        // (deeper numeric pairs are the new format's "apps" sizes)
+        let old_format = depth == 1 && !key.is_empty() && key.chars().all(|c| c.is_ascii_digit());
+        if !(key.eq_ignore_ascii_case("path") || old_format) { continue; }
+        #[cfg(windows)]
+        let val = val.replace('/', "\\");
+        let p = PathBuf::from(val);
+        if !results.contains(&p) { results.push(p); }
     }
-    None
+    results
 }
 
+/// Steam install roots to look in, in order of preference. On Linux this is the one list of
+/// native, classic (~/.steam), Flatpak and Snap locations that every Steam lookup uses.
 #[cfg(windows)]
-pub fn detect_install_folder_path(install_folder: &str) -> Option<PathBuf> {
-    let mut candidates = Vec::new();
+pub(crate) fn steam_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
     if let Ok(pf86) = std::env::var("ProgramFiles(x86)") {
-        let def = PathBuf::from(pf86).join("Steam");
-        candidates.push(def);
-    }
-    candidates.push(PathBuf::from("C:/Program Files (x86)/Steam"));
-
-    for steam_root in candidates {
-        let common = steam_root.join("steamapps").join("common");
-        let path = common.join(install_folder);
-        if path.exists() { return Some(path); }
-        let vdf = steam_root.join("steamapps").join("libraryfolders.vdf");
-        if let Ok(text) = fs::read_to_string(&vdf) {
-            for lib_root in parse_libraryfolders_vdf_paths(&text) {
-                let path = lib_root.join("steamapps").join("common").join(install_folder);
-                if path.exists() { return Some(path); }
-            }
-        }
+        roots.push(PathBuf::from(pf86).join("Steam"));
     }
-    None
+    // Fallback: C:\Program Files (x86)\Steam
+    roots.push(PathBuf::from("C:/Program Files (x86)/Steam"));
+    roots
 }
 
-
 #[cfg(unix)]
-fn locate_in_steam_libraries(name: &str) -> Option<PathBuf> {
+pub(crate) fn steam_roots() -> Vec<PathBuf> {
     let mut roots: Vec<PathBuf> = Vec::new();
     if let Ok(home) = std::env::var("HOME") {
         let home = PathBuf::from(home);
         roots.push(home.join(".local/share/Steam"));
         roots.push(home.join(".steam/steam"));
+        roots.push(home.join(".steam/root"));
         roots.push(home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"));
+        roots.push(home.join("snap/steam/common/.local/share/Steam"));
     }
     // Common system path on some distros
     roots.push(PathBuf::from("/usr/lib/steam"));
+    roots
+}
 
-    for root in roots {
-        let candidate = root.join("steamapps").join("common").join(name);
-        if candidate.exists() { return Some(candidate); }
+/// Every Steam library: each root followed by the libraries in its libraryfolders.vdf.
+/// Roots reached through several paths (e.g. ~/.steam/steam) are listed once.
+fn steam_libraries() -> Vec<PathBuf> {
+    let mut libs: Vec<PathBuf> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut add = |p: PathBuf, libs: &mut Vec<PathBuf>| {
+        if !p.exists() { return; }
+        if seen.insert(fs::canonicalize(&p).unwrap_or_else(|_| p.clone())) { libs.push(p); }
+    };
+    for root in steam_roots() {
+        add(root.clone(), &mut libs);
         let vdf = root.join("steamapps").join("libraryfolders.vdf");
         if let Ok(text) = fs::read_to_string(&vdf) {
-            for lib_root in parse_libraryfolders_vdf_paths(&text) {
-                let lib_path = lib_root.join("steamapps").join("common").join(name);
-                if lib_path.exists() { return Some(lib_path); }
-            }
+            for lib_root in parse_libraryfolders_vdf_paths(&text) { add(lib_root, &mut libs); }
         }
     }
-    None
+    libs
+}
+
+// `steamapps/common/<name>` in every library where it exists
+fn find_in_steam_libraries(name: &str) -> Vec<PathBuf> {
+    steam_libraries().into_iter()
+        .map(|lib| lib.join("steamapps").join("common").join(name))
+        .filter(|p| p.exists())
+        .collect()
+}
+
+const GMOD_APP_ID: u32 = 4000;
+
+/// The `"installdir"` value of an appmanifest_<id>.acf.
+fn parse_appmanifest_installdir(text: &str) -> Option<String> {
+    vdf_pairs(text).into_iter().find(|(_, k, _)| k.eq_ignore_ascii_case("installdir")).map(|(_, _, v)| v)
+}
+
+// Install folders Steam recorded for `app_id`, which hold even when the folder was renamed
+fn find_app_in_steam_libraries(app_id: u32) -> Vec<PathBuf> {
+    steam_libraries().into_iter()
+        .filter_map(|lib| {
+            let steamapps = lib.join("steamapps");
+            let text = fs::read_to_string(steamapps.join(format!("appmanifest_{}.acf", app_id))).ok()?;
+            let dir = steamapps.join("common").join(parse_appmanifest_installdir(&text)?);
+            dir.exists().then_some(dir)
+        })
+        .collect()
 }
 
-#[cfg(unix)]
 pub fn detect_gmod_install_folder() -> Option<PathBuf> {
-    locate_in_steam_libraries("GarrysMod")
+    detect_all_gmod_installs().into_iter().next()
+}
+
+/// Every Garry's Mod install across Steam libraries (e.g. a stable and a beta branch copy).
+/// Installs found through Steam's app manifests come first, then `GarrysMod` folders.
+pub fn detect_all_gmod_installs() -> Vec<PathBuf> {
+    let mut installs = find_app_in_steam_libraries(GMOD_APP_ID);
+    for p in find_in_steam_libraries("GarrysMod") {
+        if !installs.contains(&p) { installs.push(p); }
+    }
+    installs
 }
 
-#[cfg(unix)]
 pub fn detect_install_folder_path(install_folder: &str) -> Option<PathBuf> {
-    locate_in_steam_libraries(install_folder)
+    find_in_steam_libraries(install_folder).into_iter().next()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::parse_libraryfolders_vdf_paths;
+    use super::{parse_appmanifest_installdir, parse_libraryfolders_vdf_paths};
     use std::path::PathBuf;
 
+    #[test]
+    fn appmanifest_installdir() {
+        let acf = "\"AppState\"\n{\n\t\"appid\"\t\t\"4000\"\n\t\"name\"\t\t\"Garry's Mod\"\n\t\"installdir\"\t\t\"GarrysMod Beta\"\n}\n";
+        assert_eq!(parse_appmanifest_installdir(acf).as_deref(), Some("GarrysMod Beta"));
+        assert_eq!(parse_appmanifest_installdir("\"AppState\" { }"), None);
+    }
+
     #[cfg(windows)]
     #[test]
     fn parse_vdf_paths_windows_mixed_formats() {
@@ -224,6 +218,13 @@ mod tests {
         assert!(libs.contains(&PathBuf::from("E:\\Games\\SteamLibrary")));
     }
 
+    #[cfg(windows)]
+    #[test]
+    fn parse_vdf_paths_windows_escaped_quote() {
+        let vdf = "\"libraryfolders\"\n{\n\t\"0\"\n\t{\n\t\t\"path\"\t\t\"F:\\\\My \\\"Games\\\"\\\\Steam\"\n\t}\n}\n";
+        assert_eq!(parse_libraryfolders_vdf_paths(vdf), [PathBuf::from("F:\\My \"Games\"\\Steam")]);
+    }
+
     #[cfg(unix)]
     #[test]
     fn parse_vdf_paths_unix_mixed_formats() {
@@ -244,5 +245,13 @@ mod tests {
         assert!(libs.contains(&PathBuf::from("/mnt/ssd/SteamLibrary")));
         assert!(libs.contains(&PathBuf::from("/home/user/.steam/steamapps/compat/SteamLibrary")));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_vdf_paths_unix_escaped_quote() {
+        // Tab-indented, with the value holding an escaped quote and the key not first on its line
+        let vdf = "\"libraryfolders\"\n{\n\t\"0\"\t{\t\"path\"\t\t\"/mnt/My \\\"Games\\\"/Steam\"\n\t\t\"label\"\t\t\"\"\n\t\t\"apps\" { \"4000\" \"123456\" }\n\t}\n\t\"1\"\t\"/old/Library\"\n}\n";
+        assert_eq!(parse_libraryfolders_vdf_paths(vdf), [PathBuf::from("/mnt/My \"Games\"/Steam"), PathBuf::from("/old/Library")]);
+    }
 }
 
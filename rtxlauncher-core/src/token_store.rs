@@ -0,0 +1,125 @@
+//! At-rest protection for the GitHub token file. The file holds either a tagged
+//! protected value (`dpapi:<hex>` on Windows, `keyring:` on Linux where the token lives
+//! in the Secret Service) or, when neither is available, the plaintext token tagged `plain:`.
+//! An untagged file is a raw token from an older version.
+use anyhow::Result;
+
+// Marks a token written in plaintext because protection failed, so it is not migrated again
+const PLAIN_PREFIX: &str = "plain:";
+
+#[cfg(windows)]
+mod imp {
+    use super::*;
+    use windows::Win32::{
+        Foundation::{LocalFree, HLOCAL},
+        Security::Cryptography::{CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB},
+    };
+
+    const PREFIX: &str = "dpapi:";
+
+    // Copy a DPAPI output blob and release it
+    unsafe fn take_blob(blob: CRYPT_INTEGER_BLOB) -> Vec<u8> {
+        let out = std::slice::from_raw_parts(blob.pbData, blob.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(blob.pbData as _));
+        out
+    }
+
+    pub fn protect(token: &str) -> Result<String> {
+        let mut data = token.as_bytes().to_vec();
+        let input = CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_mut_ptr() };
+        let mut output = CRYPT_INTEGER_BLOB::default();
+        unsafe {
+            CryptProtectData(&input, windows::core::w!("RTXLauncher GitHub token"), None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)?;
+            Ok(format!("{}{}", PREFIX, hex::encode(take_blob(output))))
+        }
+    }
+
+    pub fn unprotect(stored: &str) -> Option<Result<String>> {
+        let hex_data = stored.strip_prefix(PREFIX)?;
+        Some((|| -> Result<String> {
+            let mut data = hex::decode(hex_data)?;
+            let input = CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_mut_ptr() };
+            let mut output = CRYPT_INTEGER_BLOB::default();
+            let plain = unsafe {
+                CryptUnprotectData(&input, None, None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)?;
+                take_blob(output)
+            };
+            Ok(String::from_utf8(plain)?)
+        })())
+    }
+
+    pub fn clear() {}
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::*;
+
+    const MARKER: &str = "keyring:";
+
+    fn entry() -> keyring::Result<keyring::Entry> {
+        keyring::Entry::new("rtxlauncher", "github_token")
+    }
+
+    pub fn protect(token: &str) -> Result<String> {
+        entry()?.set_password(token)?;
+        Ok(MARKER.to_string())
+    }
+
+    pub fn unprotect(stored: &str) -> Option<Result<String>> {
+        if stored != MARKER { return None; }
+        Some(entry().and_then(|e| e.get_password()).map_err(Into::into))
+    }
+
+    pub fn clear() {
+        if let Ok(e) = entry() { let _ = e.delete_credential(); }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+mod imp {
+    use super::*;
+
+    pub fn protect(_token: &str) -> Result<String> {
+        Err(anyhow::anyhow!("no secure storage on this platform"))
+    }
+
+    pub fn unprotect(_stored: &str) -> Option<Result<String>> { None }
+
+    pub fn clear() {}
+}
+
+/// File contents for `token`: protected when possible, tagged plaintext (with a warning) otherwise.
+pub(crate) fn protect(token: &str) -> String {
+    match imp::protect(token) {
+        Ok(stored) => stored,
+        Err(e) => {
+            tracing::warn!("Could not protect the GitHub token ({}); storing it in plaintext", e);
+            format!("{}{}", PLAIN_PREFIX, token)
+        }
+    }
+}
+
+/// Token from file contents, and whether it was stored untagged by an older version (and
+/// should be migrated).
+pub(crate) fn unprotect(stored: &str) -> Result<(String, bool)> {
+    if let Some(token) = stored.strip_prefix(PLAIN_PREFIX) { return Ok((token.to_string(), false)); }
+    match imp::unprotect(stored) {
+        Some(token) => Ok((token?, false)),
+        None => Ok((stored.to_string(), true)),
+    }
+}
+
+/// Drop any copy held outside the token file.
+pub(crate) fn clear() { imp::clear() }
+
+#[cfg(test)]
+mod tests {
+    use super::unprotect;
+
+    #[test]
+    fn only_untagged_tokens_are_migrated() {
+        assert_eq!(unprotect("plain:ghp_abc").unwrap(), ("ghp_abc".to_string(), false));
+        assert_eq!(unprotect("ghp_abc").unwrap(), ("ghp_abc".to_string(), true));
+    }
+}
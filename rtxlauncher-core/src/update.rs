@@ -1,6 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use crate::fs_linker::{copy_file, link_dir_best_effort, link_file_best_effort, LinkStrategy};
+use crate::error::LauncherResult;
+use crate::jobs::CancellationToken;
+use crate::github::{fetch_releases, GitHubRateLimit, GitHubRelease};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 #[derive(Debug, Clone)]
 pub struct FileUpdateInfo {
@@ -12,14 +17,72 @@ pub struct FileUpdateInfo {
     pub is_changed: bool,
 }
 
+/// How `scan_updates` decides that an existing destination file differs from the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateCompare {
+    /// Size or modification time differ. Fast, but copies made without preserving mtime all look changed.
+    #[default]
+    SizeAndTime,
+    /// Size differs or a content fingerprint differs; mtime is ignored.
+    Contents,
+}
+
+// Small files are hashed whole; larger ones by their first and last FINGERPRINT_SAMPLE bytes plus length
+const FINGERPRINT_SAMPLE: u64 = 64 * 1024;
+
+fn fingerprint(path: &Path, len: u64) -> std::io::Result<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+    use std::io::{Read, Seek, SeekFrom};
+    let mut f = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(len.to_le_bytes());
+    if len <= FINGERPRINT_SAMPLE * 2 {
+        let mut all = Vec::with_capacity(len as usize);
+        f.read_to_end(&mut all)?;
+        hasher.update(&all);
+    } else {
+        let mut buf = vec![0u8; FINGERPRINT_SAMPLE as usize];
+        f.read_exact(&mut buf)?;
+        hasher.update(&buf);
+        f.seek(SeekFrom::End(-(FINGERPRINT_SAMPLE as i64)))?;
+        f.read_exact(&mut buf)?;
+        hasher.update(&buf);
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// A destination that already links back into the vanilla install, so `apply_updates` leaves it alone.
+#[derive(Debug, Clone)]
+pub struct LinkedEntry {
+    pub relative_path: String,
+    /// Files under the link (1 for a linked file).
+    pub files: usize,
+}
+
+/// What `scan_updates` found: entries for `apply_updates`, plus the linked destinations it skipped.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateScan {
+    pub updates: Vec<FileUpdateInfo>,
+    pub linked: Vec<LinkedEntry>,
+}
+
 fn is_symlink(path: &Path) -> bool {
     fs::symlink_metadata(path)
         .map(|m| m.file_type().is_symlink())
         .unwrap_or(false)
 }
 
-pub fn detect_updates(source_dir: &Path, dest_dir: &Path) -> Result<Vec<FileUpdateInfo>> {
-    let mut result = Vec::new();
+// A link whose target no longer exists (e.g. the vanilla game moved to another drive)
+fn is_broken_link(path: &Path) -> bool {
+    is_symlink(path) && fs::metadata(path).is_err()
+}
+
+pub fn detect_updates(source_dir: &Path, dest_dir: &Path, compare: UpdateCompare) -> LauncherResult<Vec<FileUpdateInfo>> {
+    Ok(scan_updates(source_dir, dest_dir, compare)?.updates)
+}
+
+pub fn scan_updates(source_dir: &Path, dest_dir: &Path, compare: UpdateCompare) -> LauncherResult<UpdateScan> {
+    let mut scan = UpdateScan::default();
     let excluded_dirs = [
         "addons", "saves", "dupes", "demos", "settings", "cache",
         "materials", "models", "maps", "screenshots", "videos", "download",
@@ -30,7 +93,8 @@ pub fn detect_updates(source_dir: &Path, dest_dir: &Path) -> Result<Vec<FileUpda
         source_root: &Path,
         dest_root: &Path,
         rel: &Path,
-        result: &mut Vec<FileUpdateInfo>,
+        scan: &mut UpdateScan,
+        compare: UpdateCompare,
         excluded_dirs: &[&str],
         excluded_ext: &[&str],
     ) -> Result<()> {
@@ -47,35 +111,50 @@ pub fn detect_updates(source_dir: &Path, dest_dir: &Path) -> Result<Vec<FileUpda
                 if rel.as_os_str().is_empty() && ["crashes","logs","temp","update","xenmod"].contains(&name_str.as_str()) {
                     continue;
                 }
+                // Linked content folders are excluded from copying, but a dangling link still needs repair
+                if is_broken_link(&dest_path) {
+                    scan.updates.push(FileUpdateInfo { relative_path: relative(&rel_child), source_path: p.clone(), destination_path: dest_path.clone(), is_directory: true, is_new: false, is_changed: true });
+                    continue;
+                }
                 if excluded_dirs.iter().any(|d| d.eq_ignore_ascii_case(&name_str)) { continue; }
+                // A linked folder already shows the vanilla files; walking it would only find itself
+                if is_symlink(&dest_path) {
+                    let files = walkdir::WalkDir::new(&p).into_iter().flatten().filter(|e| e.file_type().is_file()).count();
+                    scan.linked.push(LinkedEntry { relative_path: relative(&rel_child), files });
+                    continue;
+                }
                 if !dest_path.exists() {
-                    result.push(FileUpdateInfo { relative_path: rel_child.to_string_lossy().to_string(), source_path: p.clone(), destination_path: dest_path.clone(), is_directory: true, is_new: true, is_changed: false });
+                    scan.updates.push(FileUpdateInfo { relative_path: relative(&rel_child), source_path: p.clone(), destination_path: dest_path.clone(), is_directory: true, is_new: true, is_changed: false });
                 }
-                walk(source_root, dest_root, &rel_child, result, excluded_dirs, excluded_ext)?;
+                walk(source_root, dest_root, &rel_child, scan, compare, excluded_dirs, excluded_ext)?;
             } else {
                 // root-level: only allow gmod.exe/hl2.exe
-                if rel.as_os_str().is_empty() {
-                    if name_str.to_lowercase() != "gmod.exe" && name_str.to_lowercase() != "hl2.exe" && name_str.to_lowercase() != "steam_appid.txt" { continue; }
-                }
+                if rel.as_os_str().is_empty() && name_str.to_lowercase() != "gmod.exe" && name_str.to_lowercase() != "hl2.exe" && name_str.to_lowercase() != "steam_appid.txt" { continue; }
                 if let Some(ext) = p.extension().and_then(|e| e.to_str()) {
                     if excluded_ext.iter().any(|x| x.trim_start_matches('.').eq_ignore_ascii_case(ext)) { continue; }
                 }
-                let is_new = !dest_path.exists();
-                let is_changed = if is_new { false } else {
-                    if is_symlink(&dest_path) { false } else {
-                        let src_meta = fs::metadata(&p)?;
-                        let dst_meta = fs::metadata(&dest_path)?;
-                        let size_diff = src_meta.len() != dst_meta.len();
-                        let time_diff = match (src_meta.modified().ok(), dst_meta.modified().ok()) {
+                let broken = is_broken_link(&dest_path);
+                if !broken && is_symlink(&dest_path) {
+                    scan.linked.push(LinkedEntry { relative_path: relative(&rel_child), files: 1 });
+                    continue;
+                }
+                let is_new = !broken && !dest_path.exists();
+                let is_changed = if is_new { false } else if broken { true } else {
+                    let src_meta = fs::metadata(&p)?;
+                    let dst_meta = fs::metadata(&dest_path)?;
+                    let size_diff = src_meta.len() != dst_meta.len();
+                    match compare {
+                        _ if size_diff => true,
+                        UpdateCompare::SizeAndTime => match (src_meta.modified().ok(), dst_meta.modified().ok()) {
                             (Some(a), Some(b)) => a != b,
                             _ => false,
-                        };
-                        size_diff || time_diff
+                        },
+                        UpdateCompare::Contents => fingerprint(&p, src_meta.len())? != fingerprint(&dest_path, dst_meta.len())?,
                     }
                 };
                 if is_new || is_changed {
-                    result.push(FileUpdateInfo {
-                        relative_path: rel_child.to_string_lossy().to_string(),
+                    scan.updates.push(FileUpdateInfo {
+                        relative_path: relative(&rel_child),
                         source_path: p.clone(),
                         destination_path: dest_path.clone(),
                         is_directory: false,
@@ -88,25 +167,231 @@ pub fn detect_updates(source_dir: &Path, dest_dir: &Path) -> Result<Vec<FileUpda
         Ok(())
     }
 
-    walk(source_dir, dest_dir, Path::new(""), &mut result, &excluded_dirs, &excluded_ext)?;
-    Ok(result)
+    walk(source_dir, dest_dir, Path::new(""), &mut scan, compare, &excluded_dirs, &excluded_ext)?;
+    Ok(scan)
 }
 
-pub fn apply_updates(updates: &[FileUpdateInfo], mut progress: impl FnMut(&str, u8)) -> Result<()> {
+// Relative paths always use '/' so folder filters work the same on every platform
+fn relative(rel: &Path) -> String {
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+/// Whether an update entry falls under one of the chosen top-level `folders`. The root
+/// executables and steam_appid.txt go with "bin".
+pub fn update_selected(relative_path: &str, folders: &[String]) -> bool {
+    let top = relative_path.split('/').next().unwrap_or("");
+    if top == relative_path && ["gmod.exe", "hl2.exe", "steam_appid.txt"].iter().any(|f| f.eq_ignore_ascii_case(top)) {
+        return folders.iter().any(|f| f == "bin");
+    }
+    folders.iter().any(|f| f == top)
+}
+
+const MAX_PARALLEL_COPIES: usize = 8;
+
+/// Relinks and directories are handled first, in order; file copies then run on a small
+/// worker pool. Progress counts finished items, so it only moves forward. Broken links are
+/// relinked with `strategy`; on cancel, copies already finished are kept.
+pub fn apply_updates(updates: &[FileUpdateInfo], strategy: LinkStrategy, cancel: &CancellationToken, mut progress: impl FnMut(&str, u8)) -> LauncherResult<()> {
     let total = updates.len().max(1);
-    for (i, u) in updates.iter().enumerate() {
-        let pct = ((i as f32 / total as f32) * 100.0) as u8;
-        if u.is_directory {
-            progress(&format!("Creating directory: {}", u.relative_path), pct);
+    let pct = |done: usize| ((done as f32 / total as f32) * 100.0) as u8;
+    let mut done = 0usize;
+    let mut copies = Vec::new();
+    for u in updates {
+        cancel.check()?;
+        if is_broken_link(&u.destination_path) {
+            progress(&format!("Relinking: {}", u.relative_path), pct(done));
+            fs::remove_file(&u.destination_path)
+                .or_else(|_| fs::remove_dir(&u.destination_path))
+                .with_context(|| format!("remove broken link {}", u.destination_path.display()))?;
+            if u.is_directory { link_dir_best_effort(&u.source_path, &u.destination_path, strategy, false)?; } else { link_file_best_effort(&u.source_path, &u.destination_path, strategy, false)?; }
+        } else if u.is_directory {
+            progress(&format!("Creating directory: {}", u.relative_path), pct(done));
             fs::create_dir_all(&u.destination_path)?;
         } else {
-            progress(&format!("Copying file: {}", u.relative_path), pct);
-            if let Some(parent) = u.destination_path.parent() { fs::create_dir_all(parent)?; }
-            fs::copy(&u.source_path, &u.destination_path)?;
+            copies.push(u);
+            continue;
         }
+        done += 1;
     }
+    if copies.is_empty() { progress("Update complete", 100); return Ok(()); }
+
+    let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1).min(MAX_PARALLEL_COPIES).min(copies.len());
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let mut first_error = None;
+    std::thread::scope(|s| {
+        let (tx, rx) = std::sync::mpsc::channel::<(usize, Result<()>)>();
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, failed, copies) = (&next, &failed, &copies);
+            s.spawn(move || loop {
+                if failed.load(Ordering::Relaxed) || cancel.is_cancelled() { break; }
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(u) = copies.get(i) else { break };
+                let _ = tx.send((i, copy_update(u)));
+            });
+        }
+        drop(tx);
+        for (i, result) in rx {
+            match result {
+                Ok(()) => {
+                    done += 1;
+                    progress(&format!("Copying file: {}", copies[i].relative_path), pct(done));
+                }
+                Err(e) => {
+                    // Let the workers drain; the first failure is the one reported
+                    failed.store(true, Ordering::Relaxed);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+    });
+    if let Some(e) = first_error { return Err(e.into()); }
+    cancel.check()?;
     progress("Update complete", 100);
     Ok(())
 }
 
+// create_dir_all tolerates other workers creating the same parents concurrently
+fn copy_update(u: &FileUpdateInfo) -> Result<()> {
+    if let Some(parent) = u.destination_path.parent() { fs::create_dir_all(parent)?; }
+    copy_file(&u.source_path, &u.destination_path).with_context(|| format!("copy {}", u.relative_path))?;
+    Ok(())
+}
+
+/// Returns the latest stable launcher release when it is newer than the running build.
+/// Releases are compared by version number against `current_version`; a tag that mentions
+/// `current_commit` is treated as the running build.
+pub async fn check_launcher_update(current_version: &str, current_commit: Option<&str>) -> LauncherResult<Option<GitHubRelease>> {
+    let mut rl = GitHubRateLimit::default();
+    let releases = fetch_releases("sambow23", "RTXLauncher", &mut rl).await?;
+    let Some(latest) = releases.into_iter().find(|r| !r.prerelease.unwrap_or(false)) else { return Ok(None) };
+    let tag = latest.tag_name.clone().unwrap_or_default();
+    if let Some(commit) = current_commit.filter(|c| c.len() >= 7) {
+        if tag.contains(&commit[..7]) { return Ok(None); }
+    }
+    match (parse_version(&tag), parse_version(current_version)) {
+        (Some(latest_v), Some(current_v)) if latest_v > current_v => Ok(Some(latest)),
+        _ => Ok(None),
+    }
+}
+
+// "v1.2.3" / "1.2" -> [1, 2, 3] / [1, 2, 0]; anything after a '-' or '+' is ignored
+fn parse_version(s: &str) -> Option<[u64; 3]> {
+    let core = s.trim().trim_start_matches(['v', 'V']).split(['-', '+']).next()?;
+    let mut out = [0u64; 3];
+    for (i, part) in core.split('.').enumerate() {
+        if i >= 3 { return None; }
+        out[i] = part.parse().ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn linked_folders_are_skipped_not_copied() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-update-test-{}", std::process::id()));
+        let (src, dst) = (root.join("vanilla"), root.join("rtx"));
+        fs::create_dir_all(src.join("platform/sub")).unwrap();
+        fs::create_dir_all(src.join("bin")).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(src.join("platform/a.txt"), "a").unwrap();
+        fs::write(src.join("platform/sub/b.txt"), "b").unwrap();
+        fs::write(src.join("bin/engine.dll"), "e").unwrap();
+        fs::write(src.join("gmod.exe"), "g").unwrap();
+        std::os::unix::fs::symlink(src.join("platform"), dst.join("platform")).unwrap();
 
+        let scan = scan_updates(&src, &dst, UpdateCompare::SizeAndTime).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(scan.linked.len(), 1);
+        assert_eq!((scan.linked[0].relative_path.as_str(), scan.linked[0].files), ("platform", 2));
+        assert!(scan.updates.iter().all(|u| !u.relative_path.starts_with("platform")));
+
+        let folders = vec!["bin".to_string()];
+        let mut picked: Vec<_> = scan.updates.iter().filter(|u| update_selected(&u.relative_path, &folders)).map(|u| u.relative_path.as_str()).collect();
+        picked.sort();
+        assert_eq!(picked, ["bin", "bin/engine.dll", "gmod.exe"]);
+    }
+
+    #[test]
+    fn content_compare_ignores_mtime() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-compare-test-{}", std::process::id()));
+        let (src, dst) = (root.join("vanilla"), root.join("rtx"));
+        fs::create_dir_all(src.join("bin")).unwrap();
+        fs::create_dir_all(dst.join("bin")).unwrap();
+        let big: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+        fs::write(src.join("bin/same.dll"), &big).unwrap();
+        fs::write(dst.join("bin/same.dll"), &big).unwrap();
+        fs::write(src.join("bin/edited.txt"), "abc").unwrap();
+        fs::write(dst.join("bin/edited.txt"), "abd").unwrap();
+        let old = filetime::FileTime::from_unix_time(1_000_000, 0);
+        filetime::set_file_mtime(dst.join("bin/same.dll"), old).unwrap();
+        filetime::set_file_mtime(dst.join("bin/edited.txt"), filetime::FileTime::from_last_modification_time(&fs::metadata(src.join("bin/edited.txt")).unwrap())).unwrap();
+
+        let changed = |compare| {
+            let mut v: Vec<_> = detect_updates(&src, &dst, compare).unwrap().into_iter().map(|u| u.relative_path).collect();
+            v.sort();
+            v
+        };
+        let by_time = changed(UpdateCompare::SizeAndTime);
+        let by_contents = changed(UpdateCompare::Contents);
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(by_time, ["bin/same.dll"]);
+        assert_eq!(by_contents, ["bin/edited.txt"]);
+    }
+
+    #[test]
+    fn apply_copies_nested_files_with_forward_progress() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-apply-test-{}", std::process::id()));
+        let (src, dst) = (root.join("vanilla"), root.join("rtx"));
+        for i in 0..40 {
+            let dir = src.join(format!("garrysmod/lua/d{}", i % 5));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(format!("f{}.lua", i)), i.to_string()).unwrap();
+        }
+        fs::create_dir_all(&dst).unwrap();
+
+        let updates = detect_updates(&src, &dst, UpdateCompare::Contents).unwrap();
+        let mut seen = Vec::new();
+        apply_updates(&updates, LinkStrategy::Auto, &CancellationToken::new(), |_, p| seen.push(p)).unwrap();
+        let left = detect_updates(&src, &dst, UpdateCompare::Contents).unwrap();
+        let by_time_after = detect_updates(&src, &dst, UpdateCompare::SizeAndTime).unwrap();
+        let copied = fs::read_to_string(dst.join("garrysmod/lua/d2/f17.lua")).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        assert!(seen.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(seen.last(), Some(&100));
+        assert!(left.is_empty());
+        assert_eq!(copied, "17");
+        // Copies carry the source mtime, so the cheap comparison agrees too
+        assert!(by_time_after.is_empty());
+    }
+
+    #[test]
+    fn nested_new_directories_use_forward_slashes() {
+        let root = std::env::temp_dir().join(format!("rtxlauncher-newdir-test-{}", std::process::id()));
+        let (src, dst) = (root.join("vanilla"), root.join("rtx"));
+        fs::create_dir_all(src.join("garrysmod/lua/autorun")).unwrap();
+        fs::write(src.join("garrysmod/lua/autorun/init.lua"), "x").unwrap();
+        fs::create_dir_all(&dst).unwrap();
+
+        let scan = scan_updates(&src, &dst, UpdateCompare::SizeAndTime).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        let mut dirs: Vec<_> = scan.updates.iter().filter(|u| u.is_directory).map(|u| u.relative_path.as_str()).collect();
+        dirs.sort();
+        assert_eq!(dirs, ["garrysmod", "garrysmod/lua", "garrysmod/lua/autorun"]);
+        let folders = vec!["garrysmod".to_string()];
+        assert!(scan.updates.iter().all(|u| update_selected(&u.relative_path, &folders)));
+    }
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert_eq!(parse_version("v1.2.3"), Some([1, 2, 3]));
+        assert_eq!(parse_version("0.10-beta"), Some([0, 10, 0]));
+        assert!(parse_version("v0.10.0") > parse_version("0.9.9"));
+        assert_eq!(parse_version("nightly"), None);
+    }
+}
@@ -1,33 +1,42 @@
 use anyhow::Result;
 use reqwest::Client;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 use std::io::Cursor;
 use futures_util::StreamExt;
 use std::time::Duration;
 use tracing::info;
 use crate::logging::ProgressThrottle;
+use crate::download::{is_transient, retry_delay, RateMeter, DOWNLOAD_ATTEMPTS};
 
-pub async fn apply_usda_fixes(game_install_path: &Path, remix_mod_folder: &str, mut progress: impl FnMut(&str, u8)) -> Result<bool> {
-	if remix_mod_folder != "hl2rtx" { return Ok(true); }
-	let url = "https://github.com/sambow23/rtx-usda-fixes/archive/refs/heads/main.zip";
-	progress("Downloading USDA fixes", 10);
+/// Remix mod folder -> archive of its USDA fixes repo/branch.
+pub const USDA_FIXES_SOURCES: &[(&str, &str)] = &[
+	("hl2rtx", "https://github.com/sambow23/rtx-usda-fixes/archive/refs/heads/main.zip"),
+	("portalrtx", "https://github.com/sambow23/rtx-usda-fixes/archive/refs/heads/portalrtx.zip"),
+];
 
-	info!("USDA download start: {}", url);
-	let client = match Client::builder().timeout(Duration::from_secs(300)).build() {
-		Ok(c) => c,
-		Err(e) => { progress(&format!("USDA error: {}", e), 100); info!("USDA client error: {}", e); return Ok(false); }
-	};
-	let resp = match client.get(url).header("User-Agent", "RTXLauncher-RS").send().await {
-		Ok(r) => r,
-		Err(e) => { progress(&format!("USDA error: {}", e), 100); info!("USDA request error: {}", e); return Ok(false); }
-	};
-	let status = resp.status();
-	if !status.is_success() {
-		progress(&format!("HTTP error: {}", status), 100);
-		info!("USDA HTTP error: {}", status);
-		return Ok(false);
-	}
+// Fixes branches move rarely; re-download at most this often
+const USDA_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Cached archive for a fixes URL, named after its repo and branch
+fn usda_cache_path(url: &str) -> Option<PathBuf> {
+	let dirs = directories::ProjectDirs::from("com", "rtxlauncher", "rtxlauncher")?;
+	let dir = dirs.cache_dir().join("usda");
+	std::fs::create_dir_all(&dir).ok();
+	let key: String = url.trim_start_matches("https://").trim_end_matches(".zip")
+		.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect();
+	Some(dir.join(format!("{}.zip", key)))
+}
+
+fn read_fresh_cache(path: &Path) -> Option<Vec<u8>> {
+	let age = std::fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+	if age >= USDA_CACHE_TTL { return None; }
+	std::fs::read(path).ok().filter(|b| !b.is_empty())
+}
+
+// One download attempt of the fixes archive into memory
+async fn download_zip(client: &Client, url: &str, progress: &mut impl FnMut(&str, u8)) -> Result<Vec<u8>> {
+	let resp = client.get(url).header("User-Agent", "RTXLauncher-RS").send().await?.error_for_status()?;
 	let total = resp.content_length().unwrap_or(0);
 	info!("USDA content_length: {} bytes", total);
 	let mut stream = resp.bytes_stream();
@@ -35,33 +44,68 @@ pub async fn apply_usda_fixes(game_install_path: &Path, remix_mod_folder: &str,
 	let mut downloaded: u64 = 0;
 	let mut chunks = 0u64;
 	let mut throttler = ProgressThrottle::new(150);
+	let mut rate = RateMeter::new(150);
 	while let Some(chunk_res) = stream.next().await {
-		let chunk = match chunk_res { Ok(c) => c, Err(e) => { progress(&format!("USDA stream error: {}", e), 100); info!("USDA stream error: {}", e); return Ok(false); } };
+		let chunk = chunk_res?;
 		downloaded += chunk.len() as u64;
 		buf.extend_from_slice(&chunk);
 		chunks += 1;
+		rate.update(downloaded);
 		if total > 0 {
 			let pct = 10 + ((downloaded as f32 / total as f32) * 60.0) as u8;
-			let msg = format!("Downloading: {}/{} MB", downloaded/1_048_576, total/1_048_576);
+			let msg = format!("Downloading: {}/{} MB{}", downloaded/1_048_576, total/1_048_576, rate.suffix(downloaded, total));
 			throttler.emit("Downloading:", msg, pct.min(70), |m,p| progress(m,p));
 		}
-		if chunks % 32 == 0 { info!("USDA downloaded {} bytes ({} chunks)", downloaded, chunks); }
+		if chunks.is_multiple_of(32) { info!("USDA downloaded {} bytes ({} chunks)", downloaded, chunks); }
 	}
 	info!("USDA download complete: {} bytes ({} chunks)", downloaded, chunks);
+	Ok(buf)
+}
 
-	// Write to temp for debugging
-	if let Ok(tmpdir) = std::env::temp_dir().canonicalize() {
-		let tmpzip = tmpdir.join("rtx_usda_fixes.zip");
-		if let Ok(mut f) = std::fs::File::create(&tmpzip) {
-			let _ = std::io::Write::write_all(&mut f, &buf);
-			info!("USDA zip saved to {} ({} bytes)", tmpzip.display(), buf.len());
+pub async fn apply_usda_fixes(game_install_path: &Path, remix_mod_folder: &str, mut progress: impl FnMut(&str, u8)) -> Result<bool> {
+	let Some(&(_, url)) = USDA_FIXES_SOURCES.iter().find(|(m, _)| *m == remix_mod_folder) else { return Ok(true); };
+	let cache_path = usda_cache_path(url);
+	let buf = if let Some(buf) = cache_path.as_deref().and_then(read_fresh_cache) {
+		info!("USDA cache hit: {} ({} bytes)", cache_path.as_deref().unwrap_or(Path::new("")).display(), buf.len());
+		progress("Using cached USDA fixes", 70);
+		buf
+	} else {
+		progress("Downloading USDA fixes", 10);
+		info!("USDA download start: {}", url);
+		let client = match Client::builder().timeout(Duration::from_secs(300)).build() {
+			Ok(c) => c,
+			Err(e) => { progress(&format!("USDA error: {}", e), 100); info!("USDA client error: {}", e); return Ok(false); }
+		};
+		let mut attempt = 1;
+		let buf = loop {
+			match download_zip(&client, url, &mut progress).await {
+				Ok(buf) => break buf,
+				Err(e) if attempt < DOWNLOAD_ATTEMPTS && is_transient(&e) => {
+					attempt += 1;
+					info!("USDA download error: {:#}; retrying ({}/{})", e, attempt, DOWNLOAD_ATTEMPTS);
+					progress(&format!("USDA download interrupted ({}), retrying ({}/{})...", e, attempt, DOWNLOAD_ATTEMPTS), 10);
+					tokio::time::sleep(retry_delay(attempt)).await;
+				}
+				Err(e) => { progress(&format!("USDA error: {}", e), 100); info!("USDA download error: {:#}", e); return Ok(false); }
+			}
+		};
+		if let Some(p) = &cache_path {
+			match std::fs::write(p, &buf) {
+				Ok(()) => info!("USDA zip cached at {} ({} bytes)", p.display(), buf.len()),
+				Err(e) => info!("USDA cache write error: {}", e),
+			}
 		}
-	}
+		buf
+	};
 
 	// Build two independent archives from the same buffer so counting doesn't affect extraction
 	let mut zip_count = match ZipArchive::new(Cursor::new(buf.clone())) {
 		Ok(z) => z,
-		Err(e) => { progress(&format!("USDA zip open error: {}", e), 100); info!("USDA zip open error: {}", e); return Ok(false); }
+		Err(e) => {
+			// Don't keep serving a bad archive from the cache
+			if let Some(p) = &cache_path { let _ = std::fs::remove_file(p); }
+			progress(&format!("USDA zip open error: {}", e), 100); info!("USDA zip open error: {}", e); return Ok(false);
+		}
 	};
 	let dest = game_install_path.join("rtx-remix").join("mods").join(remix_mod_folder);
 	if !dest.exists() {
@@ -97,8 +141,8 @@ pub async fn apply_usda_fixes(game_install_path: &Path, remix_mod_folder: &str,
 		let mut f = zip.by_index(i)?;
 		let name = f.name().to_string();
 		if name.ends_with(".usda") {
-			let base = name.rsplit('/').next().unwrap_or(&name);
-			let path = dest.join(base);
+			let base = name.rsplit(['/', '\\']).next().unwrap_or(&name);
+			let Some(path) = crate::remix_installer::sanitize_zip_path(&dest, base) else { continue };
 			if let Some(parent) = path.parent() { let _ = std::fs::create_dir_all(parent); }
 			let mut out = match std::fs::File::create(&path) { Ok(f) => f, Err(e) => { progress(&format!("USDA write error: {}", e), 100); info!("USDA write error: {}", e); return Ok(false); } };
 			if let Err(e) = std::io::copy(&mut f, &mut out) { progress(&format!("USDA copy error: {}", e), 100); info!("USDA copy error: {}", e); return Ok(false); }
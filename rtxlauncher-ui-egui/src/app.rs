@@ -1,76 +1,63 @@
 use eframe::{egui, App};
-use rtxlauncher_core::{SettingsStore, JobProgress, AppSettings, detect_gmod_install_folder, launch_game, GitHubRelease};
+use rtxlauncher_core::{SettingsStore, JobProgress, AppSettings, CancellationToken, JobLock, LauncherError, SourceLists, detect_gmod_install_folder, launch_game};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 #[cfg(windows)]
 use rtxlauncher_core::is_elevated;
 
-pub const DEFAULT_IGNORE_PATTERNS: &str = r#"
-# 32bit Bridge
-bin/.trex/*
-bin/d3d8to9.dll
-bin/d3d9.dll
-bin/LICENSE.txt
-bin/NvRemixLauncher32.exe
-bin/ThirdPartyLicenses-bridge.txt
-bin/ThirdPartyLicenses-d3d8to9.txt
-bin/ThirdPartyLicenses-dxvk.txt
-
-# Remix in 64 install
-bin/win64/usd/*
-bin/win64/artifacts_readme.txt
-bin/win64/cudart64_12.dll
-bin/win64/d3d9.dll
-bin/win64/d3d9.pdb
-bin/win64/GFSDK_Aftermath_Lib.x64.dll
-bin/win64/NRC_Vulkan.dll
-bin/win64/NRD.dll
-bin/win64/NvLowLatencyVk.dll
-bin/win64/nvngx_dlss.dll
-bin/win64/nvngx_dlssd.dll
-bin/win64/nvngx_dlssg.dll
-bin/win64/NvRemixBridge.exe
-bin/win64/nvrtc64_120_0.dll
-bin/win64/nvrtc-builtins64_125.dll
-bin/win64/rtxio.dll
-bin/win64/tbb.dll
-bin/win64/tbbmalloc.dll
-bin/win64/usd_ms.dll
-"#;
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Tab { Setup, Mount, Repositories, Settings, About, Logs }
 
 pub struct Toast { pub msg: String, pub color: egui::Color32, pub until: std::time::Instant }
 
+// One row of the base-update checklist; `bytes` is what copying it writes (0 for directories and relinks)
+pub struct UpdateEntry { pub info: rtxlauncher_core::FileUpdateInfo, pub bytes: u64, pub selected: bool }
+
+// The update dialog's background scan, with each update's copy size, index for index
+type UpdateScanResult = Result<(rtxlauncher_core::UpdateScan, Vec<u64>), String>;
+
+/// A job the error dialog can start again, recorded when the job is started.
+#[derive(Clone)]
+pub enum RetryJob {
+	QuickInstall(rtxlauncher_core::InstallPlan),
+	Repo(crate::ui::repositories::RepoRequest),
+	BaseUpdate,
+	Reapply,
+}
+
 pub struct LauncherApp {
-	pub log: String,
-	pub progress: u8,
-	pub not_elevated_warned: bool,
+	pub log: LogSink,
+	// Runtime started by #[tokio::main]; background work runs here instead of on a fresh runtime per click
+	pub runtime: JobRuntime,
+	// Base game update / reapply job started from the dialogs, and where it reports failures
 	pub current_job: Option<std::sync::mpsc::Receiver<JobProgress>>,
+	pub progress: u8,
+	failure_tx: std::sync::mpsc::Sender<LauncherError>,
+	failure_rx: std::sync::mpsc::Receiver<LauncherError>,
 	pub settings_store: SettingsStore,
 	pub settings: AppSettings,
+	// Remix / fixes / patch repositories from sources.toml
+	pub sources: rtxlauncher_core::SourceLists,
 	pub selected: Tab,
 	pub is_running: bool,
 	pub show_error_modal: Option<String>,
+	pub error_modal_hint: Option<String>,
+	// Most recently started job, and the one the open error dialog offers to retry
+	pub last_job: Option<RetryJob>,
+	pub error_retry: Option<RetryJob>,
 	pub toasts: Vec<Toast>,
-	pub remix_source_idx: usize,
-	pub remix_releases: Vec<GitHubRelease>,
-	pub remix_release_idx: usize,
-	pub remix_rx: Option<std::sync::mpsc::Receiver<Vec<GitHubRelease>>>,
-	pub remix_loading: bool,
-	pub fixes_source_idx: usize,
-	pub fixes_releases: Vec<GitHubRelease>,
-	pub fixes_release_idx: usize,
-	pub fixes_rx: Option<std::sync::mpsc::Receiver<Vec<GitHubRelease>>>,
-	pub fixes_loading: bool,
-	pub patch_source_idx: usize,
+	// Exit report of the last launched game (message, clean exit), sent when it ends
+	pub game_exit_rx: Option<std::sync::mpsc::Receiver<(String, bool)>>,
 	// Update dialog state
 	pub show_update_dialog: bool,
 	pub update_folder_options: Vec<String>,
 	pub update_folder_selected: Vec<bool>,
-	// Update preview
+	// Update preview, filtered from one scan per dialog open (run off the UI thread)
+	pub update_scan_rx: Option<std::sync::mpsc::Receiver<UpdateScanResult>>,
+	pub update_scan: Option<UpdateScanResult>,
 	pub update_preview_dirty: bool,
-	pub update_preview_count: usize,
-	pub update_preview_bytes: u64,
+	pub update_entries: Vec<UpdateEntry>,
+	pub update_preview_linked: usize,
 	pub show_reapply_dialog: bool,
 	pub reapply_fixes: bool,
 	pub reapply_patches: bool,
@@ -78,6 +65,14 @@ pub struct LauncherApp {
 	pub setup: crate::ui::setup::SetupState,
 	pub mount: crate::ui::mount::MountState,
 	pub repositories: crate::ui::repositories::RepositoriesState,
+	pub about: crate::ui::about::AboutState,
+	pub logs: crate::ui::logs::LogsState,
+	pub settings_tab: crate::ui::settings::SettingsState,
+	// Last seen outer position and inner size, written to settings on exit
+	pub window_geometry: Option<(egui::Pos2, egui::Vec2)>,
+	// Saved position applied on the first frame, once egui reports the monitor size; only used
+	// where it cannot be checked before the window exists
+	pending_position: Option<egui::Pos2>,
 }
 
 impl Default for LauncherApp {
@@ -97,57 +92,261 @@ impl Default for LauncherApp {
 			Some(false) => Tab::Repositories,  // Setup was skipped, go to repositories
 			None => Tab::Setup,  // First time, show setup
 		};
+		let sources = store.load_sources();
+		let pending_position = if cfg!(windows) { None } else { settings.window_pos.map(|[x, y]| egui::pos2(x, y)) };
+		let runtime = JobRuntime::current();
+		let mut repositories = crate::ui::repositories::RepositoriesState::default();
+		repositories.fetch_missing_releases(&runtime, &sources);
+		let (failure_tx, failure_rx) = std::sync::mpsc::channel();
 		Self {
-			log: String::new(),
-			progress: 0,
-			not_elevated_warned: false,
+			log: LogSink::default(),
+			runtime,
 			current_job: None,
+			progress: 0,
+			failure_tx,
+			failure_rx,
 			settings_store: store,
 			settings,
+			sources,
 			selected: initial_tab,
 			is_running: false,
 			show_error_modal: None,
+			error_modal_hint: None,
+			last_job: None,
+			error_retry: None,
 			toasts: Vec::new(),
-			remix_source_idx: 0,
-			remix_releases: Vec::new(),
-			remix_release_idx: 0,
-			remix_rx: None,
-			remix_loading: false,
-			fixes_source_idx: 0,
-			fixes_releases: Vec::new(),
-			fixes_release_idx: 0,
-			fixes_rx: None,
-			fixes_loading: false,
-			patch_source_idx: 0,
+			game_exit_rx: None,
 			show_update_dialog: false,
 			update_folder_options: Vec::new(),
 			update_folder_selected: Vec::new(),
+			update_scan_rx: None,
+			update_scan: None,
 			update_preview_dirty: false,
-			update_preview_count: 0,
-			update_preview_bytes: 0,
+			update_entries: Vec::new(),
+			update_preview_linked: 0,
 			show_reapply_dialog: false,
 			reapply_fixes: true,
 			reapply_patches: true,
 			setup: Default::default(),
 			mount: Default::default(),
-			repositories: Default::default(),
+			repositories,
+			about: Default::default(),
+			logs: Default::default(),
+			settings_tab: Default::default(),
+			window_geometry: None,
+			pending_position,
+		}
+	}
+}
+
+/// Handle to the tokio runtime plus what `on_exit` needs to stop its jobs: a count of the
+/// blocking jobs still running and the cancellation tokens they poll.
+#[derive(Clone)]
+pub struct JobRuntime {
+	handle: tokio::runtime::Handle,
+	running: Arc<AtomicUsize>,
+	tokens: Arc<Mutex<Vec<CancellationToken>>>,
+}
+
+// Decrements the running count when a job returns or panics
+struct RunningGuard(Arc<AtomicUsize>);
+
+impl Drop for RunningGuard {
+	fn drop(&mut self) { self.0.fetch_sub(1, Ordering::SeqCst); }
+}
+
+impl JobRuntime {
+	pub fn current() -> Self {
+		Self { handle: tokio::runtime::Handle::current(), running: Default::default(), tokens: Default::default() }
+	}
+
+	/// Short async work such as release lists; dropped along with the runtime on exit.
+	pub fn spawn<F>(&self, fut: F) where F: std::future::Future<Output = ()> + Send + 'static {
+		self.handle.spawn(fut);
+	}
+
+	/// Blocking work, counted until it returns so `shutdown` can wait for it.
+	pub fn spawn_blocking<F>(&self, f: F) where F: FnOnce() + Send + 'static {
+		self.running.fetch_add(1, Ordering::SeqCst);
+		let guard = RunningGuard(self.running.clone());
+		self.handle.spawn_blocking(move || { let _guard = guard; f() });
+	}
+
+	/// A new token for a job; `shutdown` cancels it.
+	pub fn cancel_token(&self) -> CancellationToken {
+		let token = CancellationToken::new();
+		let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+		tokens.retain(|t| !t.is_cancelled());
+		tokens.push(token.clone());
+		token
+	}
+
+	pub fn running(&self) -> usize { self.running.load(Ordering::SeqCst) }
+
+	/// Cancel every job token, then wait up to `timeout` for running jobs. Returns how many are still running.
+	pub fn shutdown(&self, timeout: std::time::Duration) -> usize {
+		for t in self.tokens.lock().unwrap_or_else(|e| e.into_inner()).iter() { t.cancel(); }
+		let deadline = std::time::Instant::now() + timeout;
+		while self.running() > 0 && std::time::Instant::now() < deadline {
+			std::thread::sleep(std::time::Duration::from_millis(50));
 		}
+		self.running()
 	}
 }
 
-// Append a single line to a log, skipping if it is identical to the last line
-pub fn append_line_dedup(log: &mut String, msg: &str) {
-	let incoming = msg.trim_end_matches('\n');
-	if incoming.is_empty() { return; }
-	let last = log.rsplit('\n').next().unwrap_or("");
-	if last == incoming { return; }
-	if !log.is_empty() { log.push('\n'); }
-	log.push_str(incoming);
+// How long closing the window waits for cancelled jobs to wind down
+const EXIT_JOB_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Run `job` on a blocking-pool thread of `runtime`, driving its future there. Jobs mix sync file
+/// work with awaits (and may hold non-`Send` state), so they do not go on the async workers.
+pub fn spawn_task<F, Fut>(runtime: &JobRuntime, job: F)
+where
+	F: FnOnce() -> Fut + Send + 'static,
+	Fut: std::future::Future<Output = ()>,
+{
+	let handle = runtime.handle.clone();
+	runtime.spawn_blocking(move || handle.block_on(job()));
+}
+
+/// `spawn_task` for a job that reports progress; the receiver is what a tab polls as its current job.
+pub fn spawn_job<F, Fut>(runtime: &JobRuntime, job: F) -> std::sync::mpsc::Receiver<JobProgress>
+where
+	F: FnOnce(std::sync::mpsc::Sender<JobProgress>) -> Fut + Send + 'static,
+	Fut: std::future::Future<Output = ()>,
+{
+	let (tx, rx) = std::sync::mpsc::channel();
+	spawn_task(runtime, move || job(tx));
+	rx
+}
+
+/// Send the terminal progress line for a failed or cancelled job so the UI stops waiting on it;
+/// real failures also go to `failures`, which the owning tab drains into the error dialog.
+pub fn report_failure(tx: &std::sync::mpsc::Sender<JobProgress>, failures: &std::sync::mpsc::Sender<LauncherError>, e: impl Into<LauncherError>, what: &str) {
+	let e = e.into();
+	if e.is_cancelled() {
+		let _ = tx.send(JobProgress { message: format!("{} cancelled", what), percent: 100 });
+		return;
+	}
+	let _ = tx.send(JobProgress { message: format!("{} failed: {}", what, e), percent: 100 });
+	let _ = failures.send(e);
+}
+
+fn job_lock_message() -> Option<String> {
+	JobLock::holder().map(|operation| format!("{} is running; installs, updates, patching and mounting are unavailable until it finishes", operation))
+}
+
+/// Names the job holding the `JobLock`, next to the buttons it disables.
+pub fn job_lock_notice(ui: &mut egui::Ui) {
+	if let Some(message) = job_lock_message() { ui.colored_label(egui::Color32::YELLOW, message); }
+}
+
+/// The same explanation as a hover on a button the `JobLock` disabled.
+pub fn job_lock_hover(response: egui::Response) -> egui::Response {
+	match job_lock_message() {
+		Some(message) => response.on_disabled_hover_text(message),
+		None => response,
+	}
+}
+
+// Timestamp stored in the installed_*_at settings
+pub fn now_rfc3339() -> String { chrono::Local::now().to_rfc3339() }
+
+// Lines kept in the in-memory log; older lines are dropped in chunks once exceeded
+const MAX_LOG_LINES: usize = 5000;
+// Byte cap as well, so a few enormous lines cannot grow the log without bound
+const MAX_LOG_BYTES: usize = 1024 * 1024;
+// Shorter shared prefixes are too generic to treat as the same progress message
+const MIN_REPEAT_PREFIX: usize = 6;
+
+/// The in-memory log shown on the Logs tab. Consecutive lines that differ only in their
+/// counters (e.g. "Extracting 12/400") collapse into the latest one with a repeat count.
+#[derive(Default)]
+pub struct LogSink {
+	text: String,
+	lines: usize,
+	// Where the last line starts in `text`, its repeat key and how many lines it stands for
+	last_start: usize,
+	last_key: String,
+	repeats: usize,
+}
+
+// A line's text up to its first digit, '(' or ':' - the part that stays fixed across progress
+// updates. Errors and warnings never collapse, so none of them is hidden behind a later one.
+fn repeat_key(line: &str) -> &str {
+	let lower = line.to_ascii_lowercase();
+	if ["error", "fail", "warn"].iter().any(|w| lower.contains(w)) { return ""; }
+	let end = line.find(|c: char| c.is_ascii_digit() || c == '(' || c == ':').unwrap_or(line.len());
+	line[..end].trim_end()
+}
+
+impl LogSink {
+	pub fn push(&mut self, msg: &str) {
+		let incoming = msg.trim_end_matches('\n');
+		if incoming.is_empty() { return; }
+		let key = repeat_key(incoming);
+		if self.lines > 0 && key.len() >= MIN_REPEAT_PREFIX && key == self.last_key {
+			self.repeats += 1;
+			self.text.truncate(self.last_start);
+			self.text.push_str(&format!("{} (x{})", incoming, self.repeats));
+			return;
+		}
+		if self.lines > 0 && self.text[self.last_start..] == *incoming { return; }
+		if !self.text.is_empty() { self.text.push('\n'); }
+		self.last_start = self.text.len();
+		self.last_key = key.to_string();
+		self.repeats = 1;
+		self.text.push_str(incoming);
+		self.lines += 1;
+		if self.lines > MAX_LOG_LINES || self.text.len() > MAX_LOG_BYTES { self.trim(); }
+	}
+
+	// Drop the oldest lines down to 90% of both caps so trimming is not repeated on every push
+	fn trim(&mut self) {
+		let mut cut = 0;
+		while self.lines > MAX_LOG_LINES * 9 / 10 || self.text.len() - cut > MAX_LOG_BYTES * 9 / 10 {
+			match self.text[cut..self.last_start].find('\n') {
+				Some(i) => { cut += i + 1; self.lines -= 1; }
+				None => break,
+			}
+		}
+		self.text.drain(..cut);
+		self.last_start -= cut;
+	}
+
+	pub fn clear(&mut self) { *self = Self::default(); }
+
+	pub fn as_str(&self) -> &str { &self.text }
 }
 
 impl LauncherApp {
-	#[allow(dead_code)]
-	pub fn append_log(&mut self, msg: &str) { append_line_dedup(&mut self.log, msg); }
+	pub fn append_log(&mut self, msg: &str) { self.log.push(msg); }
+	// On Linux, wait for the game in the background so a failed Proton start is reported; Windows stays fire-and-forget
+	fn watch_game(&mut self, game: rtxlauncher_core::LaunchedGame) {
+		if cfg!(windows) { return; }
+		let (tx, rx) = std::sync::mpsc::channel::<(String, bool)>();
+		self.game_exit_rx = Some(rx);
+		let rtxlauncher_core::LaunchedGame { mut child, output_log } = game;
+		std::thread::spawn(move || {
+			let (msg, clean) = match child.wait() {
+				Ok(status) if status.success() => ("Game exited".to_string(), true),
+				Ok(status) => (match status.code() { Some(code) => format!("Game exited with code {}", code), None => format!("Game {}", status) }, false),
+				Err(e) => (format!("Lost track of the game process: {}", e), false),
+			};
+			let msg = match output_log { Some(p) => format!("{} (output: {})", msg, p.display()), None => msg };
+			let _ = tx.send((msg, clean));
+		});
+	}
+	fn poll_game_exit(&mut self) {
+		let Some(rx) = self.game_exit_rx.take() else { return; };
+		match rx.try_recv() {
+			Ok((msg, clean)) => {
+				self.append_log(&msg);
+				self.add_toast(&msg, if clean { egui::Color32::LIGHT_BLUE } else { egui::Color32::RED });
+			}
+			Err(std::sync::mpsc::TryRecvError::Empty) => self.game_exit_rx = Some(rx),
+			Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+		}
+	}
 	pub fn add_toast(&mut self, msg: &str, color: egui::Color32) { self.toasts.push(Toast { msg: msg.to_string(), color, until: std::time::Instant::now() + std::time::Duration::from_secs(4) }); }
 	fn draw_toasts(&mut self, ctx: &egui::Context) {
 		let now = std::time::Instant::now();
@@ -161,10 +360,37 @@ impl LauncherApp {
 }
 
 impl App for LauncherApp {
+	fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+		self.save_window_geometry();
+		let running = self.runtime.running();
+		if running == 0 { return; }
+		tracing::info!("Aborting {} running job(s)", running);
+		let left = self.runtime.shutdown(EXIT_JOB_TIMEOUT);
+		if left > 0 {
+			// The runtime would otherwise block process exit until they finish
+			tracing::warn!("{} job(s) did not stop within {}s; exiting anyway", left, EXIT_JOB_TIMEOUT.as_secs());
+			std::process::exit(0);
+		}
+		tracing::info!("All jobs stopped");
+	}
+
 	fn update(&mut self, ctx: &egui::Context, _: &mut eframe::Frame) {
 		egui_extras::install_image_loaders(ctx);
 		let is_focused = ctx.input(|i| i.focused);
 		if is_focused { ctx.request_repaint_after(std::time::Duration::from_millis(1000)); }
+		self.handle_shortcuts(ctx);
+		self.poll_job();
+		if let Some(pos) = self.pending_position.take() {
+			let (monitor, outer) = ctx.input(|i| (i.viewport().monitor_size, i.viewport().outer_rect));
+			// Without a monitor size the position cannot be checked, so the window manager's placement stays
+			if let Some(monitor) = monitor {
+				let size = outer.map_or(egui::Vec2::ZERO, |r| r.size());
+				ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(clamp_to_monitor(pos, size, monitor)));
+			}
+		}
+		if let Some((outer, inner)) = ctx.input(|i| i.viewport().outer_rect.zip(i.viewport().inner_rect)) {
+			self.window_geometry = Some((outer.min, inner.size()));
+		}
 
 		// Bottom status bar first (spans full width)
 		egui::TopBottomPanel::bottom("status_bar").exact_height(40.0).show(ctx, |ui| {
@@ -176,39 +402,15 @@ impl App for LauncherApp {
 					// Left padding
 					ui.add_space(8.0);
 					
-					let any_running = self.setup.is_running || self.repositories.is_running || self.mount.is_running;
-					
-					// Check if we should show the Launch Game button
-					let show_launch_button = match self.settings.setup_completed {
-						Some(true) => true,  // Setup completed successfully
-						Some(false) => true, // Setup was skipped, assume they have installation
-						None => {
-							// First time - check if there's an existing RTX installation
-							if let Ok(exec_dir) = std::env::current_exe().map(|p| p.parent().unwrap().to_path_buf()) {
-								let root_exe = exec_dir.join("gmod.exe");
-								let win64_exe = exec_dir.join("bin").join("win64").join("gmod.exe");
-								let hl2_exe = exec_dir.join("hl2.exe");
-								root_exe.exists() || win64_exe.exists() || hl2_exe.exists()
-							} else {
-								false
-							}
-						}
-					};
+					let any_running = self.any_running();
 					
 					// Launch Game button on the left
-					if show_launch_button {
-						if ui.add_enabled_ui(!any_running, |ui| {
-							ui.add_sized([120.0, 30.0], 
-								egui::Button::new(egui::RichText::new("Launch Game").size(14.0)).rounding(egui::Rounding::same(6.0))
-							)
-						}).inner.clicked() {
-							if let Ok(exec_dir) = std::env::current_exe().and_then(|p| p.parent().map(|p| p.to_path_buf()).ok_or(std::io::Error::from(std::io::ErrorKind::NotFound))) {
-								let root_exe = exec_dir.join("gmod.exe");
-								let win64_exe = exec_dir.join("bin").join("win64").join("gmod.exe");
-								let exe = if win64_exe.exists() { win64_exe } else if root_exe.exists() { root_exe } else { exec_dir.join("hl2.exe") };
-								if launch_game(exe, &self.settings).is_ok() { self.add_toast("Launched game", egui::Color32::LIGHT_GREEN); } else { self.add_toast("Failed to launch game — check Proton path/Steam root in Settings", egui::Color32::RED); }
-							}
-						}
+					if self.show_launch_button() && ui.add_enabled_ui(!any_running, |ui| {
+						ui.add_sized([120.0, 30.0], 
+							egui::Button::new(egui::RichText::new("Launch Game").size(14.0)).rounding(egui::Rounding::same(6.0))
+						)
+					}).inner.on_hover_text("Enter").clicked() {
+						self.launch_from_ui();
 					}
 					
 					// Progress bar anchored to the right with proper padding
@@ -218,9 +420,11 @@ impl App for LauncherApp {
 						let (progress_pct, progress_text) = if self.setup.is_running {
 							(self.setup.progress as f32 / 100.0, format!("{}%", self.setup.progress))
 						} else if self.repositories.is_running {
-							(self.repositories.progress as f32 / 100.0, format!("{}%", self.repositories.progress))
+							(self.repositories.progress as f32 / 100.0, self.repositories.progress_text())
 						} else if self.mount.is_running {
 							(0.0, "Mounting...".to_string())
+						} else if self.is_running {
+							(self.progress as f32 / 100.0, format!("{}%", self.progress))
 						} else {
 							(0.0, "Operation in progress...".to_string())
 						};
@@ -247,27 +451,27 @@ impl App for LauncherApp {
 			ui.separator();
 			// Larger navigation tabs with custom font size
 			ui.add_sized([ui.available_width(), 20.0], |ui: &mut egui::Ui| {
-				ui.selectable_value(&mut self.selected, Tab::Setup, egui::RichText::new("Setup").size(20.0))
+				ui.selectable_value(&mut self.selected, Tab::Setup, egui::RichText::new("Setup").size(20.0)).on_hover_text("Ctrl+1")
 			});
 			ui.add_space(10.0);
 			ui.add_sized([ui.available_width(), 20.0], |ui: &mut egui::Ui| {
-				ui.selectable_value(&mut self.selected, Tab::Mount, egui::RichText::new("Mounting").size(20.0))
+				ui.selectable_value(&mut self.selected, Tab::Mount, egui::RichText::new("Mounting").size(20.0)).on_hover_text("Ctrl+2")
 			});
 			ui.add_space(10.0);
 			ui.add_sized([ui.available_width(), 20.0], |ui: &mut egui::Ui| {
-				ui.selectable_value(&mut self.selected, Tab::Repositories, egui::RichText::new("Repositories").size(20.0))
+				ui.selectable_value(&mut self.selected, Tab::Repositories, egui::RichText::new("Repositories").size(20.0)).on_hover_text("Ctrl+3")
 			});
 			ui.add_space(10.0);
 			ui.add_sized([ui.available_width(), 20.0], |ui: &mut egui::Ui| {
-				ui.selectable_value(&mut self.selected, Tab::Settings, egui::RichText::new("Settings").size(20.0))
+				ui.selectable_value(&mut self.selected, Tab::Settings, egui::RichText::new("Settings").size(20.0)).on_hover_text("Ctrl+4")
 			});
 			ui.add_space(10.0);
 			ui.add_sized([ui.available_width(), 20.0], |ui: &mut egui::Ui| {
-				ui.selectable_value(&mut self.selected, Tab::Logs, egui::RichText::new("Logs").size(20.0))
+				ui.selectable_value(&mut self.selected, Tab::Logs, egui::RichText::new("Logs").size(20.0)).on_hover_text("Ctrl+5 / Ctrl+L")
 			});
 			ui.add_space(10.0);
 			ui.add_sized([ui.available_width(), 20.0], |ui: &mut egui::Ui| {
-				ui.selectable_value(&mut self.selected, Tab::About, egui::RichText::new("About").size(20.0))
+				ui.selectable_value(&mut self.selected, Tab::About, egui::RichText::new("About").size(20.0)).on_hover_text("Ctrl+6")
 			});
 			ui.add_space(8.0);
 			#[cfg(windows)]
@@ -294,19 +498,127 @@ impl App for LauncherApp {
 		self.render_update_dialog(ctx);
 		self.render_reapply_dialog(ctx);
 		self.render_error_modal(ctx);
+		crate::ui::repositories::render_conflict_dialog(&mut self.repositories, ctx);
+		self.poll_game_exit();
 		self.draw_toasts(ctx);
 	}
 }
 
+// Height of the strip along the top of the window that must be on a monitor to restore a position
+#[cfg(windows)]
+const TITLE_BAR_HEIGHT: f32 = 40.0;
+
+/// Apply the saved window size and position, unless the position is no longer on any monitor
+/// (e.g. it was saved on a display that has since been disconnected). Elsewhere than Windows the
+/// position is applied on the first frame instead, clamped to the monitor.
+pub fn restore_window_geometry(viewport: egui::ViewportBuilder, settings: &AppSettings) -> egui::ViewportBuilder {
+	let mut viewport = viewport;
+	let min = viewport.min_inner_size.unwrap_or(egui::Vec2::ZERO);
+	if let Some([w, h]) = settings.window_size {
+		viewport = viewport.with_inner_size(egui::vec2(w, h).max(min));
+	}
+	#[cfg(windows)]
+	if let Some([x, y]) = settings.window_pos {
+		let width = viewport.inner_size.map_or(min.x, |s| s.x);
+		let title_bar = egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(width, TITLE_BAR_HEIGHT));
+		if title_bar_visible(title_bar) { viewport = viewport.with_position([x, y]); }
+	}
+	viewport
+}
+
+#[cfg(windows)]
+fn title_bar_visible(rect: egui::Rect) -> bool {
+	use windows::Win32::Foundation::RECT;
+	use windows::Win32::Graphics::Gdi::{MonitorFromRect, MONITOR_DEFAULTTONULL};
+	let r = RECT { left: rect.min.x as i32, top: rect.min.y as i32, right: rect.max.x as i32, bottom: rect.max.y as i32 };
+	!unsafe { MonitorFromRect(&r, MONITOR_DEFAULTTONULL) }.is_invalid()
+}
+
+// Move `pos` so a window of `size` lies inside a monitor of `monitor` size, as far as it fits
+fn clamp_to_monitor(pos: egui::Pos2, size: egui::Vec2, monitor: egui::Vec2) -> egui::Pos2 {
+	egui::pos2(pos.x.clamp(0.0, (monitor.x - size.x).max(0.0)), pos.y.clamp(0.0, (monitor.y - size.y).max(0.0)))
+}
+
 impl LauncherApp {
-	pub fn append_global_log(&mut self, msg: &str) { append_line_dedup(&mut self.log, msg); }
+	// Re-read settings so values a running job saved are not overwritten with this copy
+	fn save_window_geometry(&self) {
+		let Some((pos, size)) = self.window_geometry else { return };
+		let mut settings = self.settings_store.load().unwrap_or_else(|_| self.settings.clone());
+		settings.window_pos = Some([pos.x, pos.y]);
+		settings.window_size = Some([size.x, size.y]);
+		let _ = self.settings_store.save(&settings);
+	}
+
+	pub(crate) fn any_running(&self) -> bool { self.setup.is_running || self.repositories.is_running || self.mount.is_running || self.is_running }
+
+	// Drain the dialog-started job into the log; a failure opens the error dialog
+	fn poll_job(&mut self) {
+		if let Some(rx) = self.current_job.take() {
+			while let Ok(p) = rx.try_recv() {
+				self.progress = p.percent;
+				self.log.push(&p.message);
+				if p.percent >= 100 { self.is_running = false; }
+			}
+			if self.is_running { self.current_job = Some(rx); }
+		}
+		if let Ok(e) = self.failure_rx.try_recv() {
+			// Nothing to reapply onto after a failed base update
+			self.show_reapply_dialog = false;
+			self.show_job_error(&e);
+		}
+	}
+
+	fn show_launch_button(&self) -> bool {
+		match self.settings.setup_completed {
+			Some(true) => true,  // Setup completed successfully
+			Some(false) => true, // Setup was skipped, assume they have installation
+			None => {
+				// First time - check if there's an existing RTX installation
+				rtxlauncher_core::resolve_game_exe(&self.settings.install_dir()).is_some()
+			}
+		}
+	}
+
+	pub(crate) fn launch_from_ui(&mut self) {
+		match rtxlauncher_core::resolve_game_exe(&self.settings.install_dir()).map(|exe| launch_game(exe, &self.settings)) {
+			Some(Ok(game)) => { self.add_toast("Launched game", egui::Color32::LIGHT_GREEN); self.watch_game(game); }
+			Some(Err(_)) => self.add_toast("Failed to launch game — check Proton path/Steam root in Settings", egui::Color32::RED),
+			None => self.add_toast("No gmod.exe or hl2.exe in the RTX install folder", egui::Color32::RED),
+		}
+	}
+
+	// Ctrl+1..6 pick a tab, Ctrl+L opens Logs, and Enter runs the tab's main action when no widget
+	// has focus. Nothing fires while a text field is being typed in.
+	fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+		if ctx.wants_keyboard_input() { return; }
+		const TABS: [(egui::Key, Tab); 6] = [
+			(egui::Key::Num1, Tab::Setup), (egui::Key::Num2, Tab::Mount), (egui::Key::Num3, Tab::Repositories),
+			(egui::Key::Num4, Tab::Settings), (egui::Key::Num5, Tab::Logs), (egui::Key::Num6, Tab::About),
+		];
+		let (tab, enter) = ctx.input_mut(|i| {
+			let mut tab = TABS.iter().find(|(k, _)| i.consume_key(egui::Modifiers::COMMAND, *k)).map(|(_, t)| *t);
+			if i.consume_key(egui::Modifiers::COMMAND, egui::Key::L) { tab = Some(Tab::Logs); }
+			(tab, i.key_pressed(egui::Key::Enter) && i.modifiers.is_none())
+		});
+		if let Some(tab) = tab { self.selected = tab; }
+		// A focused button handles Enter itself
+		if !enter || ctx.memory(|m| m.focused().is_some()) { return; }
+		// Leave Enter alone while a dialog is waiting on the user
+		if self.any_running() || self.show_update_dialog || self.show_reapply_dialog || self.show_error_modal.is_some() || self.setup.pending_install.is_some() { return; }
+		if self.selected == Tab::Setup && crate::ui::setup::shows_install_prompt(self) {
+			crate::ui::setup::request_quick_install(self);
+		} else if self.show_launch_button() {
+			self.launch_from_ui();
+		}
+	}
 
 	pub fn prepare_update_dialog(&mut self) {
 		self.update_folder_options.clear();
 		self.update_folder_selected.clear();
-		let vanilla = self.settings.manually_specified_install_path.clone().or_else(|| detect_gmod_install_folder().map(|p| p.display().to_string()));
-		if let Some(v) = vanilla {
-			let root = std::path::PathBuf::from(v);
+		self.update_entries.clear();
+		self.update_scan = None;
+		self.update_scan_rx = None;
+		if let Some(root) = self.vanilla_dir() {
 			if let Ok(rd) = std::fs::read_dir(&root) {
 				for e in rd.flatten() {
 					if e.path().is_dir() {
@@ -319,6 +631,26 @@ impl LauncherApp {
 			}
 		}
 		self.update_preview_dirty = true;
+		self.start_update_scan();
+	}
+
+	// Compare contents once for the whole install; folder toggles only filter the result
+	fn start_update_scan(&mut self) {
+		let Some(src) = self.vanilla_dir() else {
+			self.update_scan = Some(Err("Vanilla Garry's Mod install not found".into()));
+			return;
+		};
+		let dst = self.settings.install_dir();
+		let (tx, rx) = std::sync::mpsc::channel();
+		self.update_scan_rx = Some(rx);
+		self.runtime.spawn_blocking(move || {
+			let scan = rtxlauncher_core::scan_updates(&src, &dst, rtxlauncher_core::UpdateCompare::Contents).map_err(|e| e.to_string()).map(|scan| {
+				// Directories are only created and broken links relinked; neither copies data
+				let bytes = scan.updates.iter().map(|u| if !u.is_directory && (u.is_new || u.destination_path.exists()) { std::fs::metadata(&u.source_path).map(|m| m.len()).unwrap_or(0) } else { 0 }).collect();
+				(scan, bytes)
+			});
+			let _ = tx.send(scan);
+		});
 	}
 
 	pub fn render_update_dialog(&mut self, ctx: &egui::Context) {
@@ -332,12 +664,37 @@ impl LauncherApp {
 				any |= sel;
 			}
 			ui.separator();
+			if let Some(scan) = self.update_scan_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+				self.update_scan = Some(scan);
+				self.update_scan_rx = None;
+				self.update_preview_dirty = true;
+			}
+			match &self.update_scan {
+				None => { ui.horizontal(|ui| { ui.add(egui::Spinner::new()); ui.label("Scanning for changed files..."); }); }
+				Some(Err(e)) => { ui.colored_label(egui::Color32::RED, format!("Could not scan for changes: {}", e)); }
+				Some(Ok(_)) => {}
+			}
 			if self.update_preview_dirty { self.recompute_update_preview(); }
-			ui.label(format!("Will copy approximately {} item(s), {}", self.update_preview_count, humansize::format_size(self.update_preview_bytes, humansize::BINARY)));
+			let chosen: Vec<&UpdateEntry> = self.update_entries.iter().filter(|e| e.selected).collect();
+			let bytes: u64 = chosen.iter().map(|e| e.bytes).sum();
+			ui.label(format!("Will copy {} of {} item(s), {}", chosen.len(), self.update_entries.len(), humansize::format_size(bytes, humansize::BINARY)));
+			let none_chosen = chosen.is_empty();
+			if self.update_preview_linked > 0 { ui.label(format!("{} files already linked (skipped)", self.update_preview_linked)); }
+			if !self.update_entries.is_empty() {
+				egui::CollapsingHeader::new("Files to copy").default_open(false).show(ui, |ui| {
+					ui.horizontal(|ui| {
+						if ui.small_button("Select all").clicked() { self.update_entries.iter_mut().for_each(|e| e.selected = true); }
+						if ui.small_button("Select none").clicked() { self.update_entries.iter_mut().for_each(|e| e.selected = false); }
+					});
+					render_update_checklist(&mut self.update_entries, ui);
+				});
+			}
+			job_lock_notice(ui);
 			ui.separator();
 			ui.horizontal(|ui| {
-				if ui.add_enabled(any && !self.is_running, egui::Button::new("Apply")).clicked() {
+				if ui.add_enabled(any && !none_chosen && JobLock::holder().is_none(), egui::Button::new("Apply")).clicked() {
 					self.show_update_dialog = false;
+					self.last_job = Some(RetryJob::BaseUpdate);
 					self.start_base_update_job();
 				}
 				if ui.button("Cancel").clicked() { self.show_update_dialog = false; }
@@ -345,25 +702,34 @@ impl LauncherApp {
 		});
 	}
 
+	fn vanilla_dir(&self) -> Option<std::path::PathBuf> {
+		self.settings.manually_specified_install_path.clone().map(std::path::PathBuf::from).or_else(detect_gmod_install_folder)
+	}
+
+	fn selected_update_folders(&self) -> Vec<String> {
+		self.update_folder_options.iter().zip(&self.update_folder_selected).filter(|(_, s)| **s).map(|(l, _)| l.clone()).collect()
+	}
+
 	fn start_base_update_job(&mut self) {
-		let selected_prefixes: Vec<String> = self.update_folder_options.iter().cloned().zip(self.update_folder_selected.iter().cloned()).filter_map(|(l, s)| if s { Some(l) } else { None }).collect();
+		let lock = match JobLock::acquire("Base game update") {
+			Ok(lock) => lock,
+			Err(e) => return self.show_launcher_error(&e),
+		};
+		// Exactly the rows left checked in the dialog
+		let filtered: Vec<_> = self.update_entries.iter().filter(|e| e.selected).map(|e| e.info.clone()).collect();
 		let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
 		self.current_job = Some(rx);
 		self.is_running = true;
-		std::thread::spawn(move || {
-			let src = rtxlauncher_core::detect_gmod_install_folder().unwrap_or_default();
-			let dst = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default();
-			let updates = rtxlauncher_core::detect_updates(&src, &dst).unwrap_or_default();
-			let include_root_execs = selected_prefixes.iter().any(|p| p == "bin");
-			let filtered: Vec<_> = updates.into_iter().filter(|u| {
-				if selected_prefixes.is_empty() { return false; }
-				let rp = &u.relative_path;
-				if !rp.contains('/') { return include_root_execs && (rp.eq_ignore_ascii_case("gmod.exe") || rp.eq_ignore_ascii_case("hl2.exe") || rp.eq_ignore_ascii_case("steam_appid.txt")); }
-				for p in &selected_prefixes { let prefix = format!("{}/", p); if rp.starts_with(&prefix) || rp == p { return true; } }
-				false
-			}).collect();
-			let _ = rtxlauncher_core::apply_updates(&filtered, |m,p| { let scaled = ((p as u16 * 90) / 100) as u8; let _ = tx.send(JobProgress { message: m.to_string(), percent: scaled }); });
-			let _ = tx.send(JobProgress { message: "Base game update complete".into(), percent: 100 });
+		self.progress = 0;
+		let failures = self.failure_tx.clone();
+		let strategy = self.settings.link_strategy;
+		let cancel = self.runtime.cancel_token();
+		self.runtime.spawn_blocking(move || {
+			let _lock = lock;
+			match rtxlauncher_core::apply_updates(&filtered, strategy, &cancel, |m,p| { let scaled = ((p as u16 * 90) / 100) as u8; let _ = tx.send(JobProgress { message: m.to_string(), percent: scaled }); }) {
+				Ok(()) => { let _ = tx.send(JobProgress { message: "Base game update complete".into(), percent: 100 }); }
+				Err(e) => report_failure(&tx, &failures, e, "Base game update"),
+			}
 		});
 		self.show_reapply_dialog = true; self.reapply_fixes = true; self.reapply_patches = true;
 	}
@@ -374,29 +740,75 @@ impl LauncherApp {
 			ui.label("Reapply components after updating base game?");
 			ui.checkbox(&mut self.reapply_fixes, "Reapply Fixes Package");
 			ui.checkbox(&mut self.reapply_patches, "Reapply Binary Patches");
+			job_lock_notice(ui);
 			ui.horizontal(|ui| {
-				if ui.button("Proceed").clicked() { self.show_reapply_dialog = false; self.trigger_reapply_jobs(); }
+				if ui.add_enabled(JobLock::holder().is_none(), egui::Button::new("Proceed")).clicked() { self.show_reapply_dialog = false; self.last_job = Some(RetryJob::Reapply); self.trigger_reapply_jobs(); }
 				if ui.button("Skip").clicked() { self.show_reapply_dialog = false; }
 			});
 		});
 	}
 
+	// Fixes then patches as one job, so both report through `current_job` and the first failure stops it
 	fn trigger_reapply_jobs(&mut self) {
-		if self.reapply_fixes {
-			if let Some(rel) = self.repositories.fixes_releases.get(self.repositories.fixes_release_idx).cloned() {
-				let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
-				self.current_job = Some(rx);
-				self.is_running = true;
-				std::thread::spawn(move || { let rt = tokio::runtime::Runtime::new().unwrap(); rt.block_on(async move { let base = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default(); let _ = rtxlauncher_core::install_fixes_from_release(&rel, &base, Some(DEFAULT_IGNORE_PATTERNS), |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p }); }).await; }); });
+		let fixes = if self.reapply_fixes { self.repositories.fixes.releases.get(self.repositories.fixes.selected).cloned() } else { None };
+		let patches = self.reapply_patches.then(|| SourceLists::pick(&self.sources.patches, self.repositories.patch_source_idx).clone());
+		if fixes.is_none() && patches.is_none() { return; }
+		let lock = match JobLock::acquire("Reapplying components") {
+			Ok(lock) => lock,
+			Err(e) => return self.show_launcher_error(&e),
+		};
+		let base = self.settings.install_dir();
+		let options = rtxlauncher_core::PatchOptions::from_settings(&self.settings);
+		let cancel = self.runtime.cancel_token();
+		let failures = self.failure_tx.clone();
+		// One bar across both steps; 100 is left for the final message
+		let mut weights = Vec::new();
+		if fixes.is_some() { weights.push(("fixes", 50)); }
+		if patches.is_some() { weights.push(("patches", 50)); }
+		let stages = rtxlauncher_core::WeightedProgress::new(&weights);
+		self.progress = 0;
+		self.current_job = Some(spawn_job(&self.runtime, move |tx| async move {
+			let _lock = lock;
+			let progress = |m: &str, p: u8| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p.min(99) }); };
+			if let Some(rel) = fixes {
+				if let Err(e) = rtxlauncher_core::install_fixes_from_release(&rel, &base, Some(rtxlauncher_core::DEFAULT_IGNORE_PATTERNS), None, &cancel, stages.stage("fixes", progress)).await {
+					return report_failure(&tx, &failures, e, "Reapplying fixes");
+				}
 			}
-		}
-		if self.reapply_patches {
-			let (owner, repo) = { let s = [("sambow23","SourceRTXTweaks"),("BlueAmulet","SourceRTXTweaks"),("Xenthio","SourceRTXTweaks")][self.repositories.patch_source_idx.min(2)]; (s.0.to_string(), s.1.to_string()) };
-			let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
-			self.current_job = Some(rx);
-			self.is_running = true;
-			let install_dir = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default();
-			std::thread::spawn(move || { let rt = tokio::runtime::Runtime::new().unwrap(); rt.block_on(async move { let _ = rtxlauncher_core::apply_patches_from_repo(&owner, &repo, "applypatch.py", &install_dir, |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p }); }).await; }); });
+			if let Some(source) = patches {
+				if let Err(e) = rtxlauncher_core::apply_patches_from_repo(&source.owner, &source.repo, "applypatch.py", &base, &options, &cancel, stages.stage("patches", progress)).await {
+					return report_failure(&tx, &failures, e, "Reapplying patches");
+				}
+			}
+			let _ = tx.send(JobProgress { message: "Components reapplied".into(), percent: 100 });
+		}));
+		self.is_running = true;
+	}
+
+	/// Open the error dialog for a core error, with its hint when it has one.
+	pub fn show_launcher_error(&mut self, e: &rtxlauncher_core::LauncherError) {
+		self.show_error(e.to_string(), e.hint());
+	}
+
+	/// Like `show_launcher_error`, for a failed job; the dialog then offers to rerun the last job.
+	pub fn show_job_error(&mut self, e: &rtxlauncher_core::LauncherError) {
+		self.show_launcher_error(e);
+		self.error_retry = self.last_job.clone();
+	}
+
+	pub fn show_error(&mut self, msg: String, hint: Option<&str>) {
+		self.show_error_modal = Some(msg);
+		self.error_modal_hint = hint.map(str::to_string);
+		self.error_retry = None;
+	}
+
+	fn retry_job(&mut self, job: RetryJob) {
+		self.last_job = Some(job.clone());
+		match job {
+			RetryJob::QuickInstall(plan) => crate::ui::setup::start_quick_install(self, plan),
+			RetryJob::Repo(req) => crate::ui::repositories::start_request(&mut self.repositories, &self.runtime, &self.settings_store, &self.settings, req),
+			RetryJob::BaseUpdate => self.start_base_update_job(),
+			RetryJob::Reapply => self.trigger_reapply_jobs(),
 		}
 	}
 
@@ -404,9 +816,17 @@ impl LauncherApp {
 		if let Some(msg) = self.show_error_modal.clone() {
 			egui::Window::new("Error").collapsible(false).resizable(true).show(ctx, |ui| {
 				ui.colored_label(egui::Color32::RED, &msg);
+				if let Some(hint) = &self.error_modal_hint { ui.label(hint); }
 				ui.horizontal(|ui| {
 					if ui.button("Copy details").clicked() { ui.output_mut(|o| o.copied_text = msg.clone()); self.add_toast("Copied error", egui::Color32::LIGHT_GREEN); }
-					if ui.button("Close").clicked() { self.show_error_modal = None; }
+					// Another job may have started since this one failed
+					if let Some(job) = self.error_retry.clone() {
+						if ui.add_enabled(!self.any_running(), egui::Button::new("Retry")).clicked() {
+							self.show_error_modal = None; self.error_modal_hint = None; self.error_retry = None;
+							self.retry_job(job);
+						}
+					}
+					if ui.button("Close").clicked() { self.show_error_modal = None; self.error_modal_hint = None; self.error_retry = None; }
 				});
 			});
 		}
@@ -414,29 +834,43 @@ impl LauncherApp {
 
 	fn recompute_update_preview(&mut self) {
 		self.update_preview_dirty = false;
-		self.update_preview_count = 0;
-		self.update_preview_bytes = 0;
-		let vanilla = self.settings.manually_specified_install_path.clone().or_else(|| detect_gmod_install_folder().map(|p| p.display().to_string()));
-		let Some(v) = vanilla else { return; };
-		let src = std::path::PathBuf::from(v);
-		let dst = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default();
-		let updates = rtxlauncher_core::detect_updates(&src, &dst).unwrap_or_default();
-		let include_root_execs = self.update_folder_selected.iter().enumerate().any(|(i, s)| *s && self.update_folder_options.get(i).map(|p| p == "bin").unwrap_or(false));
-		for u in updates.into_iter() {
-			let rp = u.relative_path.clone();
-			let mut include = false;
-			if !rp.contains('/') { include = include_root_execs && (rp.eq_ignore_ascii_case("gmod.exe") || rp.eq_ignore_ascii_case("hl2.exe") || rp.eq_ignore_ascii_case("steam_appid.txt")); }
-			for (i, l) in self.update_folder_options.iter().enumerate() {
-				if !self.update_folder_selected[i] { continue; }
-				let prefix = format!("{}/", l);
-				if rp.starts_with(&prefix) || rp == *l { include = true; break; }
-			}
-			if include {
-				self.update_preview_count += 1;
-				if !u.is_directory { if let Ok(meta) = std::fs::metadata(&u.source_path) { self.update_preview_bytes = self.update_preview_bytes.saturating_add(meta.len()); } }
-			}
+		self.update_preview_linked = 0;
+		// Rows the user unchecked stay unchecked when folders are toggled
+		let unchecked: std::collections::HashSet<String> = self.update_entries.drain(..).filter(|e| !e.selected).map(|e| e.info.relative_path).collect();
+		let folders = self.selected_update_folders();
+		let Some(Ok((scan, sizes))) = &self.update_scan else { return; };
+		for (u, bytes) in scan.updates.iter().zip(sizes).filter(|(u, _)| rtxlauncher_core::update_selected(&u.relative_path, &folders)) {
+			let selected = !unchecked.contains(&u.relative_path);
+			self.update_entries.push(UpdateEntry { info: u.clone(), bytes: *bytes, selected });
 		}
+		self.update_preview_linked = scan.linked.iter().filter(|l| rtxlauncher_core::update_selected(&l.relative_path, &folders)).map(|l| l.files).sum();
 	}
 }
 
-
+// Scrollable list of update rows grouped under their top-level folder, each group with its own toggle
+fn render_update_checklist(entries: &mut [UpdateEntry], ui: &mut egui::Ui) {
+	egui::ScrollArea::vertical().max_height(300.0).auto_shrink([false, true]).show(ui, |ui| {
+		let mut start = 0;
+		while start < entries.len() {
+			let top = entries[start].info.relative_path.split('/').next().unwrap_or("").to_string();
+			let len = entries[start..].iter().take_while(|e| e.info.relative_path.split('/').next() == Some(top.as_str())).count();
+			let group = &mut entries[start..start + len];
+			start += len;
+			let chosen = group.iter().filter(|e| e.selected).count();
+			egui::CollapsingHeader::new(format!("{} ({}/{})", top, chosen, group.len())).id_salt(("update_group", &top)).show(ui, |ui| {
+				let mut all = chosen == group.len();
+				if ui.checkbox(&mut all, "All in this folder").changed() { group.iter_mut().for_each(|e| e.selected = all); }
+				for e in group.iter_mut() {
+					let kind = match (e.info.is_new, e.info.is_directory) {
+						(true, true) => "new folder",
+						(true, false) => "new",
+						// A changed entry whose destination does not resolve is a broken link
+						(false, _) if !e.info.destination_path.exists() => "relink",
+						(false, _) => "changed",
+					};
+					ui.checkbox(&mut e.selected, format!("[{}] {}", kind, e.info.relative_path));
+				}
+			});
+		}
+	});
+}
@@ -0,0 +1,94 @@
+//! `--headless`: run the quick install without a window, printing progress as JSON lines on stdout.
+use crate::quick_install::{run_quick_install, QuickInstallSources};
+use anyhow::{Context, Result};
+use rtxlauncher_core::{check_install_space, detect_gmod_install_folder, CancellationToken, InstallPlan, JobProgress, SettingsStore, SourceLists};
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage: rtxlauncher --headless [options]
+
+  --vanilla <path>        Garry's Mod install to build from (default: settings, then Steam detection)
+  --install-dir <path>    Where the RTX install is created (default: settings; not saved)
+  --remix-source <n>      0 = sambow23/dxvk-remix-gmod, 1 = NVIDIAGameWorks/rtx-remix
+  --fixes-source <n>      0 = Xenthio/gmod-rtx-fixes-2, 1 = Xenthio/RTXFixes
+  --patch-source <n>      0 = sambow23, 1 = BlueAmulet, 2 = Xenthio SourceRTXTweaks
+                          (indices into the lists in sources.toml; the built-in entries are shown)
+  --skip-base | --skip-remix | --skip-fixes | --skip-patches
+
+Progress is printed to stdout as one JSON object per line: {\"message\":...,\"percent\":...}.
+Logs go to stderr. Exits non-zero when a step fails.";
+
+struct Options {
+	vanilla: Option<PathBuf>,
+	install_dir: Option<PathBuf>,
+	remix_source: usize,
+	fixes_source: usize,
+	patch_source: usize,
+	skip_base: bool,
+	skip_remix: bool,
+	skip_fixes: bool,
+	skip_patches: bool,
+}
+
+fn parse_args(args: &[String], lists: &SourceLists) -> Result<Options> {
+	let mut o = Options { vanilla: None, install_dir: None, remix_source: 0, fixes_source: 0, patch_source: 0, skip_base: false, skip_remix: false, skip_fixes: false, skip_patches: false };
+	let mut it = args.iter();
+	while let Some(arg) = it.next() {
+		let mut value = || it.next().with_context(|| format!("{} needs a value\n\n{}", arg, USAGE));
+		let index = |v: &String, len: usize| -> Result<usize> {
+			v.parse::<usize>().ok().filter(|i| *i < len).with_context(|| format!("{} must be 0..{}, got {}", arg, len - 1, v))
+		};
+		match arg.as_str() {
+			"--headless" => {}
+			"--vanilla" => o.vanilla = Some(PathBuf::from(value()?)),
+			"--install-dir" => o.install_dir = Some(PathBuf::from(value()?)),
+			"--remix-source" => o.remix_source = index(value()?, lists.remix.len())?,
+			"--fixes-source" => o.fixes_source = index(value()?, lists.fixes.len())?,
+			"--patch-source" => o.patch_source = index(value()?, lists.patches.len())?,
+			"--skip-base" => o.skip_base = true,
+			"--skip-remix" => o.skip_remix = true,
+			"--skip-fixes" => o.skip_fixes = true,
+			"--skip-patches" => o.skip_patches = true,
+			"-h" | "--help" => { println!("{}", USAGE); std::process::exit(0); }
+			other => anyhow::bail!("unknown argument {}\n\n{}", other, USAGE),
+		}
+	}
+	Ok(o)
+}
+
+fn emit(message: &str, percent: u8) {
+	let p = JobProgress { message: message.to_string(), percent };
+	if let Ok(line) = serde_json::to_string(&p) { println!("{}", line); }
+}
+
+pub async fn run(args: &[String]) -> Result<()> {
+	let store = SettingsStore::new()?;
+	let lists = store.load_sources();
+	let o = parse_args(args, &lists)?;
+	let mut settings = store.load().unwrap_or_default();
+	let configured_dir = settings.install_dir();
+	if let Some(dir) = &o.install_dir { settings.install_target_path = Some(dir.display().to_string()); }
+	let plan = if o.skip_base { None } else {
+		let vanilla = o.vanilla.clone()
+			.or_else(|| settings.manually_specified_install_path.clone().map(PathBuf::from))
+			.or_else(detect_gmod_install_folder)
+			.context("Could not detect Garry's Mod; pass --vanilla <path>")?;
+		Some(InstallPlan { vanilla, rtx: settings.install_dir(), link_strategy: settings.link_strategy })
+	};
+	if let Some(plan) = &plan {
+		let space = check_install_space(plan);
+		if let (false, Some(free)) = (space.is_enough(), space.available) {
+			anyhow::bail!("Not enough free space on {}: need about {}, {} available",
+				plan.rtx.display(), humansize::format_size(space.required, humansize::BINARY), humansize::format_size(free, humansize::BINARY));
+		}
+	}
+	let sources = QuickInstallSources {
+		remix: (!o.skip_remix).then_some(o.remix_source),
+		fixes: (!o.skip_fixes).then_some(o.fixes_source),
+		patches: (!o.skip_patches).then_some(o.patch_source),
+	};
+	let result = run_quick_install(plan.as_ref(), sources, &lists, &mut settings, &CancellationToken::new(), emit).await;
+	// Keep the steps that did finish even when a later one failed. Installed versions describe the
+	// configured install, so a run into another --install-dir leaves settings untouched
+	if settings.install_dir() == configured_dir { store.save(&settings)?; }
+	result.map(|_| ())
+}
@@ -1,12 +1,24 @@
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
 mod app;
+mod headless;
+mod quick_install;
 mod ui;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    rtxlauncher_core::init_logging();
-    let _store = rtxlauncher_core::SettingsStore::new()?;
+	let store = rtxlauncher_core::SettingsStore::new()?;
+	let settings = store.load().unwrap_or_default();
+	rtxlauncher_core::init_logging(settings.log_retention, &settings.log_level);
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	if args.iter().any(|a| a == "--headless") {
+		if let Err(e) = headless::run(&args).await {
+			let line = serde_json::json!({ "message": format!("Error: {:#}", e), "percent": 100, "error": true });
+			println!("{}", line);
+			std::process::exit(1);
+		}
+		return Ok(());
+	}
 	let mut native_options = eframe::NativeOptions::default();
 	// Configure window min and initial size using the viewport builder (eframe 0.29)
 	native_options.viewport = native_options
@@ -15,6 +27,7 @@ async fn main() -> anyhow::Result<()> {
 		.with_min_inner_size([874.0, 500.0])
 		.with_resizable(false)
 		.with_maximize_button(false);
+	native_options.viewport = app::restore_window_geometry(native_options.viewport, &settings);
 	
 	eframe::run_native(
 		"RTXLauncher-rs",
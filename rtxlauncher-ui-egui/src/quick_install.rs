@@ -0,0 +1,122 @@
+//! The quick install shared by the Setup tab and `--headless`: base install, RTX Remix,
+//! community fixes, then binary patches, each weighted onto its slice of one 0..=100 bar.
+use anyhow::{Context, Result};
+use rtxlauncher_core::{apply_patches_from_repo, fetch_releases, install_fixes_from_release, install_remix_from_release, perform_basic_install, AppSettings, SourceLists, CancellationToken, GitHubRateLimit, GitHubRelease, InstallPlan, InstallStats, JobEvent, LauncherError, ProgressSink, WeightedProgress};
+use std::cell::Cell;
+
+/// Index into each of the `SourceLists`; `None` skips that step.
+#[derive(Clone, Copy)]
+pub struct QuickInstallSources {
+	pub remix: Option<usize>,
+	pub fixes: Option<usize>,
+	pub patches: Option<usize>,
+}
+
+impl Default for QuickInstallSources {
+	fn default() -> Self { Self { remix: Some(0), fixes: Some(0), patches: Some(0) } }
+}
+
+// Latest stable release unless the user opted into pre-releases
+async fn latest_release(owner: &str, repo: &str, include_prereleases: bool) -> Result<GitHubRelease> {
+	let releases = fetch_releases(owner, repo, &mut GitHubRateLimit::default()).await?;
+	rtxlauncher_core::latest_release(&releases, include_prereleases).cloned().with_context(|| format!("No releases in {}/{}", owner, repo))
+}
+
+/// What a finished quick install did, for the summary on the Setup tab.
+#[derive(Debug, Clone, Default)]
+pub struct QuickInstallSummary {
+	pub remix_version: Option<String>,
+	pub fixes_version: Option<String>,
+	/// `owner/repo` the patches came from
+	pub patch_source: Option<String>,
+	pub files_patched: usize,
+	/// `None` when the base install was skipped
+	pub base: Option<InstallStats>,
+	/// Warnings raised along the way (failed links, kept user files, patch sets that did not apply)
+	pub warnings: usize,
+}
+
+// Forwards a step's progress and counts the warnings it raises
+struct CountWarnings<'a, F> { progress: F, warnings: &'a Cell<usize> }
+
+impl<F: FnMut(&str, u8)> ProgressSink for CountWarnings<'_, F> {
+	fn progress(&mut self, message: &str, percent: u8) { (self.progress)(message, percent) }
+	fn event(&mut self, event: JobEvent) {
+		if matches!(event, JobEvent::Warning(_)) { self.warnings.set(self.warnings.get() + 1); }
+	}
+}
+
+fn release_name(rel: &GitHubRelease) -> String {
+	rel.name.clone().unwrap_or_else(|| rel.tag_name.clone().unwrap_or_default())
+}
+
+/// Run the quick install, recording each finished step in `settings` (the caller saves them,
+/// including after a failure so completed steps are not lost). Stops at the first failing step.
+/// `plan` of `None` skips the base install.
+pub async fn run_quick_install(plan: Option<&InstallPlan>, sources: QuickInstallSources, lists: &SourceLists, settings: &mut AppSettings, cancel: &CancellationToken, progress: impl Fn(&str, u8)) -> Result<QuickInstallSummary> {
+	let rtx = settings.install_dir();
+	let mut summary = QuickInstallSummary::default();
+	let warnings = Cell::new(0);
+	// Only the steps that run share the bar
+	let weights: Vec<(&'static str, u32)> = [("base", 25, plan.is_some()), ("remix", 35, sources.remix.is_some()), ("fixes", 25, sources.fixes.is_some()), ("patches", 15, sources.patches.is_some())]
+		.into_iter().filter(|(_, _, runs)| *runs).map(|(stage, weight, _)| (stage, weight)).collect();
+	let stages = WeightedProgress::new(&weights);
+	let report = |stage: &str, m: &str, p: u8| progress(m, stages.overall(stage, p));
+
+	if let Some(plan) = plan {
+		report("base", &format!("Building RTX install at {} from {}", plan.rtx.display(), plan.vanilla.display()), 0);
+		let stats = perform_basic_install(plan, cancel, stages.stage("base", &progress)).context("Base install failed")?;
+		warnings.set(warnings.get() + stats.link_failures);
+		summary.base = Some(stats);
+	}
+
+	if let Some(idx) = sources.remix {
+		cancel.check().map_err(LauncherError::from)?;
+		report("remix", "Downloading RTX Remix...", 0);
+		let source = SourceLists::pick(&lists.remix, idx);
+		let rel = latest_release(&source.owner, &source.repo, settings.show_prereleases).await?;
+		install_remix_from_release(&rel, &rtx, cancel, CountWarnings { progress: stages.stage("remix", &progress), warnings: &warnings }).await.context("RTX Remix install failed")?;
+		summary.remix_version = Some(release_name(&rel));
+		settings.installed_remix_version = Some(release_name(&rel));
+		settings.installed_remix_at = Some(crate::app::now_rfc3339());
+		settings.installed_remix_source = Some(source.slug());
+	}
+
+	if let Some(idx) = sources.fixes {
+		cancel.check().map_err(LauncherError::from)?;
+		report("fixes", "Installing community fixes...", 0);
+		let source = SourceLists::pick(&lists.fixes, idx);
+		let rel = latest_release(&source.owner, &source.repo, settings.show_prereleases).await?;
+		install_fixes_from_release(&rel, &rtx, Some(rtxlauncher_core::DEFAULT_IGNORE_PATTERNS), None, cancel, CountWarnings { progress: stages.stage("fixes", &progress), warnings: &warnings }).await.context("Fixes install failed")?;
+		summary.fixes_version = Some(release_name(&rel));
+		settings.installed_fixes_version = Some(release_name(&rel));
+		settings.installed_fixes_at = Some(crate::app::now_rfc3339());
+		settings.installed_fixes_source = Some(source.slug());
+	}
+
+	if let Some(idx) = sources.patches {
+		cancel.check().map_err(LauncherError::from)?;
+		report("patches", "Applying binary patches...", 0);
+		let source = SourceLists::pick(&lists.patches, idx);
+		let mut options = rtxlauncher_core::PatchOptions::from_settings(settings);
+		// Patch from the same vanilla install the base step copied
+		if let Some(plan) = plan { options.vanilla_source = Some(plan.vanilla.clone()); }
+		let result = apply_patches_from_repo(&source.owner, &source.repo, "applypatch.py", &rtx, &options, cancel, stages.stage("patches", &progress)).await.context("Patching failed")?;
+		warnings.set(warnings.get() + result.warnings.len());
+		summary.files_patched = result.files_patched;
+		let source = source.slug();
+		summary.patch_source = Some(source.clone());
+		settings.installed_patches_at = Some(crate::app::now_rfc3339());
+		settings.installed_patches_source = Some(source.clone());
+		settings.installed_patches_commit = Some(source);
+	}
+
+	summary.warnings = warnings.get();
+	progress("Setup complete! RTX Remix is ready to use.", 100);
+	Ok(summary)
+}
+
+/// The typed core error behind a quick install failure, if there is one.
+pub fn launcher_error(e: &anyhow::Error) -> Option<&LauncherError> {
+	e.chain().find_map(|c| c.downcast_ref::<LauncherError>())
+}
@@ -1,12 +1,61 @@
 use eframe::egui;
+use rtxlauncher_core::GitHubRelease;
+
+#[derive(Default)]
+pub struct AboutState {
+	pub update_checked: bool,
+	pub update_rx: Option<std::sync::mpsc::Receiver<Option<GitHubRelease>>>,
+	pub update: Option<GitHubRelease>,
+}
+
+impl AboutState {
+	// Kick off the launcher update check once per session, then poll for its result
+	fn poll_update_check(&mut self, runtime: &crate::app::JobRuntime) {
+		if !self.update_checked {
+			self.update_checked = true;
+			let (tx, rx) = std::sync::mpsc::channel::<Option<GitHubRelease>>();
+			self.update_rx = Some(rx);
+			runtime.spawn(async move {
+				let latest = rtxlauncher_core::check_launcher_update(env!("CARGO_PKG_VERSION"), option_env!("GIT_COMMIT_HASH")).await.unwrap_or(None);
+				let _ = tx.send(latest);
+			});
+		}
+		if let Some(rx) = self.update_rx.take() {
+			match rx.try_recv() {
+				Ok(r) => self.update = r,
+				Err(std::sync::mpsc::TryRecvError::Empty) => self.update_rx = Some(rx),
+				Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+			}
+		}
+	}
+}
+
+// " (from owner/repo, 12/03/2025 14:22)" for whatever is known about an installed component
+fn install_details(source: &Option<String>, at: &Option<String>) -> String {
+	let when = at.as_deref()
+		.and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+		.map(|t| t.with_timezone(&chrono::Local).format("%d/%m/%Y %H:%M").to_string());
+	let parts: Vec<String> = [source.as_ref().map(|s| format!("from {}", s)), when].into_iter().flatten().collect();
+	if parts.is_empty() { String::new() } else { format!(" ({})", parts.join(", ")) }
+}
 
 pub fn render_about_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
+	app.about.poll_update_check(&app.runtime);
 	ui.heading("About");
 	ui.separator();
 	ui.label("A recreation of Xenthio's original .NET launcher, aimed for cross-platform support like Linux, in addition to upcoming features.");
 	ui.separator();
 	let git = option_env!("GIT_COMMIT_HASH").unwrap_or("unknown");
-	ui.label(format!("Launcher version: {}", git));
+	ui.horizontal(|ui| {
+		ui.label(format!("Launcher version: {} ({})", env!("CARGO_PKG_VERSION"), git));
+		if app.about.update_rx.is_some() { ui.add(egui::Spinner::new()); }
+		if let Some(rel) = &app.about.update {
+			let tag = rel.tag_name.clone().unwrap_or_default();
+			ui.colored_label(egui::Color32::from_rgb(200,140,0), "Update available");
+			let url = rel.html_url.clone().unwrap_or_else(|| format!("https://github.com/sambow23/RTXLauncher/releases/tag/{}", tag));
+			ui.hyperlink_to(tag, url);
+		}
+	});
 	if let Some(p) = rtxlauncher_core::detect_gmod_install_folder() {
 		if let Ok(meta) = std::fs::metadata(&p) {
 			if let Ok(modified) = meta.modified() {
@@ -16,12 +65,24 @@ pub fn render_about_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 			}
 		}
 	}
-	let remix_v = app.settings.installed_remix_version.clone().unwrap_or_else(|| "(unknown)".into());
-	let fixes_v = app.settings.installed_fixes_version.clone().unwrap_or_else(|| "(unknown)".into());
-	let patch_c = app.settings.installed_patches_commit.clone().unwrap_or_else(|| "(none)".into());
-	ui.label(format!("Installed Remix: {}", remix_v));
-	ui.label(format!("Installed Fixes: {}", fixes_v));
-	ui.label(format!("Applied Patches: {}", patch_c));
+	let s = &app.settings;
+	let remix_v = s.installed_remix_version.clone().unwrap_or_else(|| "(unknown)".into());
+	let fixes_v = s.installed_fixes_version.clone().unwrap_or_else(|| "(unknown)".into());
+	let patch_c = s.installed_patches_commit.clone().unwrap_or_else(|| "(none)".into());
+	ui.label(format!("Installed Remix: {}{}", remix_v, install_details(&s.installed_remix_source, &s.installed_remix_at)));
+	ui.label(format!("Installed Fixes: {}{}", fixes_v, install_details(&s.installed_fixes_source, &s.installed_fixes_at)));
+	ui.label(format!("Applied Patches: {}{}", patch_c, install_details(&None, &s.installed_patches_at)));
+	ui.separator();
+	let install_dir = app.settings.install_dir();
+	let exists = install_dir.is_dir();
+	let btn = ui.add_enabled(exists, egui::Button::new("Open install folder"))
+		.on_hover_text(install_dir.display().to_string())
+		.on_disabled_hover_text("The install folder does not exist yet");
+	if btn.clicked() {
+		if let Err(e) = rtxlauncher_core::open_in_file_manager(&install_dir) {
+			app.append_log(&format!("Failed to open {}: {}", install_dir.display(), e));
+		}
+	}
 }
 
 
@@ -1,27 +1,118 @@
 use eframe::egui;
 
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelFilter { #[default] All, Warnings, Errors }
+
+#[derive(Default)]
+pub struct LogsState {
+	pub query: String,
+	pub level: LevelFilter,
+	// Show every line (matches highlighted) instead of only the matching ones
+	pub show_all: bool,
+}
+
+// Log lines are plain text, so the level comes from the wording the installers use
+fn line_level(line: &str) -> LevelFilter {
+	let lower = line.to_ascii_lowercase();
+	if lower.contains("error") || lower.contains("fail") { LevelFilter::Errors }
+	else if lower.contains("warn") || lower.contains("skipp") { LevelFilter::Warnings }
+	else { LevelFilter::All }
+}
+
+fn level_passes(line: &str, filter: LevelFilter) -> bool {
+	match filter {
+		LevelFilter::All => true,
+		LevelFilter::Warnings => line_level(line) != LevelFilter::All,
+		LevelFilter::Errors => line_level(line) == LevelFilter::Errors,
+	}
+}
+
+// Byte ranges of case-insensitive matches of `query` (already lowercased) in `line`
+fn match_ranges(line: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+	if query.is_empty() { return Vec::new(); }
+	let lower = line.to_ascii_lowercase();
+	lower.match_indices(query).map(|(i, m)| i..i + m.len()).collect()
+}
+
+fn highlighted(ui: &egui::Ui, line: &str, ranges: &[std::ops::Range<usize>]) -> egui::text::LayoutJob {
+	let font = egui::TextStyle::Monospace.resolve(ui.style());
+	let normal = egui::TextFormat { font_id: font.clone(), color: ui.visuals().text_color(), ..Default::default() };
+	let hit = egui::TextFormat { font_id: font, color: egui::Color32::BLACK, background: egui::Color32::from_rgb(230, 200, 60), ..Default::default() };
+	let mut job = egui::text::LayoutJob::default();
+	let mut pos = 0;
+	for r in ranges {
+		job.append(&line[pos..r.start], 0.0, normal.clone());
+		job.append(&line[r.clone()], 0.0, hit.clone());
+		pos = r.end;
+	}
+	job.append(&line[pos..], 0.0, normal);
+	job
+}
+
 pub fn render_logs_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 	ui.heading("Logs");
 	ui.separator();
-	
+
 	ui.horizontal(|ui| {
 		if ui.small_button("Copy").clicked() {
-			ui.output_mut(|o| o.copied_text = app.log.clone());
+			ui.output_mut(|o| o.copied_text = app.log.as_str().to_string());
 		}
 		if ui.small_button("Clear").clicked() {
 			app.log.clear();
 		}
+		if ui.small_button("Save to file...").clicked() {
+			let name = format!("rtxlauncher-log-{}.txt", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+			if let Some(path) = rfd::FileDialog::new().set_title("Save log").set_file_name(&name).save_file() {
+				match std::fs::write(&path, app.log.as_str()) {
+					Ok(()) => app.add_toast(&format!("Saved log to {}", path.display()), egui::Color32::LIGHT_GREEN),
+					Err(e) => app.append_log(&format!("Failed to save log to {}: {}", path.display(), e)),
+				}
+			}
+		}
+		if ui.small_button("Open log folder").clicked() {
+			let dir = rtxlauncher_core::logs_dir();
+			let _ = std::fs::create_dir_all(&dir);
+			if let Err(e) = rtxlauncher_core::open_in_file_manager(&dir) {
+				app.append_log(&format!("Failed to open {}: {}", dir.display(), e));
+			}
+		}
+	});
+	ui.horizontal(|ui| {
+		let st = &mut app.logs;
+		ui.label("Search:");
+		ui.add(egui::TextEdit::singleline(&mut st.query).hint_text("filter lines").desired_width(220.0));
+		if !st.query.is_empty() && ui.small_button("x").clicked() { st.query.clear(); }
+		egui::ComboBox::from_id_salt("log_level").selected_text(match st.level {
+			LevelFilter::All => "All lines",
+			LevelFilter::Warnings => "Warnings and errors",
+			LevelFilter::Errors => "Errors only",
+		}).show_ui(ui, |ui| {
+			ui.selectable_value(&mut st.level, LevelFilter::All, "All lines");
+			ui.selectable_value(&mut st.level, LevelFilter::Warnings, "Warnings and errors");
+			ui.selectable_value(&mut st.level, LevelFilter::Errors, "Errors only");
+		});
+		ui.checkbox(&mut st.show_all, "Show non-matching lines");
 	});
-	
+
 	ui.separator();
-	
-	let available_height = ui.available_height();
-	egui::ScrollArea::vertical()
+
+	let st = &app.logs;
+	let query = st.query.to_ascii_lowercase();
+	let lines: Vec<&str> = app.log.as_str().lines()
+		.filter(|l| level_passes(l, st.level))
+		.filter(|l| st.show_all || query.is_empty() || l.to_ascii_lowercase().contains(&query))
+		.collect();
+	if !query.is_empty() && !st.show_all { ui.small(format!("{} matching line(s)", lines.len())); }
+
+	// Only the rows in view are laid out, so long logs stay responsive
+	let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+	egui::ScrollArea::both()
 		.stick_to_bottom(true)
 		.auto_shrink([false, false])
-		.max_height(available_height)
-		.show(ui, |ui| {
-			ui.set_min_height(available_height - 20.0); // Leave some padding
-			ui.monospace(&app.log);
+		.show_rows(ui, row_height, lines.len(), |ui, rows| {
+			for line in &lines[rows] {
+				let ranges = match_ranges(line, &query);
+				if ranges.is_empty() { ui.monospace(*line); } else { ui.label(highlighted(ui, line, &ranges)); }
+			}
 		});
 }
@@ -1,30 +1,81 @@
 use eframe::egui;
-use rtxlauncher_core::{mount_game, unmount_game, JobProgress, apply_usda_fixes};
+use rtxlauncher_core::{mount_game, unmount_game, unmount_all, list_mounts, JobProgress, apply_usda_fixes, MOUNTABLE_GAMES, USDA_FIXES_SOURCES, has_rtxio_packages, extract_packages, JobLock};
 
 pub struct MountState {
+	pub mount_install_folder: String,
 	pub mount_game_folder: String,
 	pub mount_remix_mod: String,
+	// Install directory of the selected game, when it was detected
+	pub mount_install_path: Option<std::path::PathBuf>,
 	pub is_running: bool,
 	pub current_job: Option<std::sync::mpsc::Receiver<JobProgress>>,
+	pub progress: u8,
+	pub failure_rx: Option<std::sync::mpsc::Receiver<rtxlauncher_core::LauncherError>>,
+	// Install paths of MOUNTABLE_GAMES, index for index; looked up again when the tab is opened,
+	// after a job and on Refresh rather than every frame
+	detected: Option<Vec<Option<std::path::PathBuf>>>,
+	// Mount links in the install, and whether the (game folder, remix mod) pair in the fields is
+	// mounted; refreshed with `detected`, the latter also when the fields change
+	mounts: Option<Vec<String>>,
+	mounted: Option<(String, String, bool)>,
+	// Pass the tab was last drawn in, to notice it being opened again
+	last_pass: Option<u64>,
 }
 
 impl Default for MountState {
 	fn default() -> Self {
-		Self { mount_game_folder: "hl2rtx".to_string(), mount_remix_mod: "hl2rtx".to_string(), is_running: false, current_job: None }
+		let game = &MOUNTABLE_GAMES[0];
+		Self { mount_install_folder: game.install_folder.to_string(), mount_game_folder: game.game_folder.to_string(), mount_remix_mod: game.remix_mod.unwrap_or_default().to_string(), mount_install_path: None, is_running: false, current_job: None, progress: 0, failure_rx: None, detected: None, mounts: None, mounted: None, last_pass: None }
 	}
 }
 
 impl MountState {
-	pub fn poll_job(&mut self, global_log: &mut String) {
+	pub fn poll_job(&mut self, global_log: &mut crate::app::LogSink) {
 		if let Some(rx) = self.current_job.take() {
 			while let Ok(p) = rx.try_recv() {
-				// Append to global log (deduplicated)
-				crate::app::append_line_dedup(global_log, &p.message);
-				if p.percent >= 100 { self.is_running = false; }
+				self.progress = p.percent;
+				global_log.push(&p.message);
+				if p.percent >= 100 { self.is_running = false; self.refresh(); }
 			}
 			if self.is_running { self.current_job = Some(rx); }
 		}
 	}
+
+	// Drop what was looked up on disk; the next frame looks it up again
+	fn refresh(&mut self) {
+		self.detected = None;
+		self.mounts = None;
+		self.mounted = None;
+	}
+
+	fn begin_job(&mut self) -> (std::sync::mpsc::Sender<JobProgress>, std::sync::mpsc::Sender<rtxlauncher_core::LauncherError>) {
+		let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
+		let (failure_tx, failure_rx) = std::sync::mpsc::channel();
+		self.current_job = Some(rx);
+		self.failure_rx = Some(failure_rx);
+		self.is_running = true;
+		self.progress = 0;
+		(tx, failure_tx)
+	}
+}
+
+// Take the job lock for `operation`, or explain in the error dialog who holds it
+fn acquire_lock(app: &mut crate::app::LauncherApp, operation: &'static str) -> Option<JobLock> {
+	match JobLock::acquire(operation) {
+		Ok(lock) => Some(lock),
+		Err(e) => { app.show_launcher_error(&e); None }
+	}
+}
+
+// Unpack a remix mod's RTXIO .pkg files; always ends the job with a 100% line
+fn run_rtxio_extraction(base: &std::path::Path, remix_mod: &str, tx: &std::sync::mpsc::Sender<JobProgress>, failures: &std::sync::mpsc::Sender<rtxlauncher_core::LauncherError>) {
+	let result = extract_packages(base, remix_mod, |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p.min(99) }); });
+	let message = match result {
+		Ok(true) => format!("RTXIO packages for {} are ready", remix_mod),
+		Ok(false) => "RTXIO extraction did not complete; see the log above".to_string(),
+		Err(e) => return crate::app::report_failure(tx, failures, e, "RTXIO extraction"),
+	};
+	let _ = tx.send(JobProgress { message, percent: 100 });
 }
 
 pub fn render_mount_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
@@ -32,58 +83,145 @@ pub fn render_mount_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 		let st = &mut app.mount;
 		st.poll_job(&mut app.log);
 	}
+	if let Some(e) = app.mount.failure_rx.as_ref().and_then(|rx| rx.try_recv().ok()) { app.show_launcher_error(&e); }
+	let pass = ui.ctx().cumulative_pass_nr();
+	if app.mount.last_pass.is_none_or(|p| p + 1 < pass) { app.mount.refresh(); }
+	app.mount.last_pass = Some(pass);
 	ui.heading("Mounting");
+	if app.mount.is_running {
+		ui.add(egui::ProgressBar::new(app.mount.progress as f32 / 100.0).show_percentage());
+	}
 	ui.add_enabled_ui(!app.mount.is_running, |ui| {
-		ui.label("Detected mountable games:");
-		let mut detected: Vec<(&'static str, Option<std::path::PathBuf>, &'static str)> = vec![
-			("Half-Life 2 RTX", rtxlauncher_core::detect_install_folder_path("Half-Life 2 RTX"), "hl2rtx"),
-			("Portal RTX", rtxlauncher_core::detect_install_folder_path("Portal RTX"), "portalrtx"),
-		];
-		for (name, path_opt, mod_folder) in detected.drain(..) {
-			let label = if let Some(p) = path_opt { format!("{} — {}", name, p.display()) } else { format!("{} — not found", name) };
+		ui.horizontal(|ui| {
+			ui.label("Detected mountable games:");
+			if ui.small_button("Refresh").clicked() { app.mount.refresh(); }
+		});
+		let detected = app.mount.detected.get_or_insert_with(|| MOUNTABLE_GAMES.iter().map(|g| rtxlauncher_core::detect_install_folder_path(g.install_folder)).collect()).clone();
+		for (game, path_opt) in MOUNTABLE_GAMES.iter().zip(detected) {
+			let label = if let Some(p) = &path_opt { format!("{} — {}", game.display, p.display()) } else { format!("{} — not found", game.display) };
 			if ui.button(label).clicked() {
-				app.mount.mount_game_folder = mod_folder.to_string();
-				app.mount.mount_remix_mod = mod_folder.to_string();
+				app.mount.mount_install_folder = game.install_folder.to_string();
+				app.mount.mount_game_folder = game.game_folder.to_string();
+				app.mount.mount_remix_mod = game.remix_mod.unwrap_or_default().to_string();
+				app.mount.mount_install_path = path_opt;
 			}
 		}
 		ui.separator();
+		let mut inf = app.mount.mount_install_folder.clone();
+		ui.horizontal(|ui| { ui.label("Install folder (steamapps/common):"); ui.text_edit_singleline(&mut inf); });
+		if inf != app.mount.mount_install_folder { app.mount.mount_install_path = None; }
+		app.mount.mount_install_folder = inf;
 		let mut gf = app.mount.mount_game_folder.clone();
 		ui.horizontal(|ui| { ui.label("Game folder (source content):"); ui.text_edit_singleline(&mut gf); });
 		app.mount.mount_game_folder = gf;
 		let mut rm = app.mount.mount_remix_mod.clone();
-		ui.horizontal(|ui| { ui.label("Remix mod folder:"); ui.text_edit_singleline(&mut rm); });
+		ui.horizontal(|ui| { ui.label("Remix mod folder (optional):"); ui.text_edit_singleline(&mut rm); });
 		app.mount.mount_remix_mod = rm;
 		// Mounted status
-		let mounted = rtxlauncher_core::is_game_mounted(&app.mount.mount_game_folder, "Half-Life 2 RTX", &app.mount.mount_remix_mod);
+		let st = &mut app.mount;
+		let mounted = match &st.mounted {
+			Some((gf, rm, mounted)) if *gf == st.mount_game_folder && *rm == st.mount_remix_mod => *mounted,
+			_ => {
+				let mounted = rtxlauncher_core::is_game_mounted(&st.mount_game_folder, &st.mount_remix_mod);
+				st.mounted = Some((st.mount_game_folder.clone(), st.mount_remix_mod.clone(), mounted));
+				mounted
+			}
+		};
 		let status_col = if mounted { egui::Color32::from_rgb(0,200,0) } else { egui::Color32::from_rgb(200,0,0) };
 		ui.colored_label(status_col, if mounted { "Mounted" } else { "Not mounted" });
-		if ui.button("Mount").clicked() {
+		let unlocked = JobLock::holder().is_none();
+		if crate::app::job_lock_hover(ui.add_enabled(unlocked, egui::Button::new("Mount"))).clicked() {
+			let Some(lock) = acquire_lock(app, "Mounting") else { return; };
+			let inf = app.mount.mount_install_folder.clone();
 			let gf = app.mount.mount_game_folder.clone();
 			let rm = app.mount.mount_remix_mod.clone();
-			let mut tmp = String::new();
-			let _ = mount_game(&gf, "Half-Life 2 RTX", &rm, |m| { tmp.push_str(m); tmp.push('\n'); });
-			app.append_global_log(&tmp);
+			// Games installed next to the configured GMod folder share its Steam library
+			let install_path = app.mount.mount_install_path.clone().or_else(|| {
+				app.settings.manually_specified_install_path.as_ref()
+					.and_then(|p| std::path::Path::new(p).parent().map(|parent| parent.join(&inf)))
+					.filter(|p| p.exists())
+			});
+			// On Linux a custom Steam root (e.g. a Flatpak or external library used with Proton) is not searched by default
+			#[cfg(unix)]
+			let install_path = install_path.or_else(|| {
+				app.settings.linux_steam_root_override.as_ref()
+					.map(|root| std::path::Path::new(root).join("steamapps").join("common").join(&inf))
+					.filter(|p| p.exists())
+			});
+			let base = app.settings.install_dir();
+			let (tx, failures) = app.mount.begin_job();
+			app.runtime.spawn_blocking(move || {
+				let _lock = lock;
+				// Hold the job open past "Mount complete" so packages can be extracted afterwards
+				let result = mount_game(&gf, &inf, &rm, install_path.as_deref(), |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p.min(99) }); });
+				match result {
+					Ok(()) if !rm.is_empty() && has_rtxio_packages(&base, &rm) => run_rtxio_extraction(&base, &rm, &tx, &failures),
+					Ok(()) => { let _ = tx.send(JobProgress { message: "Mount complete".to_string(), percent: 100 }); }
+					Err(e) => crate::app::report_failure(&tx, &failures, e, "Mount"),
+				}
+			});
 		}
-		if ui.button("Unmount").clicked() {
+		if crate::app::job_lock_hover(ui.add_enabled(unlocked, egui::Button::new("Unmount"))).clicked() {
+			let Some(lock) = acquire_lock(app, "Unmounting") else { return; };
 			let gf = app.mount.mount_game_folder.clone();
 			let rm = app.mount.mount_remix_mod.clone();
-			let mut tmp = String::new();
-			let _ = unmount_game(&gf, "Half-Life 2 RTX", &rm, |m| { tmp.push_str(m); tmp.push('\n'); });
-			app.append_global_log(&tmp);
+			let (tx, failures) = app.mount.begin_job();
+			app.runtime.spawn_blocking(move || {
+				let _lock = lock;
+				let result = unmount_game(&gf, &rm, |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p.min(99) }); });
+				match result {
+					Ok(()) => { let _ = tx.send(JobProgress { message: "Unmount complete".to_string(), percent: 100 }); }
+					Err(e) => crate::app::report_failure(&tx, &failures, e, "Unmount"),
+				}
+			});
 		}
 		ui.separator();
-		if ui.button("Apply USDA fixes for hl2rtx").clicked() {
-			let (tx, rx) = std::sync::mpsc::channel::<rtxlauncher_core::JobProgress>();
-			app.mount.current_job = Some(rx);
-			app.mount.is_running = true;
-			std::thread::spawn(move || {
-				let rt = tokio::runtime::Runtime::new().unwrap();
-				rt.block_on(async move {
-					let base = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default();
-					let _ = apply_usda_fixes(&base, "hl2rtx", |m,p| { let _ = tx.send(rtxlauncher_core::JobProgress { message: m.to_string(), percent: p }); }).await;
-				});
+		let mounts = app.mount.mounts.get_or_insert_with(list_mounts).clone();
+		ui.label("Currently mounted:");
+		if mounts.is_empty() { ui.label("(nothing)"); }
+		for m in &mounts { ui.monospace(format!("• {}", m)); }
+		if crate::app::job_lock_hover(ui.add_enabled(unlocked && !mounts.is_empty(), egui::Button::new("Unmount everything"))).clicked() {
+			let Some(lock) = acquire_lock(app, "Unmounting") else { return; };
+			let (tx, failures) = app.mount.begin_job();
+			app.runtime.spawn_blocking(move || {
+				let _lock = lock;
+				match unmount_all(|m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p.min(99) }); }) {
+					Ok(n) => { let _ = tx.send(JobProgress { message: format!("Unmounted {} game(s)", n), percent: 100 }); }
+					Err(e) => crate::app::report_failure(&tx, &failures, e, "Unmount"),
+				}
 			});
 		}
+		ui.separator();
+		let rm = app.mount.mount_remix_mod.clone();
+		if crate::app::job_lock_hover(ui.add_enabled(unlocked && !rm.is_empty(), egui::Button::new("Extract RTXIO packages"))
+			.on_hover_text("Unpack the .pkg files in the selected remix mod folder")).clicked() {
+			let Some(lock) = acquire_lock(app, "RTXIO extraction") else { return; };
+			let (tx, failures) = app.mount.begin_job();
+			let base = app.settings.install_dir();
+			app.runtime.spawn_blocking(move || {
+				let _lock = lock;
+				if has_rtxio_packages(&base, &rm) { run_rtxio_extraction(&base, &rm, &tx, &failures); }
+				else { let _ = tx.send(JobProgress { message: format!("No RTXIO packages found for {}", rm), percent: 100 }); }
+			});
+		}
+		ui.horizontal(|ui| {
+			for &(remix_mod, _) in USDA_FIXES_SOURCES {
+				if crate::app::job_lock_hover(ui.add_enabled(unlocked, egui::Button::new(format!("Apply USDA fixes for {}", remix_mod)))).clicked() {
+					let Some(lock) = acquire_lock(app, "USDA fixes") else { return; };
+					let (tx, failures) = app.mount.begin_job();
+					let base = app.settings.install_dir();
+					crate::app::spawn_task(&app.runtime, move || async move {
+						let _lock = lock;
+						match apply_usda_fixes(&base, remix_mod, |m,p| { let _ = tx.send(rtxlauncher_core::JobProgress { message: m.to_string(), percent: p.min(99) }); }).await {
+							Ok(true) => { let _ = tx.send(JobProgress { message: format!("USDA fixes for {} applied", remix_mod), percent: 100 }); }
+							// The reason was already logged as a progress line
+							Ok(false) => crate::app::report_failure(&tx, &failures, anyhow::anyhow!("did not complete; see the Logs tab"), "USDA fixes"),
+							Err(e) => crate::app::report_failure(&tx, &failures, e, "USDA fixes"),
+						}
+					});
+				}
+			}
+		});
 	});
 }
 
@@ -1,59 +1,370 @@
 use eframe::egui;
-use rtxlauncher_core::{GitHubRelease, JobProgress, fetch_releases, GitHubRateLimit, install_remix_from_release, install_fixes_from_release, apply_patches_from_repo};
+use rtxlauncher_core::{GitHubAsset, GitHubRelease, JobProgress, fetch_releases, GitHubRateLimit, install_remix_from_release, install_remix_asset, select_best_asset, select_best_package_asset, install_fixes_from_release, uninstall_fixes, apply_patches_from_repo, SourceLists, patch_report_path, reapply_failed, restore_patch_backup, PatchOptions, PatchResult, CancellationToken, ConflictAction, FileConflict, LauncherError, JobEvent, ProgressSink, AppSettings, SettingsStore, JobLock};
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RepoJob { Remix, Fixes, Patches, UninstallFixes, RestoreBackup }
+
+// Modified files the fixes install is waiting on; the reply carries one action per file, in order
+pub struct FixesConflicts {
+	pub files: Vec<FileConflict>,
+	pub reply: std::sync::mpsc::Sender<Vec<ConflictAction>>,
+}
+
+// How often a fixes install waiting on the conflict dialog checks for cancellation
+const CONFLICT_POLL: std::time::Duration = std::time::Duration::from_millis(200);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseKind { Remix, Fixes }
+
+// Source picker and release list for one of the release-based sections
+#[derive(Default)]
+pub struct ReleaseList {
+	pub source_idx: usize,
+	// Everything fetched for the source; `releases` is what the dropdown shows
+	pub fetched: Vec<GitHubRelease>,
+	pub releases: Vec<GitHubRelease>,
+	pub selected: usize,
+	pub loading: bool,
+	// Long release notes start collapsed; this shows them in full
+	pub notes_expanded: bool,
+	// Asset picked under "Advanced: choose asset"; None installs the auto-selected one
+	pub asset_override: Option<String>,
+}
+
+#[derive(Default)]
 pub struct RepositoriesState {
 	pub is_running: bool,
 	pub current_job: Option<std::sync::mpsc::Receiver<JobProgress>>,
 	pub progress: u8,
-	pub remix_source_idx: usize,
-	pub remix_releases: Vec<GitHubRelease>,
-	pub remix_release_idx: usize,
-	pub remix_rx: Option<std::sync::mpsc::Receiver<Vec<GitHubRelease>>>,
-	pub remix_loading: bool,
-	pub fixes_source_idx: usize,
-	pub fixes_releases: Vec<GitHubRelease>,
-	pub fixes_release_idx: usize,
-	pub fixes_rx: Option<std::sync::mpsc::Receiver<Vec<GitHubRelease>>>,
-	pub fixes_loading: bool,
+	pub remix: ReleaseList,
+	pub fixes: ReleaseList,
+	// Fetched lists tagged with what they are for and the source index they were fetched from
+	releases_tx: Option<std::sync::mpsc::Sender<(ReleaseKind, usize, Vec<GitHubRelease>)>>,
+	releases_rx: Option<std::sync::mpsc::Receiver<(ReleaseKind, usize, Vec<GitHubRelease>)>>,
 	pub patch_source_idx: usize,
+	// Branch override for the patch script; empty uses main, then master
+	pub patch_branch: String,
+	// Local applypatch.py to use instead of fetching one
+	pub patch_script: Option<std::path::PathBuf>,
+	pub active_job: Option<RepoJob>,
+	pub cancel: Option<CancellationToken>,
+	pub patch_result_rx: Option<std::sync::mpsc::Receiver<PatchResult>>,
+	pub last_patch_result: Option<PatchResult>,
+	pub patch_preview: bool,
+	pub conflict_rx: Option<std::sync::mpsc::Receiver<FixesConflicts>>,
+	// Conflicts shown in the dialog, with the action picked for each file
+	pub pending_conflicts: Option<(FixesConflicts, Vec<ConflictAction>)>,
+	pub failure_rx: Option<std::sync::mpsc::Receiver<LauncherError>>,
+	pub event_rx: Option<std::sync::mpsc::Receiver<JobEvent>>,
+	pub stage: Option<String>,
+	pub bytes: Option<(u64, u64)>,
+	pub files_extracted: usize,
 }
 
-impl Default for RepositoriesState {
-	fn default() -> Self {
-		Self {
-			is_running: false,
-			current_job: None,
-			progress: 0,
-			remix_source_idx: 0,
-			remix_releases: Vec::new(),
-			remix_release_idx: 0,
-			remix_rx: None,
-			remix_loading: false,
-			fixes_source_idx: 0,
-			fixes_releases: Vec::new(),
-			fixes_release_idx: 0,
-			fixes_rx: None,
-			fixes_loading: false,
-			patch_source_idx: 0,
-		}
-	}
+// Installer output for a repositories job: log lines and percent, plus structured events
+#[derive(Clone)]
+struct JobSink {
+	tx: std::sync::mpsc::Sender<JobProgress>,
+	events: std::sync::mpsc::Sender<JobEvent>,
+}
+
+impl ProgressSink for JobSink {
+	fn progress(&mut self, message: &str, percent: u8) { let _ = self.tx.send(JobProgress { message: message.to_string(), percent }); }
+	fn event(&mut self, event: JobEvent) { let _ = self.events.send(event); }
 }
 
 impl RepositoriesState {
-	pub fn poll_job(&mut self, global_log: &mut String) -> bool {
+	pub fn poll_job(&mut self, global_log: &mut crate::app::LogSink) -> bool {
 		if self.current_job.is_none() { return false; }
+		if let Some(rx) = &self.event_rx {
+			while let Ok(ev) = rx.try_recv() {
+				match ev {
+					JobEvent::Stage(name) => { self.stage = Some(name); self.bytes = None; }
+					JobEvent::Bytes { done, total } => self.bytes = Some((done, total)),
+					JobEvent::FileExtracted(_) => self.files_extracted += 1,
+					JobEvent::Warning(_) | JobEvent::Done => {}
+				}
+			}
+		}
 		let mut finished = false;
 		if let Some(rx) = self.current_job.take() {
 			while let Ok(p) = rx.try_recv() {
 				self.progress = p.percent;
-				// Append to global log (deduplicated)
-				crate::app::append_line_dedup(global_log, &p.message);
+				global_log.push(&p.message);
 				if p.percent >= 100 { self.is_running = false; finished = true; }
 			}
 			if !finished { self.current_job = Some(rx); }
 		}
+		if finished { self.active_job = None; self.cancel = None; }
 		finished
 	}
+
+	// Re-derive the dropdown lists after the pre-release toggle changes
+	fn refilter(&mut self, settings: &rtxlauncher_core::AppSettings) {
+		let remix = std::mem::take(&mut self.remix.fetched);
+		self.remix.set_releases(remix, settings.show_prereleases, settings.remix_pinned_tag.as_deref());
+		let fixes = std::mem::take(&mut self.fixes.fetched);
+		self.fixes.set_releases(fixes, settings.show_prereleases, settings.fixes_pinned_tag.as_deref());
+	}
+
+	fn release_list(&mut self, kind: ReleaseKind) -> &mut ReleaseList {
+		match kind { ReleaseKind::Remix => &mut self.remix, ReleaseKind::Fixes => &mut self.fixes }
+	}
+
+	/// Start fetching every release list that is empty and not already loading; called at startup
+	/// so the tab has its lists by the time it is opened.
+	pub fn fetch_missing_releases(&mut self, runtime: &crate::app::JobRuntime, sources: &SourceLists) {
+		let missing: Vec<ReleaseKind> = [ReleaseKind::Remix, ReleaseKind::Fixes].into_iter()
+			.filter(|k| { let l = self.release_list(*k); !l.loading && l.fetched.is_empty() })
+			.collect();
+		if !missing.is_empty() { start_fetch_releases(self, runtime, sources, &missing); }
+	}
+
+	fn begin_job(&mut self, kind: RepoJob, runtime: &crate::app::JobRuntime) -> (JobSink, CancellationToken, std::sync::mpsc::Sender<LauncherError>) {
+		let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
+		let (events, event_rx) = std::sync::mpsc::channel();
+		let (failure_tx, failure_rx) = std::sync::mpsc::channel();
+		let token = runtime.cancel_token();
+		self.current_job = Some(rx);
+		self.event_rx = Some(event_rx);
+		self.failure_rx = Some(failure_rx);
+		self.stage = None;
+		self.bytes = None;
+		self.files_extracted = 0;
+		self.is_running = true;
+		self.active_job = Some(kind);
+		self.cancel = Some(token.clone());
+		(JobSink { tx, events }, token, failure_tx)
+	}
+
+	/// Progress bar text: the current stage with its byte or file count, then the percentage.
+	pub fn progress_text(&self) -> String {
+		let detail = match (&self.bytes, self.files_extracted) {
+			(Some((done, total)), _) if *total > 0 => format!(" · {}/{} MB", done / 1_048_576, total / 1_048_576),
+			(_, n) if n > 0 => format!(" · {} files", n),
+			_ => String::new(),
+		};
+		match &self.stage {
+			Some(stage) => format!("{}{} · {}%", stage, detail, self.progress),
+			None => format!("{}%", self.progress),
+		}
+	}
+
+	// Renders a Cancel button in place of the action button while `kind` is the running job
+	fn cancel_button(&self, ui: &mut egui::Ui, kind: RepoJob) -> bool {
+		if self.active_job != Some(kind) { return false; }
+		let cancelling = self.cancel.as_ref().map(|t| t.is_cancelled()).unwrap_or(false);
+		if ui.add_enabled(!cancelling, egui::Button::new(if cancelling { "Cancelling..." } else { "Cancel" })).clicked() {
+			if let Some(t) = &self.cancel { t.cancel(); }
+		}
+		true
+	}
+}
+
+// Ask once about every file the user modified since the last fixes install while the worker waits;
+// each file gets its own action, with buttons to set them all at once
+pub fn render_conflict_dialog(st: &mut RepositoriesState, ctx: &egui::Context) {
+	// The worker stops waiting once the job is cancelled or over
+	if !st.is_running || st.cancel.as_ref().is_some_and(|t| t.is_cancelled()) { st.pending_conflicts = None; }
+	if st.pending_conflicts.is_none() {
+		if let Some(c) = st.conflict_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+			let actions = vec![ConflictAction::Backup; c.files.len()];
+			st.pending_conflicts = Some((c, actions));
+		}
+	}
+	let Some((conflicts, actions)) = &mut st.pending_conflicts else { return; };
+	let mut done = false;
+	let mut cancel = false;
+	egui::Window::new("Modified Files").collapsible(false).resizable(true).show(ctx, |ui| {
+		ui.label(format!("{} file(s) were changed since the fixes package was last installed:", conflicts.files.len()));
+		ui.horizontal(|ui| {
+			ui.label("Apply to all:");
+			if ui.button("Backup all").on_hover_text("Save your versions as .bak, then overwrite").clicked() { actions.fill(ConflictAction::Backup); }
+			if ui.button("Overwrite all").clicked() { actions.fill(ConflictAction::Overwrite); }
+			if ui.button("Skip all").on_hover_text("Keep your versions").clicked() { actions.fill(ConflictAction::Skip); }
+		});
+		ui.separator();
+		egui::ScrollArea::vertical().max_height(300.0).auto_shrink([false, true]).show(ui, |ui| {
+			egui::Grid::new("fixes_conflicts").num_columns(3).striped(true).show(ui, |ui| {
+				for (file, action) in conflicts.files.iter().zip(actions.iter_mut()) {
+					ui.monospace(&file.path);
+					ui.label(chrono::DateTime::<chrono::Local>::from(file.modified).format("%Y-%m-%d %H:%M").to_string());
+					ui.horizontal(|ui| {
+						ui.selectable_value(action, ConflictAction::Backup, "Backup");
+						ui.selectable_value(action, ConflictAction::Overwrite, "Overwrite");
+						ui.selectable_value(action, ConflictAction::Skip, "Skip");
+					});
+					ui.end_row();
+				}
+			});
+		});
+		ui.separator();
+		ui.horizontal(|ui| {
+			if ui.button("Continue").clicked() { done = true; }
+			if ui.button("Cancel install").clicked() { cancel = true; }
+		});
+	});
+	if cancel {
+		if let Some(t) = &st.cancel { t.cancel(); }
+		st.pending_conflicts = None;
+	} else if done {
+		if let Some((c, actions)) = st.pending_conflicts.take() { let _ = c.reply.send(actions); }
+	}
+}
+
+/// A job started from this tab, with what it needs to be started again from the error dialog.
+#[derive(Clone)]
+pub enum RepoRequest {
+	Remix { release: GitHubRelease, asset: Option<GitHubAsset>, source: String },
+	Fixes { release: GitHubRelease, source: String },
+	/// `retry_failed` reruns only the files the last patch report lists as failed
+	Patches { owner: String, repo: String, options: PatchOptions, retry_failed: bool },
+	UninstallFixes,
+	/// Put back the binaries saved by the newest patch backup
+	RestorePatchBackup,
+}
+
+/// Spawn `req` as the tab's current job; on success the installed version is recorded in settings.
+pub(crate) fn start_request(st: &mut RepositoriesState, runtime: &crate::app::JobRuntime, settings_store: &SettingsStore, settings: &AppSettings, req: RepoRequest) {
+	let settings_store = settings_store.clone();
+	let mut settings = settings.clone();
+	let release_name = |rel: &GitHubRelease| rel.name.clone().unwrap_or_else(|| rel.tag_name.clone().unwrap_or_default());
+	let operation = match &req {
+		RepoRequest::Remix { .. } => "RTX Remix install",
+		RepoRequest::Fixes { .. } => "Fixes install",
+		RepoRequest::Patches { options, .. } if options.dry_run => "Patch preview",
+		RepoRequest::Patches { .. } => "Patching",
+		RepoRequest::UninstallFixes => "Fixes uninstall",
+		RepoRequest::RestorePatchBackup => "Patch backup restore",
+	};
+	let lock = match JobLock::acquire(operation) {
+		Ok(lock) => lock,
+		Err(e) => {
+			// Shown by the tab's failure poll, like a job that failed right away
+			let (failure_tx, failure_rx) = std::sync::mpsc::channel();
+			let _ = failure_tx.send(e);
+			st.failure_rx = Some(failure_rx);
+			return;
+		}
+	};
+	match req {
+		RepoRequest::Remix { release: rel, asset, source } => {
+			let (sink, cancel, failures) = st.begin_job(RepoJob::Remix, runtime);
+			let rel_name = release_name(&rel);
+			crate::app::spawn_task(runtime, move || async move {
+				let _lock = lock;
+				let base = settings.install_dir();
+				let result = match &asset {
+					Some(asset) => install_remix_asset(&rel, asset, &base, &cancel, sink.clone()).await,
+					None => install_remix_from_release(&rel, &base, &cancel, sink.clone()).await,
+				};
+				match result {
+					Ok(()) => {
+						settings.installed_remix_version = Some(rel_name);
+						settings.installed_remix_at = Some(crate::app::now_rfc3339());
+						settings.installed_remix_source = Some(source);
+						let _ = settings_store.save(&settings);
+					}
+					Err(e) => crate::app::report_failure(&sink.tx, &failures, e, "Remix install"),
+				}
+			});
+		}
+		RepoRequest::Fixes { release: rel, source } => {
+			let (sink, cancel, failures) = st.begin_job(RepoJob::Fixes, runtime);
+			let rel_name = release_name(&rel);
+			let (conflict_tx, conflict_rx) = std::sync::mpsc::channel::<FixesConflicts>();
+			st.conflict_rx = Some(conflict_rx);
+			st.pending_conflicts = None;
+			crate::app::spawn_task(runtime, move || async move {
+				let _lock = lock;
+				let base = settings.install_dir();
+				// No answer (cancelled, or the dialog went away) keeps the user's files
+				let mut on_conflict = |files: &[FileConflict]| {
+					let (reply, answer) = std::sync::mpsc::channel();
+					if conflict_tx.send(FixesConflicts { files: files.to_vec(), reply }).is_err() { return Vec::new(); }
+					// Wake up now and then so Cancel works while the dialog is open
+					loop {
+						match answer.recv_timeout(CONFLICT_POLL) {
+							Ok(actions) => return actions,
+							Err(std::sync::mpsc::RecvTimeoutError::Timeout) if !cancel.is_cancelled() => {}
+							Err(_) => return Vec::new(),
+						}
+					}
+				};
+				let result = install_fixes_from_release(&rel, &base, Some(rtxlauncher_core::DEFAULT_IGNORE_PATTERNS), Some(&mut on_conflict), &cancel, sink.clone()).await;
+				match result {
+					Ok(()) => {
+						settings.installed_fixes_version = Some(rel_name);
+						settings.installed_fixes_at = Some(crate::app::now_rfc3339());
+						settings.installed_fixes_source = Some(source);
+						let _ = settings_store.save(&settings);
+					}
+					Err(e) => crate::app::report_failure(&sink.tx, &failures, e, "Fixes install"),
+				}
+			});
+		}
+		RepoRequest::Patches { owner, repo, options, retry_failed } => {
+			let (sink, cancel, failures) = st.begin_job(RepoJob::Patches, runtime);
+			let install_dir = settings.install_dir();
+			let patch_info = format!("{}/{}", &owner, &repo);
+			let (result_tx, result_rx) = std::sync::mpsc::channel::<PatchResult>();
+			st.patch_result_rx = Some(result_rx);
+			st.patch_preview = options.dry_run;
+			crate::app::spawn_task(runtime, move || async move {
+				let _lock = lock;
+				let on_progress = |m: &str, p: u8| { let _ = sink.tx.send(JobProgress { message: m.to_string(), percent: p }); };
+				let result = if retry_failed {
+					reapply_failed(&owner, &repo, "applypatch.py", &install_dir, &options, &cancel, on_progress).await
+				} else {
+					apply_patches_from_repo(&owner, &repo, "applypatch.py", &install_dir, &options, &cancel, on_progress).await
+				};
+				match result {
+					Ok(r) => {
+						if !options.dry_run {
+							settings.installed_patches_at = Some(crate::app::now_rfc3339());
+							settings.installed_patches_source = Some(patch_info.clone());
+							settings.installed_patches_commit = Some(patch_info);
+							let _ = settings_store.save(&settings);
+						}
+						let _ = result_tx.send(r);
+					}
+					Err(e) => crate::app::report_failure(&sink.tx, &failures, e, "Patching"),
+				}
+			});
+		}
+		RepoRequest::UninstallFixes => {
+			let (sink, _, failures) = st.begin_job(RepoJob::UninstallFixes, runtime);
+			runtime.spawn_blocking(move || {
+				let _lock = lock;
+				let message = match uninstall_fixes(&settings.install_dir()) {
+					Ok(Some(r)) => {
+						settings.installed_fixes_version = None;
+						settings.installed_fixes_at = None;
+						settings.installed_fixes_source = None;
+						let _ = settings_store.save(&settings);
+						let mut msg = format!("Removed {} fixes file(s)", r.removed);
+						if !r.kept_modified.is_empty() { msg.push_str(&format!("; kept {} modified: {}", r.kept_modified.len(), r.kept_modified.join(", "))); }
+						msg
+					}
+					Ok(None) => "No fixes install manifest found; nothing to uninstall".to_string(),
+					Err(e) => return crate::app::report_failure(&sink.tx, &failures, e, "Fixes uninstall"),
+				};
+				let _ = sink.tx.send(JobProgress { message, percent: 100 });
+			});
+		}
+		RepoRequest::RestorePatchBackup => {
+			let (sink, _, failures) = st.begin_job(RepoJob::RestoreBackup, runtime);
+			runtime.spawn_blocking(move || {
+				let _lock = lock;
+				let message = match restore_patch_backup(&settings.install_dir()) {
+					Ok(Some((dir, n))) => format!("Restored {} file(s) from {}", n, dir.display()),
+					Ok(None) => "No patch backups found".to_string(),
+					Err(e) => return crate::app::report_failure(&sink.tx, &failures, e, "Patch backup restore"),
+				};
+				let _ = sink.tx.send(JobProgress { message, percent: 100 });
+			});
+		}
+	}
 }
 
 pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
@@ -61,10 +372,10 @@ pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui:
 	let job_finished = {
 		let st = &mut app.repositories;
 		let finished = st.poll_job(&mut app.log);
-		if !st.remix_loading && st.remix_releases.is_empty() { start_fetch_releases(true, st); }
-		if !st.fixes_loading && st.fixes_releases.is_empty() { start_fetch_releases(false, st); }
+		st.fetch_missing_releases(&app.runtime, &app.sources);
 		finished
 	};
+	if let Some(e) = app.repositories.failure_rx.as_ref().and_then(|rx| rx.try_recv().ok()) { app.show_job_error(&e); }
 	if job_finished {
 		// Reload settings when a job finishes to update version info
 		if let Ok(new_settings) = app.settings_store.load() {
@@ -72,16 +383,27 @@ pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui:
 		}
 	}
 
-	ui.heading("Repositories");
+	ui.horizontal(|ui| {
+		ui.heading("Repositories");
+		if rtxlauncher_core::is_offline() {
+			ui.colored_label(egui::Color32::from_rgb(200,140,0), "Offline mode").on_hover_text("GitHub could not be reached; release lists are from the local cache and may be out of date");
+		}
+	});
+	if ui.checkbox(&mut app.settings.show_prereleases, "Show pre-releases").on_hover_text("List pre-releases in the version dropdowns; quick install also picks the newest one").changed() {
+		let _ = app.settings_store.save(&app.settings);
+		app.repositories.refilter(&app.settings);
+	}
+	crate::app::job_lock_notice(ui);
 	ui.separator();
+	// Nothing that writes into the install starts while another such job holds the lock
+	let idle = !app.repositories.is_running && JobLock::holder().is_none();
 
 	egui::ScrollArea::vertical().id_salt("repos-sections").auto_shrink([false, false]).show(ui, |ui| {
 					// Base Game Updates (collapsible)
 					{
-						let st = &mut app.repositories;
 						let mut trigger_update = false;
 						egui::CollapsingHeader::new("Base Game Updates").default_open(false).show(ui, |ui| {
-							if ui.add_enabled(!st.is_running, egui::Button::new("Update Base Game")).clicked() { trigger_update = true; }
+							if ui.add_enabled(idle, egui::Button::new("Update Base Game")).clicked() { trigger_update = true; }
 						});
 						if trigger_update { app.prepare_update_dialog(); app.show_update_dialog = true; }
 					}
@@ -92,58 +414,51 @@ pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui:
 					{
 						let st = &mut app.repositories;
 						egui::CollapsingHeader::new("NVIDIA RTX Remix").default_open(false).show(ui, |ui| {
-							let remix_sources: [(&str, &str, &str); 2] = [
-								("sambow23/dxvk-remix-gmod", "sambow23", "dxvk-remix-gmod"),
-								("(OFFICIAL) NVIDIAGameWorks/rtx-remix", "NVIDIAGameWorks", "rtx-remix"),
-							];
+							let remix_sources = &app.sources.remix;
 							ui.horizontal(|ui| {
 								ui.label("Source");
-								egui::ComboBox::from_id_salt("remix-source").selected_text(remix_sources[st.remix_source_idx].0).show_ui(ui, |ui| {
-									for (i, (label, _, _)) in remix_sources.iter().enumerate() {
-										if ui.selectable_label(st.remix_source_idx == i, *label).clicked() { st.remix_source_idx = i; start_fetch_releases(true, st); }
+								egui::ComboBox::from_id_salt("remix-source").selected_text(SourceLists::pick(remix_sources, st.remix.source_idx).label()).show_ui(ui, |ui| {
+									for (i, s) in remix_sources.iter().enumerate() {
+										if ui.selectable_label(st.remix.source_idx == i, s.label()).clicked() { st.remix.source_idx = i; start_fetch_releases(st, &app.runtime, &app.sources, &[ReleaseKind::Remix]); }
 									}
 								});
 							});
 							ui.horizontal(|ui| {
 								ui.label("Version");
 								let label = |r: &GitHubRelease| r.name.clone().unwrap_or_else(|| r.tag_name.clone().unwrap_or_default());
-								let selected_text = if st.remix_releases.is_empty() { if st.remix_loading { "Loading...".to_string() } else { "No releases".to_string() } } else { label(&st.remix_releases[st.remix_release_idx.min(st.remix_releases.len()-1)]) };
+								let selected_text = if st.remix.releases.is_empty() { if st.remix.loading { "Loading...".to_string() } else if !st.remix.fetched.is_empty() { "No stable releases".to_string() } else { "No releases".to_string() } } else { label(&st.remix.releases[st.remix.selected.min(st.remix.releases.len()-1)]) };
 								egui::ComboBox::from_id_salt("remix-version").selected_text(selected_text).show_ui(ui, |ui| {
-									for (i, r) in st.remix_releases.iter().enumerate() {
-										let text = label(r);
-										if ui.selectable_label(st.remix_release_idx == i, text).clicked() { st.remix_release_idx = i; }
+									for (i, r) in st.remix.releases.iter().enumerate() {
+										let text = dated_label(r);
+										if ui.selectable_label(st.remix.selected == i, text).clicked() {
+											st.remix.selected = i;
+											st.remix.notes_expanded = false;
+											st.remix.asset_override = None;
+											// Picking the newest release follows future releases; anything older is pinned
+											app.settings.remix_pinned_tag = if i == 0 { None } else { r.tag_name.clone() };
+											let _ = app.settings_store.save(&app.settings);
+										}
 									}
 								});
-								if st.remix_loading { ui.add(egui::Spinner::new()); }
-								if ui.add_enabled(!st.is_running && !st.remix_releases.is_empty(), egui::Button::new("Install/Update")).clicked() {
-									let rel = st.remix_releases[st.remix_release_idx].clone();
-									let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
-									st.current_job = Some(rx);
-									st.is_running = true;
-									let rel_name = rel.name.clone().unwrap_or_else(|| rel.tag_name.clone().unwrap_or_default());
-									let settings_store = app.settings_store.clone();
-									let mut settings = app.settings.clone();
-									std::thread::spawn(move || {
-										let rt = tokio::runtime::Runtime::new().unwrap();
-										rt.block_on(async move {
-											let base = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default();
-											let result = install_remix_from_release(&rel, &base, |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p }); }).await;
-											if result.is_ok() {
-												settings.installed_remix_version = Some(rel_name);
-												let _ = settings_store.save(&settings);
-											}
-										});
-									});
+								if st.remix.loading { ui.add(egui::Spinner::new()); }
+								let installable = st.remix.releases.get(st.remix.selected).is_some_and(|r| st.remix.asset_override.is_some() || select_best_asset(r, app.settings.install_dir().join("bin").join("win64").exists()).is_some());
+								if !st.cancel_button(ui, RepoJob::Remix) && ui.add_enabled(idle && installable, egui::Button::new("Install/Update")).clicked() {
+									let rel = st.remix.releases[st.remix.selected].clone();
+									let asset = st.remix.asset_override.as_ref().and_then(|n| rel.assets.iter().find(|a| &a.name == n)).cloned();
+									let req = RepoRequest::Remix { release: rel, asset, source: SourceLists::pick(remix_sources, st.remix.source_idx).slug() };
+									app.last_job = Some(crate::app::RetryJob::Repo(req.clone()));
+									start_request(st, &app.runtime, &app.settings_store, &app.settings, req);
 								}
 							});
 							// details panel
-							if let Some(rel) = st.remix_releases.get(st.remix_release_idx) {
+							if let Some(rel) = st.remix.releases.get(st.remix.selected) {
 								ui.separator();
 								let name = rel.name.clone().unwrap_or_else(|| rel.tag_name.clone().unwrap_or_default());
 								let prerelease = rel.prerelease.unwrap_or(false);
 								ui.horizontal(|ui| {
 									ui.label(format!("Selected: {}", name));
 									if prerelease { ui.colored_label(egui::Color32::YELLOW, "pre-release"); }
+									if let Some(date) = rel.published_date() { ui.weak(format!("published {}", date)); }
 									let installed = app.settings.installed_remix_version.clone().unwrap_or_default();
 									if !installed.is_empty() {
 										let up_to_date = installed == name;
@@ -152,8 +467,12 @@ pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui:
 										ui.label(format!("Installed: {}", installed));
 									}
 								});
+								let is64 = app.settings.install_dir().join("bin").join("win64").exists();
+								let auto = select_best_asset(rel, is64);
+								if auto.is_none() && st.remix.asset_override.is_none() { ui.colored_label(egui::Color32::from_rgb(200,140,0), "This release has no installable asset; choose one below or pick another release."); }
+								render_asset_picker(ui, rel, auto, &mut st.remix.asset_override);
 								if let Some(body) = &rel.body {
-									egui::ScrollArea::vertical().id_salt("remix-md").max_height(200.0).auto_shrink([false, true]).show(ui, |ui| { render_simple_markdown(ui, body); });
+									render_release_notes(ui, "remix-md", body, &mut st.remix.notes_expanded);
 								}
 							}
 						});
@@ -165,54 +484,50 @@ pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui:
 					{
 						let st = &mut app.repositories;
 						egui::CollapsingHeader::new("Fixes Package").default_open(false).show(ui, |ui| {
-							let fixes_sources: [(&str, &str, &str); 2] = [
-								("Xenthio/gmod-rtx-fixes-2 (Any)", "Xenthio", "gmod-rtx-fixes-2"),
-								("Xenthio/RTXFixes (gmod_main)", "Xenthio", "RTXFixes"),
-							];
+							let fixes_sources = &app.sources.fixes;
 							ui.horizontal(|ui| {
 								ui.label("Source");
-								egui::ComboBox::from_id_salt("fixes-source").selected_text(fixes_sources[st.fixes_source_idx].0).show_ui(ui, |ui| {
-									for (i, (label, _, _)) in fixes_sources.iter().enumerate() { if ui.selectable_label(st.fixes_source_idx == i, *label).clicked() { st.fixes_source_idx = i; start_fetch_releases(false, st); } }
+								egui::ComboBox::from_id_salt("fixes-source").selected_text(SourceLists::pick(fixes_sources, st.fixes.source_idx).label()).show_ui(ui, |ui| {
+									for (i, s) in fixes_sources.iter().enumerate() { if ui.selectable_label(st.fixes.source_idx == i, s.label()).clicked() { st.fixes.source_idx = i; start_fetch_releases(st, &app.runtime, &app.sources, &[ReleaseKind::Fixes]); } }
 								});
 							});
 							ui.horizontal(|ui| {
 								ui.label("Version");
 								let label = |r: &GitHubRelease| r.name.clone().unwrap_or_else(|| r.tag_name.clone().unwrap_or_default());
-								let selected_text = if st.fixes_releases.is_empty() { if st.fixes_loading { "Loading...".to_string() } else { "No packages".to_string() } } else { label(&st.fixes_releases[st.fixes_release_idx.min(st.fixes_releases.len()-1)]) };
+								let selected_text = if st.fixes.releases.is_empty() { if st.fixes.loading { "Loading...".to_string() } else if !st.fixes.fetched.is_empty() { "No stable packages".to_string() } else { "No packages".to_string() } } else { label(&st.fixes.releases[st.fixes.selected.min(st.fixes.releases.len()-1)]) };
 								egui::ComboBox::from_id_salt("fixes-version").selected_text(selected_text).show_ui(ui, |ui| {
-									for (i, r) in st.fixes_releases.iter().enumerate() {
-										let text = label(r);
-										if ui.selectable_label(st.fixes_release_idx == i, text).clicked() { st.fixes_release_idx = i; }
+									for (i, r) in st.fixes.releases.iter().enumerate() {
+										let text = dated_label(r);
+										if ui.selectable_label(st.fixes.selected == i, text).clicked() {
+											st.fixes.selected = i;
+											st.fixes.notes_expanded = false;
+											// Picking the newest release follows future releases; anything older is pinned
+											app.settings.fixes_pinned_tag = if i == 0 { None } else { r.tag_name.clone() };
+											let _ = app.settings_store.save(&app.settings);
+										}
 									}
 								});
-								if st.fixes_loading { ui.add(egui::Spinner::new()); }
-								if ui.add_enabled(!st.is_running && !st.fixes_releases.is_empty(), egui::Button::new("Install/Update")).clicked() {
-									let rel = st.fixes_releases[st.fixes_release_idx].clone();
-									let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
-									st.current_job = Some(rx);
-									st.is_running = true;
-									let rel_name = rel.name.clone().unwrap_or_else(|| rel.tag_name.clone().unwrap_or_default());
-									let settings_store = app.settings_store.clone();
-									let mut settings = app.settings.clone();
-									std::thread::spawn(move || { 
-										let rt = tokio::runtime::Runtime::new().unwrap(); 
-										rt.block_on(async move { 
-											let base = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default(); 
-											let result = install_fixes_from_release(&rel, &base, Some(crate::app::DEFAULT_IGNORE_PATTERNS), |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p }); }).await; 
-											if result.is_ok() {
-												settings.installed_fixes_version = Some(rel_name);
-												let _ = settings_store.save(&settings);
-											}
-										}); 
-									});
+								if st.fixes.loading { ui.add(egui::Spinner::new()); }
+								let installable = st.fixes.releases.get(st.fixes.selected).is_some_and(|r| select_best_package_asset(r).is_some());
+								if !st.cancel_button(ui, RepoJob::Fixes) && ui.add_enabled(idle && installable, egui::Button::new("Install/Update")).clicked() {
+									let rel = st.fixes.releases[st.fixes.selected].clone();
+									let req = RepoRequest::Fixes { release: rel, source: SourceLists::pick(fixes_sources, st.fixes.source_idx).slug() };
+									app.last_job = Some(crate::app::RetryJob::Repo(req.clone()));
+									start_request(st, &app.runtime, &app.settings_store, &app.settings, req);
+								}
+								if ui.add_enabled(idle, egui::Button::new("Uninstall Fixes"))
+									.on_hover_text("Remove the files written by the last fixes install; files you changed since are kept").clicked() {
+									app.last_job = Some(crate::app::RetryJob::Repo(RepoRequest::UninstallFixes));
+									start_request(st, &app.runtime, &app.settings_store, &app.settings, RepoRequest::UninstallFixes);
 								}
 							});
 							// details panel
-							if let Some(rel) = st.fixes_releases.get(st.fixes_release_idx) {
+							if let Some(rel) = st.fixes.releases.get(st.fixes.selected) {
 								ui.separator();
 								let name = rel.name.clone().unwrap_or_else(|| rel.tag_name.clone().unwrap_or_default());
-								ui.horizontal(|ui| { ui.label(format!("Selected: {}", name)); let installed = app.settings.installed_fixes_version.clone().unwrap_or_default(); if !installed.is_empty() { let up_to_date = installed == name; let col = if up_to_date { egui::Color32::from_rgb(0,200,0) } else { egui::Color32::from_rgb(200,140,0) }; ui.colored_label(col, if up_to_date { "Up to date" } else { "Update available" }); ui.label(format!("Installed: {}", installed)); } });
-								if let Some(body) = &rel.body { egui::ScrollArea::vertical().id_salt("fixes-md").max_height(200.0).auto_shrink([false, true]).show(ui, |ui| { render_simple_markdown(ui, body); }); }
+								ui.horizontal(|ui| { ui.label(format!("Selected: {}", name)); if let Some(date) = rel.published_date() { ui.weak(format!("published {}", date)); } let installed = app.settings.installed_fixes_version.clone().unwrap_or_default(); if !installed.is_empty() { let up_to_date = installed == name; let col = if up_to_date { egui::Color32::from_rgb(0,200,0) } else { egui::Color32::from_rgb(200,140,0) }; ui.colored_label(col, if up_to_date { "Up to date" } else { "Update available" }); ui.label(format!("Installed: {}", installed)); } });
+								if select_best_package_asset(rel).is_none() { ui.colored_label(egui::Color32::from_rgb(200,140,0), "This release has no installable package; pick another release."); }
+								if let Some(body) = &rel.body { render_release_notes(ui, "fixes-md", body, &mut st.fixes.notes_expanded); }
 							}
 						});
 					}
@@ -223,63 +538,215 @@ pub fn render_repositories_tab(app: &mut crate::app::LauncherApp, ui: &mut egui:
 					{
 						let st = &mut app.repositories;
 						egui::CollapsingHeader::new("Binary Patches").default_open(false).show(ui, |ui| {
-							let patch_sources: [(&str, &str, &str); 3] = [
-								("sambow23/SourceRTXTweaks", "sambow23", "SourceRTXTweaks"),
-								("BlueAmulet/SourceRTXTweaks", "BlueAmulet", "SourceRTXTweaks"),
-								("Xenthio/SourceRTXTweaks", "Xenthio", "SourceRTXTweaks"),
-							];
-							ui.horizontal(|ui| { ui.label("Source"); egui::ComboBox::from_id_salt("patch-source").selected_text(patch_sources[st.patch_source_idx].0).show_ui(ui, |ui| { for (i, (label, _, _)) in patch_sources.iter().enumerate() { if ui.selectable_label(st.patch_source_idx == i, *label).clicked() { st.patch_source_idx = i; } } }); });
-							ui.horizontal(|ui| { ui.label("Action"); if ui.add_enabled(!st.is_running, egui::Button::new("Apply Patches")).clicked() { let (owner, repo) = { let s = patch_sources[st.patch_source_idx]; (s.1.to_string(), s.2.to_string()) }; let (tx, rx) = std::sync::mpsc::channel::<JobProgress>(); st.current_job = Some(rx); st.is_running = true; let install_dir = std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_path_buf())).unwrap_or_default(); let patch_info = format!("{}/{}", &owner, &repo); let settings_store = app.settings_store.clone(); let mut settings = app.settings.clone(); std::thread::spawn(move || { let rt = tokio::runtime::Runtime::new().unwrap(); rt.block_on(async move { let result = apply_patches_from_repo(&owner, &repo, "applypatch.py", &install_dir, |m,p| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p }); }).await; if result.is_ok() { settings.installed_patches_commit = Some(patch_info); let _ = settings_store.save(&settings); } }); }); } });
+							let patch_sources = &app.sources.patches;
+							ui.horizontal(|ui| { ui.label("Source"); egui::ComboBox::from_id_salt("patch-source").selected_text(SourceLists::pick(patch_sources, st.patch_source_idx).label()).show_ui(ui, |ui| { for (i, s) in patch_sources.iter().enumerate() { if ui.selectable_label(st.patch_source_idx == i, s.label()).clicked() { st.patch_source_idx = i; } } }); });
+							ui.horizontal(|ui| {
+								ui.label("Branch");
+								ui.add(egui::TextEdit::singleline(&mut st.patch_branch).hint_text("main / master").desired_width(160.0))
+									.on_hover_text("Fetch applypatch.py from this branch (e.g. a fork's feature branch). Leave empty to try main, then master.");
+							});
+							ui.horizontal(|ui| {
+								ui.label("Script");
+								match &st.patch_script {
+									Some(path) => { ui.monospace(path.display().to_string()); }
+									None => { ui.weak("fetched from the source repository"); }
+								}
+								if ui.small_button("Browse...").on_hover_text("Use a local applypatch.py, e.g. when offline").clicked() {
+									if let Some(path) = rfd::FileDialog::new().add_filter("Python", &["py"]).pick_file() { st.patch_script = Some(path); }
+								}
+								if st.patch_script.is_some() && ui.small_button("Clear").clicked() { st.patch_script = None; }
+							});
+							ui.horizontal(|ui| {
+								ui.label("Action");
+								let requested = if st.cancel_button(ui, RepoJob::Patches) { None } else {
+									let apply = ui.add_enabled(idle, egui::Button::new("Apply Patches")).clicked();
+									let preview = ui.add_enabled(idle, egui::Button::new("Preview Patches")).on_hover_text("Match patches against the installed DLLs without writing anything").clicked();
+									let has_report = patch_report_path(&app.settings.install_dir()).exists();
+									let retry = ui.add_enabled(idle && has_report, egui::Button::new("Retry failed")).on_hover_text("Rerun only the files the last patch report lists as missing or partly patched").clicked();
+									if apply { Some((false, false)) } else if preview { Some((true, false)) } else if retry { Some((false, true)) } else { None }
+								};
+								if let Some((dry_run, retry)) = requested {
+									let (owner, repo) = { let s = SourceLists::pick(patch_sources, st.patch_source_idx); (s.owner.clone(), s.repo.clone()) };
+									let branch = Some(st.patch_branch.trim().to_string()).filter(|b| !b.is_empty());
+									let options = PatchOptions { dry_run, branch, local_script: st.patch_script.clone(), ..PatchOptions::from_settings(&app.settings) };
+									let req = RepoRequest::Patches { owner, repo, options, retry_failed: retry };
+									app.last_job = Some(crate::app::RetryJob::Repo(req.clone()));
+									start_request(st, &app.runtime, &app.settings_store, &app.settings, req);
+								}
+								if ui.add_enabled(idle, egui::Button::new("Restore Last Backup")).clicked() {
+									app.last_job = Some(crate::app::RetryJob::Repo(RepoRequest::RestorePatchBackup));
+									start_request(st, &app.runtime, &app.settings_store, &app.settings, RepoRequest::RestorePatchBackup);
+								}
+							});
+							if ui.checkbox(&mut app.settings.patch_backup, "Back up live binaries before patching").changed() { let _ = app.settings_store.save(&app.settings); }
+							if let Some(rx) = st.patch_result_rx.take() {
+								match rx.try_recv() {
+									Ok(r) => st.last_patch_result = Some(r),
+									Err(std::sync::mpsc::TryRecvError::Empty) => st.patch_result_rx = Some(rx),
+									Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+								}
+							}
+							if let Some(r) = &st.last_patch_result { render_patch_result(ui, r, st.patch_preview); }
 						});
 					}
 	});
 	
 	// Handle async release fetching outside the UI
-	if let Some(rx) = app.repositories.remix_rx.take() { 
-		if let Ok(list) = rx.try_recv() { 
-			app.repositories.remix_releases = list; 
-			app.repositories.remix_release_idx = 0; 
-			app.repositories.remix_loading = false; 
-		} else { 
-			app.repositories.remix_rx = Some(rx); 
-		} 
+	let arrived: Vec<_> = app.repositories.releases_rx.as_ref().map(|rx| rx.try_iter().collect()).unwrap_or_default();
+	for (kind, source_idx, list) in arrived {
+		let pinned = match kind { ReleaseKind::Remix => app.settings.remix_pinned_tag.clone(), ReleaseKind::Fixes => app.settings.fixes_pinned_tag.clone() };
+		let target = app.repositories.release_list(kind);
+		// A list for a source the user has since switched away from; the newer fetch is still pending
+		if source_idx != target.source_idx { continue; }
+		target.set_releases(list, app.settings.show_prereleases, pinned.as_deref());
+		target.loading = false;
 	}
-	if let Some(rx) = app.repositories.fixes_rx.take() { 
-		if let Ok(list) = rx.try_recv() { 
-			app.repositories.fixes_releases = list; 
-			app.repositories.fixes_release_idx = 0; 
-			app.repositories.fixes_loading = false; 
-		} else { 
-			app.repositories.fixes_rx = Some(rx); 
-		} 
+}
+
+impl ReleaseList {
+	// Keep the full list and show it minus pre-releases unless they are wanted
+	fn set_releases(&mut self, fetched: Vec<GitHubRelease>, show_prereleases: bool, pinned: Option<&str>) {
+		self.releases = fetched.iter().filter(|r| show_prereleases || !r.is_prerelease()).cloned().collect();
+		self.fetched = fetched;
+		self.selected = pinned_index(&self.releases, pinned);
+		self.notes_expanded = false;
+		self.asset_override = None;
 	}
 }
 
-fn start_fetch_releases(remix: bool, st: &mut RepositoriesState) {
-	let (owner, repo) = if remix {
-		match st.remix_source_idx { 0 => ("sambow23", "dxvk-remix-gmod"), _ => ("NVIDIAGameWorks", "rtx-remix") }
-	} else {
-		match st.fixes_source_idx { 0 => ("Xenthio", "gmod-rtx-fixes-2"), _ => ("Xenthio", "RTXFixes") }
+// Every asset of the release with its size; the automatic choice stays the default
+fn render_asset_picker(ui: &mut egui::Ui, rel: &GitHubRelease, auto: Option<&GitHubAsset>, chosen: &mut Option<String>) {
+	let size = |a: &GitHubAsset| a.size.map(|s| humansize::format_size(s, humansize::BINARY)).unwrap_or_else(|| "size unknown".into());
+	egui::CollapsingHeader::new("Advanced: choose asset").id_salt("remix-asset").show(ui, |ui| {
+		let auto_text = match auto { Some(a) => format!("Automatic ({}, {})", a.name, size(a)), None => "Automatic (no suitable asset)".to_string() };
+		ui.radio_value(chosen, None, auto_text);
+		for a in &rel.assets {
+			ui.radio_value(chosen, Some(a.name.clone()), format!("{}  {}", a.name, size(a)));
+		}
+	});
+}
+
+// Dropdown entry: the release name followed by its publication day
+fn dated_label(r: &GitHubRelease) -> String {
+	let name = r.name.clone().unwrap_or_else(|| r.tag_name.clone().unwrap_or_default());
+	match r.published_date() { Some(date) => format!("{}  ({})", name, date), None => name }
+}
+
+// Index of the release matching the pinned tag, falling back to the latest when unpinned or not listed
+fn pinned_index(releases: &[GitHubRelease], pinned: Option<&str>) -> usize {
+	pinned.and_then(|tag| releases.iter().position(|r| r.tag_name.as_deref() == Some(tag))).unwrap_or(0)
+}
+
+// Per-file summary of the last patch run: green when every patch set applied, orange when some did not
+fn render_patch_result(ui: &mut egui::Ui, r: &PatchResult, preview: bool) {
+	ui.separator();
+	if preview { ui.label(format!("Preview: {} file(s) would be patched", r.files_patched)); } else { ui.label(format!("Last run: {} file(s) patched", r.files_patched)); }
+	if r.already_patched > 0 { ui.label(format!("{} patch set(s) already up to date", r.already_patched)); }
+	egui::Grid::new("patch-result").striped(true).num_columns(3).show(ui, |ui| {
+		ui.strong("File");
+		ui.strong("Patch sets applied");
+		ui.strong("SHA-256 (before -> after)");
+		ui.end_row();
+		for f in &r.per_file {
+			let col = if f.is_complete() { egui::Color32::from_rgb(0,200,0) } else { egui::Color32::from_rgb(200,140,0) };
+			ui.monospace(&f.path);
+			ui.colored_label(col, format!("{}/{}", f.applied, f.total));
+			match (&f.before_hash, &f.after_hash) {
+				(Some(before), Some(after)) => { ui.monospace(format!("{} -> {}", before, after)).on_hover_text("Short hashes; compare with another install's patch-report.txt"); }
+				_ => { ui.weak("missing"); }
+			}
+			ui.end_row();
+		}
+	});
+	if preview {
+		egui::CollapsingHeader::new("Planned writes").id_salt("patch-preview").show(ui, |ui| {
+			for w in r.warnings.iter().filter(|w| w.starts_with("DRY:")) { ui.monospace(w); }
+		});
+	}
+}
+
+// Fetch the release lists for `kinds` concurrently as tasks on the launcher's runtime
+fn start_fetch_releases(st: &mut RepositoriesState, runtime: &crate::app::JobRuntime, sources: &SourceLists, kinds: &[ReleaseKind]) {
+	let tx = match &st.releases_tx {
+		Some(tx) => tx.clone(),
+		None => {
+			let (tx, rx) = std::sync::mpsc::channel();
+			st.releases_rx = Some(rx);
+			st.releases_tx = Some(tx.clone());
+			tx
+		}
 	};
-	let (tx, rx) = std::sync::mpsc::channel::<Vec<GitHubRelease>>();
-	if remix { st.remix_rx = Some(rx); st.remix_loading = true; } else { st.fixes_rx = Some(rx); st.fixes_loading = true; }
-	std::thread::spawn(move || {
-		let rt = tokio::runtime::Runtime::new().unwrap();
-		rt.block_on(async move {
-			let mut rl = GitHubRateLimit::default();
-			let list = fetch_releases(owner, repo, &mut rl).await.unwrap_or_default();
-			let _ = tx.send(list);
+	for &kind in kinds {
+		let list = st.release_list(kind);
+		list.loading = true;
+		let source_idx = list.source_idx;
+		let source = match kind {
+			ReleaseKind::Remix => SourceLists::pick(&sources.remix, source_idx),
+			ReleaseKind::Fixes => SourceLists::pick(&sources.fixes, source_idx),
+		};
+		let (owner, repo) = (source.owner.clone(), source.repo.clone());
+		let tx = tx.clone();
+		runtime.spawn(async move {
+			let list = fetch_releases(&owner, &repo, &mut GitHubRateLimit::default()).await.unwrap_or_default();
+			let _ = tx.send((kind, source_idx, list));
 		});
-	});
+	}
+}
+
+// Bodies longer than this many lines show only their start until expanded
+const NOTES_COLLAPSED_LINES: usize = 30;
+// Largest size a release-notes image is drawn at
+const NOTES_IMAGE_MAX: egui::Vec2 = egui::vec2(480.0, 270.0);
+
+fn render_release_notes(ui: &mut egui::Ui, id: &str, body: &str, expanded: &mut bool) {
+	let total = body.lines().count();
+	let long = total > NOTES_COLLAPSED_LINES;
+	let shown = if long && !*expanded {
+		body.lines().take(NOTES_COLLAPSED_LINES).collect::<Vec<_>>().join("\n")
+	} else {
+		body.to_string()
+	};
+	let height = if *expanded { 500.0 } else { 200.0 };
+	egui::ScrollArea::vertical().id_salt(id).max_height(height).auto_shrink([false, true]).show(ui, |ui| { render_simple_markdown(ui, &shown); });
+	if long {
+		let label = if *expanded { "Show less".to_string() } else { format!("Show more ({} more lines)", total - NOTES_COLLAPSED_LINES) };
+		if ui.link(label).clicked() { *expanded = !*expanded; }
+	}
+}
+
+// http(s) images load through the egui_extras loaders; anything else, or a failed load, shows the alt text
+fn render_markdown_image(ui: &mut egui::Ui, alt: &str, url: &str) {
+	let alt_label = |ui: &mut egui::Ui| { ui.label(egui::RichText::new(format!("[image: {}]", if alt.is_empty() { url } else { alt })).italics().weak()); };
+	if !(url.starts_with("http://") || url.starts_with("https://")) { alt_label(ui); return; }
+	match ui.ctx().try_load_image(url, egui::SizeHint::default()) {
+		Err(_) => alt_label(ui),
+		Ok(egui::load::ImagePoll::Pending { .. }) => { ui.spinner(); }
+		Ok(egui::load::ImagePoll::Ready { .. }) => {
+			ui.add(egui::Image::new(url).max_size(NOTES_IMAGE_MAX).fit_to_original_size(1.0)).on_hover_text(alt);
+		}
+	}
 }
 
 // Minimal markdown renderer (headings h1..h6, bullet lists, code blocks, simple links & inline code)
 fn render_simple_markdown(ui: &mut egui::Ui, text: &str) {
 	let mut in_code = false;
-	for raw_line in text.lines() {
-		let line = raw_line.trim_end();
+	let lines: Vec<&str> = text.lines().collect();
+	let mut idx = 0;
+	while idx < lines.len() {
+		let line = lines[idx].trim_end();
+		idx += 1;
 		if line.starts_with("```") { in_code = !in_code; continue; }
 		if in_code { ui.monospace(line); continue; }
+		// tables: a header row followed by a |---|---| separator; anything else stays plain text
+		if let Some(header) = table_row(line) {
+			if lines.get(idx).is_some_and(|l| is_table_separator(l, header.len())) {
+				let mut rows = Vec::new();
+				idx += 1;
+				while let Some(row) = lines.get(idx).and_then(|l| table_row(l.trim_end())) { rows.push(row); idx += 1; }
+				render_table(ui, idx, &header, &rows);
+				continue;
+			}
+		}
 		// headings h6..h1 (render inline so links/bold work inside)
 		if let Some(rest) = line.strip_prefix("###### ") { render_inline_with_heading(ui, rest, true); continue; }
 		if let Some(rest) = line.strip_prefix("##### ") { render_inline_with_heading(ui, rest, true); continue; }
@@ -290,15 +757,65 @@ fn render_simple_markdown(ui: &mut egui::Ui, text: &str) {
 		// bullets
 		if let Some(rest) = line.strip_prefix("- ") { ui.horizontal_wrapped(|ui| { ui.label("•"); render_inline_with_heading(ui, rest, false); }); continue; }
 		if let Some(rest) = line.strip_prefix("* ") { ui.horizontal_wrapped(|ui| { ui.label("•"); render_inline_with_heading(ui, rest, false); }); continue; }
+		// numbered list items keep their own number
+		if let Some((n, rest)) = ordered_item(line) { ui.horizontal_wrapped(|ui| { ui.label(format!("{}.", n)); render_inline_with_heading(ui, rest, false); }); continue; }
 		// plain
 		if line.is_empty() { ui.add_space(4.0); } else { render_inline_with_heading(ui, line, false); }
 	}
 }
 
+// "12. text" -> (12, "text"); the number is kept as written
+fn ordered_item(line: &str) -> Option<(&str, &str)> {
+	let trimmed = line.trim_start();
+	let digits = trimmed.find(|c: char| !c.is_ascii_digit())?;
+	if digits == 0 || digits > 9 { return None; }
+	let rest = trimmed[digits..].strip_prefix(". ")?;
+	Some((&trimmed[..digits], rest))
+}
+
+// Cells of a pipe-delimited row; `\|` stays part of the cell
+fn table_row(line: &str) -> Option<Vec<String>> {
+	let t = line.trim();
+	if !t.starts_with('|') && !t.ends_with('|') { return None; }
+	let inner = t.strip_prefix('|').unwrap_or(t);
+	let inner = inner.strip_suffix('|').filter(|s| !s.ends_with('\\')).unwrap_or(inner);
+	let mut cells = vec![String::new()];
+	let mut chars = inner.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' if chars.peek() == Some(&'|') => { cells.last_mut()?.push('|'); chars.next(); }
+			'|' => cells.push(String::new()),
+			c => cells.last_mut()?.push(c),
+		}
+	}
+	let cells: Vec<String> = cells.into_iter().map(|c| c.trim().to_string()).collect();
+	(cells.len() >= 2 || t.starts_with('|')).then_some(cells)
+}
+
+fn is_table_separator(line: &str, columns: usize) -> bool {
+	let Some(cells) = table_row(line.trim_end()) else { return false };
+	cells.len() == columns && cells.iter().all(|c| {
+		let dashes = c.trim_start_matches(':').trim_end_matches(':');
+		!dashes.is_empty() && dashes.chars().all(|ch| ch == '-')
+	})
+}
+
+// Rows shorter than the header are padded and longer ones cut, so a sloppy table still lines up
+fn render_table(ui: &mut egui::Ui, id: usize, header: &[String], rows: &[Vec<String>]) {
+	egui::Grid::new(("md_table", id)).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+		for cell in header { ui.label(egui::RichText::new(cell).strong()); }
+		ui.end_row();
+		for row in rows {
+			for i in 0..header.len() { render_inline_with_heading(ui, row.get(i).map(String::as_str).unwrap_or(""), false); }
+			ui.end_row();
+		}
+	});
+}
+
 // inline renderer with optional heading styling: supports **bold**, `code`, and [label](url)
 fn render_inline_with_heading(ui: &mut egui::Ui, line: &str, heading: bool) {
 	#[derive(Debug)]
-	enum Seg { Text(String, bool), Code(String), Link { label: String, url: String, bold: bool } }
+	enum Seg { Text(String, bool), Code(String), Link { label: String, url: String, bold: bool }, Image { alt: String, url: String } }
 	let mut segs: Vec<Seg> = Vec::new();
 	let mut bold = false;
 	let mut code = false;
@@ -311,17 +828,31 @@ fn render_inline_with_heading(ui: &mut egui::Ui, line: &str, heading: bool) {
 			if !buf.is_empty() { segs.push(Seg::Text(std::mem::take(&mut buf), bold)); }
 			bold = !bold; i += 2; continue;
 		}
+		// image ![alt](url)
+		if !code && chars[i] == '!' && chars.get(i + 1) == Some(&'[') {
+			let rest: String = chars[i + 1..].iter().collect();
+			if let Some(close_br) = rest.find(']') {
+				if let Some(close_paren) = rest[close_br + 1..].strip_prefix('(').and_then(|a| a.find(')')) {
+					if !buf.is_empty() { segs.push(Seg::Text(std::mem::take(&mut buf), bold)); }
+					let alt = rest[1..close_br].trim().to_string();
+					let url = rest[close_br + 2..close_br + 2 + close_paren].trim().to_string();
+					i += 1 + rest[..close_br + 2 + close_paren + 1].chars().count();
+					segs.push(Seg::Image { alt, url });
+					continue;
+				}
+			}
+		}
 		// inline link [text](url)
 		if !code && chars[i] == '[' {
 			let rest: String = chars[i..].iter().collect();
 			if let Some(close_br) = rest.find(']') {
 				let after = &rest[close_br+1..];
-				if after.starts_with('(') {
-					if let Some(close_paren) = after[1..].find(')') {
+				if let Some(after_paren) = after.strip_prefix('(') {
+					if let Some(close_paren) = after_paren.find(')') {
 						if !buf.is_empty() { segs.push(Seg::Text(std::mem::take(&mut buf), bold)); }
 						let mut label = rest[1..close_br].trim().to_string();
 						if label.starts_with('`') && label.ends_with('`') && label.len() >= 2 { label = label[1..label.len()-1].to_string(); }
-						let url = &after[1..1+close_paren];
+						let url = &after_paren[..close_paren];
 						segs.push(Seg::Link { label, url: url.to_string(), bold });
 						// advance i by consumed chars
 						i += 1 + close_br + 1 + 1 + close_paren + 1;
@@ -357,12 +888,10 @@ fn render_inline_with_heading(ui: &mut egui::Ui, line: &str, heading: bool) {
 					if heading { text = text.heading(); }
 					ui.add(egui::widgets::Hyperlink::from_label_and_url(text, url));
 				}
+				Seg::Image { alt, url } => render_markdown_image(ui, &alt, &url),
 			}
 		}
 	});
 }
 
-// Backwards-compat shim
-fn render_inline(ui: &mut egui::Ui, line: &str) { render_inline_with_heading(ui, line, false); }
-
 
@@ -3,9 +3,32 @@ use rtxlauncher_core::detect_gmod_install_folder;
 #[cfg(windows)]
 use rtxlauncher_core::is_elevated;
 
-pub struct SettingsState {}
+#[derive(Default)]
+pub struct SettingsState {
+	// PAT text box contents; loaded from disk on first render
+	pub pat_input: Option<String>,
+	pub pat_test_rx: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+	pub pat_test_result: Option<Result<String, String>>,
+	// Waiting for the user to confirm deleting the Proton prefix
+	pub confirm_recreate_prefix: bool,
+	// GMod installs found across Steam libraries; scanned on first render
+	pub gmod_installs: Option<Vec<std::path::PathBuf>>,
+	// GitHub mirror text boxes (API, raw), applied on Enter, focus loss or Apply
+	pub mirror_input: Option<[String; 2]>,
+	pub mirror_error: Option<String>,
+}
 
-impl Default for SettingsState { fn default() -> Self { Self {} } }
+impl SettingsState {
+	fn poll_pat_test(&mut self) {
+		if let Some(rx) = self.pat_test_rx.take() {
+			match rx.try_recv() {
+				Ok(r) => self.pat_test_result = Some(r),
+				Err(std::sync::mpsc::TryRecvError::Empty) => self.pat_test_rx = Some(rx),
+				Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+			}
+		}
+	}
+}
 
 pub fn render_settings_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui, ctx: &egui::Context) {
 	ui.heading("Settings");
@@ -31,18 +54,164 @@ pub fn render_settings_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui,
         || detect_gmod_install_folder().is_some();
     let col = if path_ok { egui::Color32::from_rgb(0,200,0) } else { egui::Color32::from_rgb(200,0,0) };
     ui.colored_label(col, if path_ok { "GMod path OK" } else { "GMod path not found" });
+	let installs = app.settings_tab.gmod_installs.get_or_insert_with(rtxlauncher_core::detect_all_gmod_installs).clone();
+	if installs.len() > 1 {
+		ui.horizontal(|ui| {
+			ui.label("Detected installs:");
+			let current = app.settings.manually_specified_install_path.clone().unwrap_or_default();
+			egui::ComboBox::from_id_salt("gmod-installs").selected_text(if current.is_empty() { "Choose...".to_string() } else { current.clone() }).show_ui(ui, |ui| {
+				for p in &installs {
+					let text = p.display().to_string();
+					if ui.selectable_label(current == text, &text).clicked() {
+						app.settings.manually_specified_install_path = Some(text);
+						let _ = app.settings_store.save(&app.settings);
+					}
+				}
+			});
+		});
+	}
+	ui.horizontal(|ui| {
+		ui.label("RTX install folder:");
+		let mut target = app.settings.install_target_path.clone().unwrap_or_default();
+		if ui.add(egui::TextEdit::singleline(&mut target).hint_text("launcher folder")).changed() {
+			app.settings.install_target_path = if target.trim().is_empty() { None } else { Some(target) };
+			let _ = app.settings_store.save(&app.settings);
+		}
+		if ui.add_enabled(!app.setup.is_running, egui::Button::new("Browse")).clicked() {
+			if let Some(p) = rfd::FileDialog::new().set_title("Select where the RTX install is created").pick_folder() {
+				app.settings.install_target_path = Some(p.display().to_string());
+				let _ = app.settings_store.save(&app.settings);
+			}
+		}
+		if app.settings.install_target_path.is_some() && ui.button("Reset").clicked() {
+			app.settings.install_target_path = None;
+			let _ = app.settings_store.save(&app.settings);
+		}
+	});
+	ui.horizontal(|ui| {
+		use rtxlauncher_core::SettingsLocation;
+		ui.label("Settings stored in:");
+		let label = |l: SettingsLocation| match l { SettingsLocation::User => "User config folder", SettingsLocation::Portable => "Launcher folder (portable)" };
+		let current = app.settings_store.location();
+		let mut picked = None;
+		egui::ComboBox::from_id_salt("settings-location").selected_text(label(current)).show_ui(ui, |ui| {
+			for l in [SettingsLocation::User, SettingsLocation::Portable] {
+				if ui.selectable_label(current == l, label(l)).clicked() && current != l { picked = Some(l); }
+			}
+		});
+		if let Some(l) = picked {
+			match app.settings_store.relocate(l, &app.settings) {
+				Ok(store) => app.settings_store = store,
+				Err(e) => app.append_log(&format!("Failed to move settings: {}", e)),
+			}
+		}
+	}).response.on_hover_text(app.settings_store.path().display().to_string());
+	ui.horizontal(|ui| {
+		use rtxlauncher_core::LinkStrategy;
+		ui.label("Content linking:");
+		let label = |s: LinkStrategy| match s { LinkStrategy::Auto => "Auto (link, copy if needed)", LinkStrategy::SymlinkOnly => "Links only", LinkStrategy::CopyOnly => "Always copy" };
+		egui::ComboBox::from_id_salt("link-strategy").selected_text(label(app.settings.link_strategy)).show_ui(ui, |ui| {
+			for s in [LinkStrategy::Auto, LinkStrategy::SymlinkOnly, LinkStrategy::CopyOnly] {
+				if ui.selectable_label(app.settings.link_strategy == s, label(s)).clicked() {
+					app.settings.link_strategy = s;
+					let _ = app.settings_store.save(&app.settings);
+				}
+			}
+		});
+	});
+	ui.horizontal(|ui| {
+		ui.label("RTXIO extractor:");
+		let mut extractor = app.settings.rtxio_extractor_path.clone().unwrap_or_default();
+		if ui.add(egui::TextEdit::singleline(&mut extractor).hint_text("auto-detect")).changed() {
+			app.settings.rtxio_extractor_path = if extractor.trim().is_empty() { None } else { Some(extractor) };
+			let _ = app.settings_store.save(&app.settings);
+		}
+		if ui.button("Browse").clicked() {
+			if let Some(p) = rfd::FileDialog::new().set_title("Select RtxIoResourceExtractor").pick_file() {
+				app.settings.rtxio_extractor_path = Some(p.display().to_string());
+				let _ = app.settings_store.save(&app.settings);
+			}
+		}
+		if app.settings.rtxio_extractor_path.is_some() && ui.button("Reset").clicked() {
+			app.settings.rtxio_extractor_path = None;
+			let _ = app.settings_store.save(&app.settings);
+		}
+	});
+	app.settings_tab.poll_pat_test();
 	ui.horizontal(|ui| {
 		ui.label("GitHub PAT (optional):");
-		let mut pat = rtxlauncher_core::load_personal_access_token().unwrap_or_default();
-		ui.add(egui::TextEdit::singleline(&mut pat).password(true).desired_width(200.0));
+		let st = &mut app.settings_tab;
+		let pat = st.pat_input.get_or_insert_with(|| rtxlauncher_core::load_personal_access_token().unwrap_or_default());
+		if ui.add(egui::TextEdit::singleline(pat).password(true).desired_width(200.0)).changed() {
+			st.pat_test_result = None;
+		}
+		let pat = pat.trim().to_string();
 		if ui.button("Save PAT").clicked() {
-			let _ = rtxlauncher_core::set_personal_access_token(if pat.trim().is_empty() { None } else { Some(pat.clone()) });
+			let _ = rtxlauncher_core::set_personal_access_token(if pat.is_empty() { None } else { Some(pat.clone()) });
 		}
+		let testing = st.pat_test_rx.is_some();
+		if ui.add_enabled(!testing && !pat.is_empty(), egui::Button::new("Test"))
+			.on_hover_text("Check the token with GitHub and save it if it works").clicked() {
+			let (tx, rx) = std::sync::mpsc::channel();
+			st.pat_test_rx = Some(rx);
+			st.pat_test_result = None;
+			crate::app::spawn_task(&app.runtime, move || async move {
+				let res = rtxlauncher_core::validate_personal_access_token(&pat).await.map_err(|e| e.to_string()).and_then(|(login, rl)| {
+					rtxlauncher_core::set_personal_access_token(Some(pat)).map_err(|e| format!("Token works but could not be saved: {}", e))?;
+					Ok(format!("Authenticated as {} ({}/{} requests left this hour); saved", login, rl.remaining, rl.limit))
+				});
+				let _ = tx.send(res);
+			});
+		}
+		if testing { ui.add(egui::Spinner::new()); }
 	});
+	match &app.settings_tab.pat_test_result {
+		Some(Ok(msg)) => { ui.colored_label(egui::Color32::from_rgb(0,200,0), msg); }
+		Some(Err(e)) => { ui.colored_label(egui::Color32::from_rgb(200,0,0), e); }
+		None => {}
+	}
     // PAT validation hint
     let pat_ok = rtxlauncher_core::load_personal_access_token().map(|s| !s.is_empty()).unwrap_or(false);
     let col = if pat_ok { egui::Color32::from_rgb(0,200,0) } else { egui::Color32::from_rgb(200,0,0) };
     ui.colored_label(col, if pat_ok { "PAT saved" } else { "No PAT" });
+	ui.collapsing("GitHub mirror", |ui| {
+		let labels = [("API base:", rtxlauncher_core::DEFAULT_GITHUB_API_BASE), ("Raw files base:", rtxlauncher_core::DEFAULT_GITHUB_RAW_BASE)];
+		let st = &mut app.settings_tab;
+		let inputs = st.mirror_input.get_or_insert_with(|| [app.settings.github_api_base.clone().unwrap_or_default(), app.settings.github_raw_base.clone().unwrap_or_default()]);
+		let mut commit = false;
+		for ((label, default), text) in labels.iter().zip(inputs.iter_mut()) {
+			ui.horizontal(|ui| {
+				ui.label(*label);
+				// Enter also gives up focus
+				commit |= ui.add(egui::TextEdit::singleline(text).hint_text(*default).desired_width(300.0)).lost_focus();
+			});
+		}
+		ui.horizontal(|ui| {
+			commit |= ui.button("Apply").clicked();
+			ui.label("Leave empty to use the public GitHub hosts.");
+		});
+		if commit {
+			// Nothing changes until both hosts are valid
+			let parsed: Result<Vec<Option<String>>, String> = labels.iter().zip(inputs.iter()).map(|((label, _), text)| {
+				if text.trim().is_empty() { return Ok(None); }
+				rtxlauncher_core::validate_github_base(text).map(Some).map_err(|e| format!("{} {}", label, e))
+			}).collect();
+			match parsed {
+				Ok(hosts) => {
+					st.mirror_error = None;
+					let (api, raw) = (hosts[0].clone(), hosts[1].clone());
+					if api != app.settings.github_api_base || raw != app.settings.github_raw_base {
+						app.settings.github_api_base = api;
+						app.settings.github_raw_base = raw;
+						rtxlauncher_core::set_github_bases(&app.settings);
+						let _ = app.settings_store.save(&app.settings);
+					}
+				}
+				Err(e) => st.mirror_error = Some(e),
+			}
+		}
+		if let Some(e) = &st.mirror_error { ui.colored_label(egui::Color32::RED, e); }
+	});
 	ui.separator();
 	ui.heading("Launch options");
 	// Resolution dropdown
@@ -83,12 +252,64 @@ pub fn render_settings_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui,
 			if ui.add(egui::DragValue::new(&mut h).range(0..=16384)).changed() { app.settings.height = Some(h); let _ = app.settings_store.save(&app.settings); }
 		});
 	}
+	ui.horizontal(|ui| {
+		ui.label("DX level:");
+		let current = app.settings.dxlevel.unwrap_or(90);
+		egui::ComboBox::from_id_salt("dxlevel-dropdown").selected_text(current.to_string()).show_ui(ui, |ui| {
+			for level in [70u32, 80, 81, 90, 95, 98] {
+				let label = if level == 90 { "90 (default)".to_string() } else { level.to_string() };
+				if ui.selectable_label(current == level, label).clicked() {
+					app.settings.dxlevel = if level == 90 { None } else { Some(level) };
+					let _ = app.settings_store.save(&app.settings);
+				}
+			}
+		});
+	}).response.on_hover_text("Remix expects 90; other levels are only useful for debugging");
+	ui.horizontal(|ui| {
+		use rtxlauncher_core::WindowMode;
+		ui.label("Window mode:");
+		let label = |m: WindowMode| match m { WindowMode::Borderless => "Borderless (default)", WindowMode::Windowed => "Windowed", WindowMode::Fullscreen => "Fullscreen" };
+		egui::ComboBox::from_id_salt("window-mode").selected_text(label(app.settings.window_mode)).show_ui(ui, |ui| {
+			for m in [WindowMode::Borderless, WindowMode::Windowed, WindowMode::Fullscreen] {
+				if ui.selectable_label(app.settings.window_mode == m, label(m)).clicked() {
+					app.settings.window_mode = m;
+					let _ = app.settings_store.save(&app.settings);
+				}
+			}
+		});
+	});
+	if ui.checkbox(&mut app.settings.disable_d3d9ex, "Disable D3D9Ex").on_hover_text("Remix needs this; only turn it off for debugging").changed() { let _ = app.settings_store.save(&app.settings); }
+	ui.horizontal(|ui| {
+		ui.label("Log level:");
+		egui::ComboBox::from_id_salt("log-level-dropdown").selected_text(app.settings.log_level.clone()).show_ui(ui, |ui| {
+			for level in rtxlauncher_core::LOG_LEVELS {
+				if ui.selectable_label(app.settings.log_level == level, level).clicked() {
+					app.settings.log_level = level.to_string();
+					let _ = app.settings_store.save(&app.settings);
+					if let Err(e) = rtxlauncher_core::set_log_level(level) { app.append_log(&format!("Failed to change log level: {}", e)); }
+				}
+			}
+		});
+	}).response.on_hover_text("Verbosity of the files in the logs folder; debug and trace help diagnose failed patches");
 	if ui.checkbox(&mut app.settings.console_enabled, "Enable console").changed() { let _ = app.settings_store.save(&app.settings); }
 	if ui.checkbox(&mut app.settings.load_workshop_addons, "Load Workshop Addons").changed() { let _ = app.settings_store.save(&app.settings); }
 	if ui.checkbox(&mut app.settings.disable_chromium, "Disable Chromium").changed() { let _ = app.settings_store.save(&app.settings); }
 	if ui.checkbox(&mut app.settings.developer_mode, "Developer mode").changed() { let _ = app.settings_store.save(&app.settings); }
 	if ui.checkbox(&mut app.settings.tools_mode, "Particle Editor Mode").changed() { let _ = app.settings_store.save(&app.settings); }
 	ui.horizontal(|ui| { ui.label("Custom args:"); let mut custom = app.settings.custom_launch_options.clone().unwrap_or_default(); if ui.text_edit_singleline(&mut custom).changed() { app.settings.custom_launch_options = if custom.trim().is_empty() { None } else { Some(custom) }; let _ = app.settings_store.save(&app.settings); } });
+	// Live preview of what Launch Game passes
+	let install_dir = app.settings.install_dir();
+	let exe = rtxlauncher_core::resolve_game_exe(&install_dir).unwrap_or_else(|| install_dir.join("gmod.exe"));
+	let command_line = rtxlauncher_core::format_command_line(&exe, &rtxlauncher_core::build_launch_args(&app.settings));
+	ui.horizontal(|ui| {
+		ui.label("Command:");
+		if ui.button("Copy command").clicked() {
+			ui.output_mut(|o| o.copied_text = command_line.clone());
+			app.add_toast("Copied launch command", egui::Color32::LIGHT_GREEN);
+		}
+	});
+	let mut preview = command_line.as_str();
+	ui.add(egui::TextEdit::multiline(&mut preview).font(egui::TextStyle::Monospace).desired_rows(2).desired_width(f32::INFINITY));
 
 	#[cfg(windows)]
 	{
@@ -161,10 +382,34 @@ pub fn render_settings_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui,
 			}
 		});
 		
+		// WINEDLLOVERRIDES
+		ui.horizontal(|ui| {
+			ui.label("DLL overrides:");
+			let mut overrides = app.settings.linux_dll_overrides.clone().unwrap_or_default();
+			if ui.add(egui::TextEdit::singleline(&mut overrides).hint_text(rtxlauncher_core::DEFAULT_DLL_OVERRIDES)).changed() {
+				app.settings.linux_dll_overrides = if overrides.trim().is_empty() { None } else { Some(overrides) };
+				let _ = app.settings_store.save(&app.settings);
+			}
+		}).response.on_hover_text("WINEDLLOVERRIDES for the game, e.g. d3d9=n,b;dxgi=n");
+		if let Some(Err(e)) = app.settings.linux_dll_overrides.as_deref().map(rtxlauncher_core::validate_dll_overrides) {
+			ui.colored_label(egui::Color32::from_rgb(200,0,0), format!("Invalid DLL overrides: {}; {} will be used", e, rtxlauncher_core::DEFAULT_DLL_OVERRIDES));
+		}
+
+		// Extra environment
+		ui.label("Extra environment (one KEY=VALUE per line):");
+		if ui.add(egui::TextEdit::multiline(&mut app.settings.linux_extra_env).font(egui::TextStyle::Monospace).desired_rows(3).hint_text("DXVK_HUD=fps\nPROTON_NO_ESYNC=1")).changed() {
+			let _ = app.settings_store.save(&app.settings);
+		}
+		ui.label(egui::RichText::new("Applied after the launcher's own variables, so these win (including WINEDLLOVERRIDES).").small());
+
 		// Proton logging
 		if ui.checkbox(&mut app.settings.linux_enable_proton_log, "Enable Proton logging").changed() {
 			let _ = app.settings_store.save(&app.settings);
 		}
+		ui.horizontal(|ui| {
+			if ui.checkbox(&mut app.settings.linux_use_gamemode, "Run with gamemoderun").changed() { let _ = app.settings_store.save(&app.settings); }
+			if ui.checkbox(&mut app.settings.linux_use_mangohud, "Run with MangoHud").changed() { let _ = app.settings_store.save(&app.settings); }
+		}).response.on_hover_text("Skipped at launch when the tool isn't installed");
 		
 		// Proton build selection (if available)
 		let proton_builds = rtxlauncher_core::list_proton_builds(&app.settings);
@@ -189,6 +434,35 @@ pub fn render_settings_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui,
 				});
 			});
 		}
+		if let Some(path) = app.settings.linux_proton_path.as_ref().filter(|p| !std::path::Path::new(p).exists()) {
+			let fallback = match &app.settings.linux_selected_proton_label {
+				Some(label) if proton_builds.iter().any(|(l, _)| l == label) => format!("the detected \"{}\" build will be used", label),
+				_ => "an auto-detected build will be used".to_string(),
+			};
+			ui.colored_label(egui::Color32::from_rgb(200,140,0), format!("Selected Proton no longer exists at {}; {}", path, fallback));
+		}
+
+		// Proton prefix maintenance
+		if let Some(prefix) = rtxlauncher_core::proton_prefix_dir(&app.settings) {
+			if rtxlauncher_core::proton_prefix_is_broken(&app.settings) {
+				ui.colored_label(egui::Color32::from_rgb(200,140,0), "The Proton prefix looks incomplete (no pfx/drive_c); recreating it usually fixes a game that won't start");
+			}
+			ui.horizontal(|ui| {
+				if !app.settings_tab.confirm_recreate_prefix {
+					if ui.button("Recreate Proton prefix").on_hover_text(prefix.display().to_string()).clicked() { app.settings_tab.confirm_recreate_prefix = true; }
+				} else {
+					ui.label("Delete the prefix, including any saved Wine settings?");
+					if ui.button("Delete and recreate").clicked() {
+						app.settings_tab.confirm_recreate_prefix = false;
+						match rtxlauncher_core::recreate_proton_prefix(&app.settings) {
+							Ok(p) => { app.append_log(&format!("Recreated Proton prefix at {}; it is rebuilt on the next launch", p.display())); app.add_toast("Proton prefix recreated", egui::Color32::LIGHT_GREEN); }
+							Err(e) => app.append_log(&format!("Failed to recreate Proton prefix: {}", e)),
+						}
+					}
+					if ui.button("Cancel").clicked() { app.settings_tab.confirm_recreate_prefix = false; }
+				}
+			});
+		}
 	}
 }
 
@@ -1,43 +1,40 @@
 use eframe::egui;
-use rtxlauncher_core::{JobProgress, InstallPlan, detect_gmod_install_folder, perform_basic_install, GitHubRateLimit, fetch_releases, install_remix_from_release, install_fixes_from_release, apply_patches_from_repo};
+use rtxlauncher_core::{JobProgress, InstallPlan, detect_gmod_install_folder, verify_install, VerifyIssue, CancellationToken, check_install_space, SpaceCheck, JobLock};
+use crate::quick_install::{launcher_error, run_quick_install, QuickInstallSources, QuickInstallSummary};
 
+#[derive(Default)]
 pub struct SetupState {
 	pub is_running: bool,
 	pub current_job: Option<std::sync::mpsc::Receiver<JobProgress>>,
 	pub progress: u8,
 	pub setup_completed: bool,
-	pub show_quick_install_dialog: bool,
-}
-
-impl Default for SetupState {
-	fn default() -> Self {
-		Self {
-			is_running: false,
-			current_job: None,
-			progress: 0,
-			setup_completed: false,
-			show_quick_install_dialog: false,
-		}
-	}
+	pub cancel: Option<CancellationToken>,
+	pub verify_issues: Option<Vec<VerifyIssue>>,
+	pub failure_rx: Option<std::sync::mpsc::Receiver<(String, Option<&'static str>)>>,
+	// Plan and space check waiting on the confirmation dialog
+	pub pending_install: Option<(InstallPlan, SpaceCheck)>,
+	// Sent once the quick install succeeds, shown under "Setup Complete!"
+	pub summary_rx: Option<std::sync::mpsc::Receiver<QuickInstallSummary>>,
+	pub summary: Option<QuickInstallSummary>,
 }
 
 impl SetupState {
-	pub fn poll_job(&mut self, global_log: &mut String) -> bool {
+	pub fn poll_job(&mut self, global_log: &mut crate::app::LogSink) -> bool {
 		if self.current_job.is_none() { return false; }
 		let mut finished = false;
 		if let Some(rx) = self.current_job.take() {
 			while let Ok(p) = rx.try_recv() {
 				self.progress = p.percent;
-				// Append to global log (deduplicated)
-				crate::app::append_line_dedup(global_log, &p.message);
+				global_log.push(&p.message);
 				if p.percent >= 100 { 
 					self.is_running = false; 
-					self.setup_completed = true;
+					self.setup_completed = !self.cancel.as_ref().map(|t| t.is_cancelled()).unwrap_or(false);
 					finished = true; 
 				}
 			}
 			if !finished { self.current_job = Some(rx); }
 		}
+		if finished { self.cancel = None; }
 		finished
 	}
 }
@@ -52,12 +49,30 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 		if let Ok(new_settings) = app.settings_store.load() {
 			app.settings = new_settings;
 		}
-		// Mark setup as completed in settings
-		app.settings.setup_completed = Some(true);
-		let _ = app.settings_store.save(&app.settings);
-		app.add_toast("Setup completed successfully!", egui::Color32::LIGHT_GREEN);
+		let failure = app.setup.failure_rx.take().and_then(|rx| rx.try_recv().ok());
+		if let Some((msg, hint)) = failure {
+			app.setup.setup_completed = false;
+			app.show_error(msg, hint);
+			app.error_retry = app.last_job.clone();
+			app.add_toast("Quick install failed", egui::Color32::RED);
+		} else if app.setup.setup_completed {
+			// Mark setup as completed in settings
+			app.settings.setup_completed = Some(true);
+			let _ = app.settings_store.save(&app.settings);
+			app.add_toast("Setup completed successfully!", egui::Color32::LIGHT_GREEN);
+		} else {
+			app.add_toast("Quick install cancelled", egui::Color32::YELLOW);
+		}
+	}
+
+	// Arrives just after the job's final progress line
+	if let Some(summary) = app.setup.summary_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+		app.setup.summary = Some(summary);
+		app.setup.summary_rx = None;
 	}
 
+	render_confirm_dialog(app, ui.ctx());
+
 	// Use a simpler approach: center vertically using available space
 	ui.allocate_ui_with_layout(
 		ui.available_size(),
@@ -88,6 +103,11 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 					ui.add(bar);
 					ui.add_space(10.0);
 					ui.label("This may take several minutes depending on your internet connection...");
+					ui.add_space(10.0);
+					let cancelling = app.setup.cancel.as_ref().map(|t| t.is_cancelled()).unwrap_or(false);
+					if ui.add_enabled(!cancelling, egui::Button::new(if cancelling { "Cancelling..." } else { "Cancel" })).clicked() {
+						if let Some(t) = &app.setup.cancel { t.cancel(); }
+					}
 				} else if is_returning_user {
 					// Returning user with completed setup
 					ui.colored_label(egui::Color32::LIGHT_GREEN, 
@@ -106,17 +126,19 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 						});
 					});
 					ui.add_space(20.0);
+					render_verify_section(app, ui);
 					
 					// Offer reinstall option
 					ui.separator();
 					ui.add_space(15.0);
 					ui.label(egui::RichText::new("Need to reinstall?").size(16.0));
 					ui.add_space(10.0);
-					if ui.add_sized([200.0, 35.0], 
+					crate::app::job_lock_notice(ui);
+					if ui.add_enabled_ui(JobLock::holder().is_none(), |ui| ui.add_sized([200.0, 35.0], 
 						egui::Button::new(egui::RichText::new("Reinstall Garry's Mod RTX").size(14.0))
 							.rounding(egui::Rounding::same(6.0))
-					).clicked() {
-						start_quick_install(app);
+					)).inner.clicked() {
+						request_quick_install(app);
 					}
 				} else if app.setup.setup_completed {
 					ui.colored_label(egui::Color32::LIGHT_GREEN, 
@@ -124,6 +146,7 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 					ui.add_space(10.0);
 					ui.label("Garry's Mod RTX has been successfully installed and configured.");
 					ui.add_space(15.0);
+					render_install_summary(app, ui);
 					ui.label("You can now:");
 					ui.add_space(5.0);
 					ui.horizontal(|ui| {
@@ -134,6 +157,8 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 							ui.label("• Mount content from other games in the Mounting tab");
 						});
 					});
+					ui.add_space(20.0);
+					render_verify_section(app, ui);
 				} else {
 					// First-time setup prompt
 					ui.label(egui::RichText::new("Would you like to run the quick install process?").size(18.0));
@@ -173,11 +198,11 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 						let offset = (available_width - total_width) / 2.0;
 						ui.add_space(offset);
 						
-						if ui.add_sized([button_width, button_height], 
+						if ui.add_enabled_ui(JobLock::holder().is_none(), |ui| ui.add_sized([button_width, button_height], 
 							egui::Button::new(egui::RichText::new("Quick Install").size(16.0))
 								.rounding(egui::Rounding::same(8.0))
-						).clicked() {
-							start_quick_install(app);
+						)).inner.clicked() {
+							request_quick_install(app);
 						}
 						
 						ui.add_space(spacing);
@@ -199,123 +224,150 @@ pub fn render_setup_tab(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
 	);
 }
 
-fn start_quick_install(app: &mut crate::app::LauncherApp) {
+// "Verify Install" button plus the list of problems it found
+fn render_verify_section(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
+	if ui.button("Verify Install").clicked() {
+		let vanilla = app.settings.manually_specified_install_path.clone().map(std::path::PathBuf::from).or_else(detect_gmod_install_folder);
+		app.setup.verify_issues = Some(match vanilla {
+			Some(v) => verify_install(&app.settings.install_dir(), &v),
+			None => vec![VerifyIssue { path: Default::default(), problem: "Garry's Mod install not found".into(), fix: "Set the Garry's Mod path in Settings".into() }],
+		});
+	}
+	match &app.setup.verify_issues {
+		Some(issues) if issues.is_empty() => { ui.colored_label(egui::Color32::from_rgb(0,200,0), "No problems found"); }
+		Some(issues) => {
+			ui.colored_label(egui::Color32::from_rgb(200,140,0), format!("{} problem(s) found", issues.len()));
+			for i in issues {
+				ui.label(format!("{} — {}", i.problem, i.path.display()));
+				ui.small(format!("Fix: {}", i.fix));
+			}
+		}
+		None => {}
+	}
+}
+
+// What the finished quick install did, with a shortcut to start the game
+fn render_install_summary(app: &mut crate::app::LauncherApp, ui: &mut egui::Ui) {
+	let Some(s) = &app.setup.summary else { return; };
+	let can_launch = !app.any_running();
+	let mut launch = false;
+	egui::Frame::group(ui.style()).show(ui, |ui| {
+		ui.label(egui::RichText::new("Install summary").size(16.0).strong());
+		ui.add_space(5.0);
+		let or_skipped = |v: &Option<String>| v.clone().unwrap_or_else(|| "skipped".to_string());
+		egui::Grid::new("install_summary").num_columns(2).spacing([16.0, 4.0]).show(ui, |ui| {
+			ui.label("RTX Remix"); ui.label(or_skipped(&s.remix_version)); ui.end_row();
+			ui.label("Fixes"); ui.label(or_skipped(&s.fixes_version)); ui.end_row();
+			ui.label("Patches");
+			ui.label(match &s.patch_source { Some(src) => format!("{} ({} file(s) patched)", src, s.files_patched), None => "skipped".to_string() });
+			ui.end_row();
+			if let Some(base) = &s.base {
+				ui.label("Files copied");
+				ui.label(format!("{} ({})", base.copied_files, humansize::format_size(base.copied_bytes, humansize::BINARY)));
+				ui.end_row();
+			}
+			ui.label("Warnings");
+			if s.warnings == 0 { ui.label("none"); } else { ui.colored_label(egui::Color32::from_rgb(200,140,0), format!("{} (see the Logs tab)", s.warnings)); }
+			ui.end_row();
+		});
+		ui.add_space(8.0);
+		launch = ui.add_enabled(can_launch, egui::Button::new("Launch now")).clicked();
+	});
+	ui.add_space(15.0);
+	if launch { app.launch_from_ui(); }
+}
+
+/// Whether the tab is showing the first-time "Quick Install / Skip for Now" prompt.
+pub fn shows_install_prompt(app: &crate::app::LauncherApp) -> bool {
+	!app.setup.is_running && !app.setup.setup_completed && app.settings.setup_completed != Some(true)
+}
+
+/// Build the plan and measure the space it needs, then ask before starting.
+pub fn request_quick_install(app: &mut crate::app::LauncherApp) {
 	let vanilla_opt = app.settings.manually_specified_install_path.clone()
 		.or_else(|| detect_gmod_install_folder().map(|p| p.display().to_string()));
 	
 	if let Some(vanilla) = vanilla_opt {
-		if let Ok(exec_dir) = std::env::current_exe().map(|p| p.parent().unwrap().to_path_buf()) {
-			let plan = InstallPlan { 
-				vanilla: std::path::PathBuf::from(vanilla), 
-				rtx: exec_dir.clone() 
-			};
-			
-			let (tx, rx) = std::sync::mpsc::channel::<JobProgress>();
-			app.setup.current_job = Some(rx);
-			app.setup.is_running = true;
-			
-			// Use default source indices (first option for each)
-			let remix_source_idx = 0;
-			let remix_release_idx = 0;
-			let fixes_source_idx = 0;
-			let fixes_release_idx = 0;
-			let patch_source_idx = 0;
-			
-			let settings_store = app.settings_store.clone();
-			let mut settings = app.settings.clone();
-			
-			std::thread::spawn(move || {
-				let tx_clone = tx.clone();
-				let report = |m: &str, p: u8| { 
-					let _ = tx_clone.send(JobProgress { 
-						message: m.to_string(), 
-						percent: p 
-					}); 
-				};
-				
-				report("Preparing installation...", 2);
-				let tx_clone2 = tx.clone();
-				let _ = perform_basic_install(&plan, |msg, pct| { 
-					let scaled = 0 + ((pct as u16 * 25) / 100) as u8; 
-					let _ = tx_clone2.send(JobProgress { 
-						message: msg.to_string(), 
-						percent: scaled 
-					}); 
-				});
-				
-				let rt = tokio::runtime::Runtime::new().unwrap();
-				rt.block_on(async move {
-					// Install RTX Remix
-					report("Downloading RTX Remix...", 25);
-					let remix_sources: [(&str, &str); 2] = [("sambow23", "dxvk-remix-gmod"), ("NVIDIAGameWorks", "rtx-remix")];
-					let (owner_r, repo_r) = remix_sources[remix_source_idx.min(1)];
-					let mut rl = GitHubRateLimit::default();
-					let remix_list = fetch_releases(owner_r, repo_r, &mut rl).await.unwrap_or_default();
-					if !remix_list.is_empty() {
-						let rel = remix_list[remix_release_idx.min(remix_list.len()-1)].clone();
-						let base = exec_dir.clone();
-						let result = install_remix_from_release(&rel, &base, |m,p| { 
-							let scaled = 25 + ((p as u16 * 35) / 100) as u8; 
-							let _ = tx.send(JobProgress { 
-								message: m.to_string(), 
-								percent: scaled 
-							}); 
-						}).await;
-						if result.is_ok() {
-							let rel_name = rel.name.unwrap_or_else(|| rel.tag_name.unwrap_or_default());
-							settings.installed_remix_version = Some(rel_name);
-						}
-					}
-					
-					// Install fixes
-					report("Installing community fixes...", 60);
-					let fixes_sources: [(&str, &str); 2] = [("Xenthio", "gmod-rtx-fixes-2"), ("Xenthio", "RTXFixes")];
-					let (owner_f, repo_f) = fixes_sources[fixes_source_idx.min(1)];
-					let mut rl2 = GitHubRateLimit::default();
-					let fixes_list = fetch_releases(owner_f, repo_f, &mut rl2).await.unwrap_or_default();
-					if !fixes_list.is_empty() {
-						let rel = fixes_list[fixes_release_idx.min(fixes_list.len()-1)].clone();
-						let base = exec_dir.clone();
-						let result = install_fixes_from_release(&rel, &base, Some(crate::app::DEFAULT_IGNORE_PATTERNS), |m,p| { 
-							let scaled = 60 + ((p as u16 * 25) / 100) as u8; 
-							let _ = tx.send(JobProgress { 
-								message: m.to_string(), 
-								percent: scaled 
-							}); 
-						}).await;
-						if result.is_ok() {
-							let rel_name = rel.name.unwrap_or_else(|| rel.tag_name.unwrap_or_default());
-							settings.installed_fixes_version = Some(rel_name);
-						}
-					}
-					
-					// Apply patches
-					report("Applying binary patches...", 85);
-					let patch_sources: [(&str, &str); 3] = [("sambow23", "SourceRTXTweaks"), ("BlueAmulet", "SourceRTXTweaks"), ("Xenthio", "SourceRTXTweaks")];
-					let (owner_p, repo_p) = patch_sources[patch_source_idx.min(2)];
-					let base = exec_dir.clone();
-					let result = apply_patches_from_repo(owner_p, repo_p, "applypatch.py", &base, |m,p| { 
-						let scaled = 85 + ((p as u16 * 15) / 100) as u8; 
-						let _ = tx.send(JobProgress { 
-							message: m.to_string(), 
-							percent: scaled.min(99) 
-						}); 
-					}).await;
-					if result.is_ok() {
-						let patch_info = format!("{}/{}", owner_p, repo_p);
-						settings.installed_patches_commit = Some(patch_info);
-					}
-					
-					// Save settings with all version information
-					let _ = settings_store.save(&settings);
-					let _ = tx.send(JobProgress { 
-						message: "Setup complete! RTX Remix is ready to use.".into(), 
-						percent: 100 
-					});
-				});
-			});
-		}
+		let plan = InstallPlan { 
+			vanilla: std::path::PathBuf::from(vanilla), 
+			rtx: app.settings.install_dir(),
+			link_strategy: app.settings.link_strategy,
+		};
+		let space = check_install_space(&plan);
+		app.setup.pending_install = Some((plan, space));
 	} else {
 		app.show_error_modal = Some("Could not detect Garry's Mod installation. Please specify the installation path in Settings first.".to_string());
 	}
 }
+
+fn render_confirm_dialog(app: &mut crate::app::LauncherApp, ctx: &egui::Context) {
+	let Some((plan, space)) = &app.setup.pending_install else { return; };
+	let mut start = false;
+	let mut close = false;
+	egui::Window::new("Start Quick Install?").collapsible(false).resizable(false).show(ctx, |ui| {
+		ui.label(format!("Install to: {}", plan.rtx.display()));
+		ui.label(format!("Copy from: {}", plan.vanilla.display()));
+		ui.add_space(6.0);
+		ui.label(format!("Space needed: about {} (including downloads)", humansize::format_size(space.required, humansize::BINARY)));
+		match space.available {
+			Some(free) => { ui.label(format!("Free on target drive: {}", humansize::format_size(free, humansize::BINARY))); }
+			None => { ui.colored_label(egui::Color32::YELLOW, "Could not read the free space on the target drive"); }
+		}
+		if !space.is_enough() {
+			ui.add_space(6.0);
+			ui.colored_label(egui::Color32::RED, "Not enough free space. Free up room on the drive or choose another install folder in Settings.");
+		}
+		ui.add_space(8.0);
+		ui.horizontal(|ui| {
+			if ui.add_enabled(space.is_enough() && JobLock::holder().is_none(), egui::Button::new("Start")).clicked() { start = true; }
+			if ui.button("Cancel").clicked() { close = true; }
+		});
+	});
+	if start {
+		if let Some((plan, _)) = app.setup.pending_install.take() { start_quick_install(app, plan); }
+	} else if close {
+		app.setup.pending_install = None;
+	}
+}
+
+pub(crate) fn start_quick_install(app: &mut crate::app::LauncherApp, plan: InstallPlan) {
+	let lock = match JobLock::acquire("Quick install") {
+		Ok(lock) => lock,
+		Err(e) => return app.show_launcher_error(&e),
+	};
+	app.last_job = Some(crate::app::RetryJob::QuickInstall(plan.clone()));
+	let (failure_tx, failure_rx) = std::sync::mpsc::channel();
+	app.setup.failure_rx = Some(failure_rx);
+	app.setup.is_running = true;
+	app.setup.progress = 0;
+	app.setup.summary = None;
+	let (summary_tx, summary_rx) = std::sync::mpsc::channel();
+	app.setup.summary_rx = Some(summary_rx);
+	let cancel = app.runtime.cancel_token();
+	app.setup.cancel = Some(cancel.clone());
+	
+	let settings_store = app.settings_store.clone();
+	let mut settings = app.settings.clone();
+	let lists = app.sources.clone();
+	
+	app.setup.current_job = Some(crate::app::spawn_job(&app.runtime, move |tx| async move {
+		let _lock = lock;
+		let report = |m: &str, p: u8| { let _ = tx.send(JobProgress { message: m.to_string(), percent: p }); };
+		// Use the first source for each step
+		let result = run_quick_install(Some(&plan), QuickInstallSources::default(), &lists, &mut settings, &cancel, report).await;
+		// Save version information for whatever finished, even on failure
+		let _ = settings_store.save(&settings);
+		match result {
+			Ok(summary) => { let _ = summary_tx.send(summary); }
+			Err(e) => {
+				let error = launcher_error(&e);
+				if error.is_some_and(|l| l.is_cancelled()) {
+					report("Quick install cancelled", 100);
+				} else {
+					let _ = failure_tx.send((format!("{:#}", e), error.and_then(|l| l.hint())));
+					report(&format!("Quick install failed: {:#}", e), 100);
+				}
+			}
+		}
+	}));
+}